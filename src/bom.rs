@@ -0,0 +1,40 @@
+//! Generates bill-of-materials files.
+
+use super::features::NamedInfo;
+use std::collections::BTreeMap;
+
+/// Writes a row for each named feature, followed by a row for each
+/// type of feature with the number of times it appears unnamed.
+pub fn serialize<W: std::io::Write>(
+    named: &[NamedInfo],
+    parts: &[&'static str],
+    w: &mut W,
+) -> Result<(), std::io::Error> {
+    w.write_all(b"Reference,Type,X,Y,Quantity\n")?;
+
+    for info in named {
+        let center = info.bounds.center();
+        w.write_all(
+            format!(
+                "{},{},{:.4},{:.4},1\n",
+                info.name, info.kind, center.x, center.y
+            )
+            .as_bytes(),
+        )?;
+    }
+
+    let mut counts = parts.iter().fold(BTreeMap::new(), |mut acc, p| {
+        *acc.entry(*p).or_insert(0usize) += 1;
+        acc
+    });
+    // Named features already have a row of their own.
+    for info in named {
+        if let Some(count) = counts.get_mut(info.kind) {
+            *count = count.saturating_sub(1);
+        }
+    }
+    for (kind, count) in counts.into_iter().filter(|(_, count)| *count > 0) {
+        w.write_all(format!(",{},,,{}\n", kind, count).as_bytes())?;
+    }
+    Ok(())
+}
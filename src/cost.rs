@@ -0,0 +1,44 @@
+//! Rough estimation of fabrication costs.
+
+use super::Layer;
+use std::collections::HashMap;
+
+/// Describes how a fab house prices boards.
+#[derive(Debug, Clone)]
+pub struct ManufacturingTariff {
+    /// Cost per square millimeter of board area.
+    pub base_mm2: f64,
+    /// Cost of each drill hit.
+    pub drill_per_hole: f64,
+    /// Additional cost for any layer with content.
+    pub layer_surcharge: HashMap<Layer, f64>,
+}
+
+impl ManufacturingTariff {
+    /// Approximate pricing (in USD) for prototype quantities at JLCPCB,
+    /// as of 2024.
+    pub fn jlcpcb_2024() -> Self {
+        let mut layer_surcharge = HashMap::new();
+        layer_surcharge.insert(Layer::FrontCopper, 0.5);
+        layer_surcharge.insert(Layer::BackCopper, 0.5);
+        layer_surcharge.insert(Layer::FrontMask, 0.2);
+        layer_surcharge.insert(Layer::BackMask, 0.2);
+        layer_surcharge.insert(Layer::FrontLegend, 0.1);
+        layer_surcharge.insert(Layer::BackLegend, 0.1);
+
+        Self {
+            base_mm2: 0.0002,
+            drill_per_hole: 0.001,
+            layer_surcharge,
+        }
+    }
+}
+
+/// The breakdown of an estimated fabrication cost.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CostEstimate {
+    pub base: f64,
+    pub drills: f64,
+    pub layers: f64,
+    pub total: f64,
+}
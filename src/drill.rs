@@ -1,32 +1,126 @@
 //! Generates drill files.
 
 use super::InnerAtom;
+use geo::Coordinate;
 use std::collections::HashMap;
 
+/// How slots are described in drill files.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SlotFormat {
+    /// Each slot is a single canned G85 command.
+    G85,
+    /// Each slot is routed, plunging the tool at the start of the
+    /// slot and retracting it at the end.
+    Routed,
+}
+
+/// The units used for coordinates and tool sizes in drill files.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DrillUnits {
+    Inches,
+    Millimeters,
+}
+
+impl Default for DrillUnits {
+    fn default() -> Self {
+        DrillUnits::Inches
+    }
+}
+
+impl std::str::FromStr for DrillUnits {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "in" | "inch" | "inches" => Ok(DrillUnits::Inches),
+            "mm" | "metric" | "millimeters" => Ok(DrillUnits::Millimeters),
+            _ => Err(format!("no such drill units: {}", s)),
+        }
+    }
+}
+
+impl DrillUnits {
+    /// Formats a length in millimeters in these units.
+    fn fmt(&self, mm: f64) -> String {
+        match self {
+            DrillUnits::Inches => format!("{:.4}", mm / 25.4),
+            DrillUnits::Millimeters => format!("{:.3}", mm),
+        }
+    }
+}
+
+/// Returns the diameter of the tool and the endpoints of each drill
+/// hit and slot, with the end point only present for slots.
+fn hits(
+    features: &Vec<InnerAtom>,
+    want_plated: bool,
+) -> Vec<(f64, Coordinate<f64>, Option<Coordinate<f64>>)> {
+    features
+        .iter()
+        .filter_map(|f| match f {
+            InnerAtom::Drill {
+                center,
+                radius,
+                plated,
+            } if *plated == want_plated => Some((radius * 2.0, *center, None)),
+            InnerAtom::Slot {
+                start,
+                end,
+                width,
+                plated,
+            } if *plated == want_plated => Some((*width, *start, Some(*end))),
+            _ => None,
+        })
+        .collect()
+}
+
+fn write_slot<W: std::io::Write>(
+    w: &mut W,
+    format: SlotFormat,
+    start: String,
+    end: String,
+) -> Result<(), std::io::Error> {
+    match format {
+        SlotFormat::G85 => {
+            w.write_all(format!("{}G85{}\n", start, end).as_bytes())?;
+            w.write_all(b"G05\n") // Back to drill mode
+        }
+        SlotFormat::Routed => {
+            w.write_all(format!("G00{}\n", start).as_bytes())?;
+            w.write_all(b"M15\n")?; // Plunge the tool
+            w.write_all(format!("G01{}\n", end).as_bytes())?;
+            w.write_all(b"M16\n")?; // Retract the tool
+            w.write_all(b"G05\n") // Back to drill mode
+        }
+    }
+}
+
 pub fn serialize<W: std::io::Write>(
     features: &Vec<InnerAtom>,
     w: &mut W,
     want_plated: bool,
+    slot_format: SlotFormat,
+    units: DrillUnits,
 ) -> Result<(), std::io::Error> {
     w.write(b"M48\n")?; // Start of header
     w.write(b";DRILL file {KiCad 5.0.2 compatible}\n")?;
-    w.write(b";FORMAT={-:-/ absolute / inch / decimal}\n")?;
-    w.write(b"FMAT,2\n")?; // Uses format 2 commands
-    w.write(b"INCH,TZ\n")?; // Units are inches, trailing zeroes included.
+    match units {
+        DrillUnits::Inches => {
+            w.write(b";FORMAT={-:-/ absolute / inch / decimal}\n")?;
+            w.write(b"FMAT,2\n")?; // Uses format 2 commands
+            w.write(b"INCH,TZ\n")?; // Units are inches, trailing zeroes included.
+        }
+        DrillUnits::Millimeters => {
+            w.write(b";FORMAT={-:-/ absolute / metric / decimal}\n")?;
+            w.write(b"FMAT,2\n")?; // Uses format 2 commands
+            w.write(b"METRIC,LZ\n")?; // Units are millimeters, leading zeroes included.
+        }
+    }
 
+    let hits = hits(features, want_plated);
     let mut circle_dia = HashMap::new();
-    for f in features {
-        if let InnerAtom::Drill {
-            center: _,
-            radius,
-            plated,
-        } = f
-        {
-            if want_plated == *plated {
-                let dia_inches = format!("{:.4}", radius * 2.0 / 25.4);
-                circle_dia.insert(dia_inches, ());
-            }
-        }
+    for (dia, _, _) in &hits {
+        circle_dia.insert(units.fmt(*dia), ());
     }
     let circle_tools: Vec<_> = circle_dia.keys().enumerate().collect();
     for (i, c) in &circle_tools {
@@ -38,28 +132,18 @@ pub fn serialize<W: std::io::Write>(
     w.write(b"G05\n")?; // Turn on drill mode
 
     let mut current_tool: Option<usize> = None;
-    for f in features {
-        if let InnerAtom::Drill {
-            center,
-            radius,
-            plated,
-        } = f
-        {
-            if want_plated == *plated {
-                let dia_inches = format!("{:.4}", radius * 2.0 / 25.4);
-                let tool_idx = circle_tools
-                    .iter()
-                    .find(|&&(_, dia)| *dia == dia_inches)
-                    .unwrap()
-                    .0;
-                if current_tool != Some(tool_idx + 1) {
-                    w.write(format!("T{}\n", tool_idx + 1).as_bytes())?;
-                    current_tool = Some(tool_idx + 1);
-                }
+    for (dia, start, end) in &hits {
+        let dia = units.fmt(*dia);
+        let tool_idx = circle_tools.iter().find(|&&(_, d)| *d == dia).unwrap().0;
+        if current_tool != Some(tool_idx + 1) {
+            w.write(format!("T{}\n", tool_idx + 1).as_bytes())?;
+            current_tool = Some(tool_idx + 1);
+        }
 
-                let (x, y) = (center.x / 25.4, center.y / 25.4);
-                w.write(format!("X{:.4}Y{:.4}\n", x, y).as_bytes())?;
-            }
+        let pos = |c: &Coordinate<f64>| format!("X{}Y{}", units.fmt(c.x), units.fmt(c.y));
+        match end {
+            Some(end) => write_slot(w, slot_format, pos(start), pos(end))?,
+            None => w.write_all(format!("{}\n", pos(start)).as_bytes())?,
         }
     }
 
@@ -68,6 +152,127 @@ pub fn serialize<W: std::io::Write>(
     Ok(())
 }
 
+/// Writes a human-readable summary of the number of drill hits of
+/// each size. Slots are counted by their width, and the total length
+/// routed by the slots of each size is reported, or n/a if there are none.
+pub fn serialize_report<W: std::io::Write>(
+    features: &Vec<InnerAtom>,
+    w: &mut W,
+    want_plated: bool,
+) -> Result<(), std::io::Error> {
+    let mut sizes: Vec<(String, usize, Option<f64>)> = Vec::new();
+    for (dia, start, end) in hits(features, want_plated) {
+        let dia = format!("{:.3}", dia);
+        let length = end.map(|end| (end.x - start.x).hypot(end.y - start.y));
+        match sizes.iter_mut().find(|(d, _, _)| *d == dia) {
+            Some((_, count, total)) => {
+                *count += 1;
+                if let Some(l) = length {
+                    *total = Some(total.unwrap_or(0.) + l);
+                }
+            }
+            None => sizes.push((dia, 1, length)),
+        }
+    }
+    sizes.sort_by(|a, b| {
+        let (a, b): (f64, f64) = (a.0.parse().unwrap(), b.0.parse().unwrap());
+        a.partial_cmp(&b).unwrap()
+    });
+
+    let kind = if want_plated { "PTH" } else { "NPTH" };
+    for (dia, count, length) in &sizes {
+        let length = match length {
+            Some(l) => format!("{:.3}mm", l),
+            None => "n/a".to_string(),
+        };
+        w.write_all(
+            format!(
+                "Diameter: {}mm, Count: {}, Type: {}, Length: {}\n",
+                dia, count, kind, length
+            )
+            .as_bytes(),
+        )?;
+    }
+    w.write_all(
+        format!(
+            "Total: {} drills in {} sizes\n",
+            sizes.iter().map(|(_, c, _)| c).sum::<usize>(),
+            sizes.len()
+        )
+        .as_bytes(),
+    )?;
+    Ok(())
+}
+
+/// Tolerance of the finished hole size, in millimeters.
+const TOLERANCE: f64 = 0.05;
+
+/// Serializes an Excellon format 2 drill file, in metric units, with a
+/// tool table describing the size, tolerance, and number of hits for
+/// each tool. Tools are numbered in order of increasing size.
+pub fn serialize_v2<W: std::io::Write>(
+    features: &Vec<InnerAtom>,
+    w: &mut W,
+    want_plated: bool,
+    slot_format: SlotFormat,
+) -> Result<(), std::io::Error> {
+    let hits: Vec<_> = hits(features, want_plated)
+        .into_iter()
+        .map(|(dia, start, end)| (format!("{:.3}", dia), start, end))
+        .collect();
+
+    let mut tools: Vec<(String, usize)> = Vec::new();
+    for (dia, _, _) in &hits {
+        match tools.iter_mut().find(|(d, _)| d == dia) {
+            Some((_, count)) => *count += 1,
+            None => tools.push((dia.clone(), 1)),
+        }
+    }
+    tools.sort_by(|a, b| {
+        let (a, b): (f64, f64) = (a.0.parse().unwrap(), b.0.parse().unwrap());
+        a.partial_cmp(&b).unwrap()
+    });
+
+    w.write_all(b"M48\n")?; // Start of header
+    w.write_all(b";FORMAT={-:-/ absolute / metric / decimal}\n")?;
+    w.write_all(b"FMAT,2\n")?; // Uses format 2 commands
+    w.write_all(b"METRIC,TZ\n")?; // Units are millimeters, trailing zeroes included.
+    for (i, (dia, count)) in tools.iter().enumerate() {
+        w.write_all(
+            format!(
+                ";Holesize {} = {} Tolerance = +{:.3}/-{:.3} {} MM Quantity = {}\n",
+                i + 1,
+                dia,
+                TOLERANCE,
+                TOLERANCE,
+                if want_plated { "PLATED" } else { "NON_PLATED" },
+                count
+            )
+            .as_bytes(),
+        )?;
+        w.write_all(format!("T{}C{}\n", i + 1, dia).as_bytes())?;
+    }
+    w.write_all(b"%\n")?; // Rewind, used instead of end of header M95.
+
+    w.write_all(b"G90\n")?; // Absolute mode
+    w.write_all(b"G05\n")?; // Turn on drill mode
+
+    for (i, (dia, _)) in tools.iter().enumerate() {
+        w.write_all(format!("T{}\n", i + 1).as_bytes())?;
+        for (_, start, end) in hits.iter().filter(|(d, _, _)| d == dia) {
+            let pos = |c: &Coordinate<f64>| format!("X{:.3}Y{:.3}", c.x, c.y);
+            match end {
+                Some(end) => write_slot(w, slot_format, pos(start), pos(end))?,
+                None => w.write_all(format!("{}\n", pos(start)).as_bytes())?,
+            }
+        }
+    }
+
+    w.write_all(b"T0\n")?; // Remove tool from spindle.
+    w.write_all(b"M30\n")?; // End of file (last line)
+    Ok(())
+}
+
 // FMAT,2
 // INCH,TZ
 // T1C0.1220
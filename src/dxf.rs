@@ -0,0 +1,85 @@
+//! Generates DXF drawings of the panel outline, for use in mechanical CAD.
+
+use super::InnerAtom;
+use geo::{LineString, Polygon, Rect};
+
+fn pair<W: std::io::Write>(w: &mut W, code: u16, value: &str) -> Result<(), std::io::Error> {
+    w.write_all(format!("{:>3}\n{}\n", code, value).as_bytes())
+}
+
+fn polyline<W: std::io::Write>(w: &mut W, ring: &LineString<f64>) -> Result<(), std::io::Error> {
+    // Rings repeat their first point at the end, which the closed
+    // flag makes redundant.
+    let points = &ring.0[..ring.0.len().saturating_sub(1)];
+
+    pair(w, 0, "LWPOLYLINE")?;
+    pair(w, 8, "0")?;
+    pair(w, 90, &points.len().to_string())?;
+    pair(w, 70, "1")?; // Closed
+    for p in points {
+        pair(w, 10, &p.x.to_string())?;
+        pair(w, 20, &p.y.to_string())?;
+    }
+    Ok(())
+}
+
+fn line<W: std::io::Write>(
+    w: &mut W,
+    layer: &str,
+    start: (f64, f64),
+    end: (f64, f64),
+) -> Result<(), std::io::Error> {
+    pair(w, 0, "LINE")?;
+    pair(w, 8, layer)?;
+    pair(w, 10, &start.0.to_string())?;
+    pair(w, 20, &start.1.to_string())?;
+    pair(w, 30, "0")?;
+    pair(w, 11, &end.0.to_string())?;
+    pair(w, 21, &end.1.to_string())?;
+    pair(w, 31, "0")
+}
+
+/// Writes a minimal ASCII DXF file, in millimeters. The outline is drawn
+/// on layer `0`, drill hits on the `DRILLS` layer, and v-score lines
+/// across the extent of the outline on the `VSCORE` layer.
+pub fn serialize<W: std::io::Write>(
+    edge: &Polygon<f64>,
+    bounds: Rect<f64>,
+    atoms: &[InnerAtom],
+    w: &mut W,
+) -> Result<(), std::io::Error> {
+    pair(w, 0, "SECTION")?;
+    pair(w, 2, "HEADER")?;
+    pair(w, 9, "$INSUNITS")?;
+    pair(w, 70, "4")?; // Millimeters
+    pair(w, 0, "ENDSEC")?;
+
+    pair(w, 0, "SECTION")?;
+    pair(w, 2, "ENTITIES")?;
+    polyline(w, edge.exterior())?;
+    for ring in edge.interiors() {
+        polyline(w, ring)?;
+    }
+
+    for a in atoms {
+        match a {
+            InnerAtom::Drill { center, radius, .. } => {
+                pair(w, 0, "CIRCLE")?;
+                pair(w, 8, "DRILLS")?;
+                pair(w, 10, &center.x.to_string())?;
+                pair(w, 20, &center.y.to_string())?;
+                pair(w, 30, "0")?;
+                pair(w, 40, &radius.to_string())?;
+            }
+            InnerAtom::VScoreH(y) => {
+                line(w, "VSCORE", (bounds.min().x, *y), (bounds.max().x, *y))?;
+            }
+            InnerAtom::VScoreV(x) => {
+                line(w, "VSCORE", (*x, bounds.min().y), (*x, bounds.max().y))?;
+            }
+            _ => {}
+        }
+    }
+    pair(w, 0, "ENDSEC")?;
+    pair(w, 0, "EOF")
+}
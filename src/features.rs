@@ -5,43 +5,90 @@ use geo::{Coordinate, MultiPolygon};
 use std::fmt;
 
 mod array;
+mod bezier;
+mod castellation;
 mod circle;
+mod circular_array;
+mod drill_feature;
+mod ellipse;
+mod fiducial;
+mod gold_finger;
+mod grid;
+mod intersect;
 mod mechanical_solder_point;
+mod mirror;
+mod mouse_bite;
 mod named;
 mod negative;
+mod offset;
+mod outline;
+mod plated_slot;
+mod poly_shape;
 mod pos;
 mod r_mount;
 mod rect;
+mod regular_polygon;
 pub mod repeating;
 mod rotate;
 mod screw_hole;
+mod silkscreen_line;
+mod silkscreen_text;
+mod smd_pad;
 mod smiley;
+mod snap;
+mod tab;
+mod trapezoid;
 mod triangle;
 mod unit;
-pub use array::Column;
+mod via_hole;
+pub use array::{Column, Row};
+pub use bezier::BezierCurve;
+pub use castellation::Castellation;
 pub use circle::Circle;
+pub use circular_array::CircularArray;
+pub use drill_feature::Drill;
+pub use ellipse::Ellipse;
+pub use fiducial::Fiducial;
+pub use gold_finger::GoldFinger;
+pub use grid::Grid;
+pub use intersect::Intersect;
 pub use mechanical_solder_point::MechanicalSolderPoint;
+pub use mirror::{Mirror, MirrorAxis};
+pub use mouse_bite::MouseBite;
 pub use named::Named;
 pub use negative::Negative;
+pub use offset::Offset;
+pub use outline::Outline;
+pub use plated_slot::PlatedSlot;
+pub use poly_shape::PolyShape;
 pub use pos::{AtPos, Positioning};
 pub use r_mount::RMount;
 pub use rect::Rect;
+pub use regular_polygon::RegularPolygon;
 pub use rotate::Rotate;
 pub use screw_hole::ScrewHole;
+pub use silkscreen_line::SilkscreenLine;
+pub use silkscreen_text::SilkscreenText;
+pub use smd_pad::SMDPad;
 pub use smiley::Smiley;
+pub use snap::SnapToGrid;
+pub use tab::Tab;
+pub use trapezoid::Trapezoid;
 pub use triangle::Triangle;
 pub use unit::Unit;
+pub use via_hole::ViaHole;
 
 /// Describes named geometry.
 #[derive(Debug, Clone)]
 pub struct NamedInfo {
     pub name: String,
+    pub kind: &'static str,
     pub bounds: geo::Rect<f64>,
 }
 
 impl NamedInfo {
-    pub fn new(name: String, bounds: geo::Rect<f64>) -> Self {
-        NamedInfo { name, bounds }
+    pub fn new(name: String, kind: &'static str, bounds: geo::Rect<f64>) -> Self {
+        NamedInfo { name, kind, bounds }
     }
     pub fn translate(&mut self, x: f64, y: f64) {
         use geo::prelude::Translate;
@@ -104,6 +151,23 @@ pub trait Feature: fmt::Display + DynClone + fmt::Debug {
     fn named_info(&self) -> Vec<NamedInfo> {
         vec![]
     }
+
+    /// Returns the names of the features which make up this feature,
+    /// including any inner features. Used to produce a bill of materials.
+    fn parts(&self) -> Vec<&'static str> {
+        vec![self.name()]
+    }
+
+    /// Returns the names of this feature and all features it contains.
+    fn all_names(&self) -> Vec<&'static str> {
+        vec![self.name()]
+    }
+
+    /// Returns v-scores along the boundaries between repeated copies
+    /// of geometry, if the feature repeats any.
+    fn tile_boundaries(&self) -> Vec<InnerAtom> {
+        vec![]
+    }
 }
 
 dyn_clone::clone_trait_object!(Feature);
@@ -132,9 +196,24 @@ impl<'a> Feature for Box<dyn Feature + 'a> {
     fn named_info(&self) -> Vec<NamedInfo> {
         self.as_ref().named_info()
     }
+
+    fn parts(&self) -> Vec<&'static str> {
+        self.as_ref().parts()
+    }
+
+    fn all_names(&self) -> Vec<&'static str> {
+        self.as_ref().all_names()
+    }
+
+    fn tile_boundaries(&self) -> Vec<InnerAtom> {
+        self.as_ref().tile_boundaries()
+    }
 }
 
 /// The smallest geometries from which inner features are composed.
+///
+/// Equality and hashing treat all NaN values as equal to each other,
+/// so that atoms can be used as keys in sets and maps.
 #[derive(Debug, Clone)]
 pub enum InnerAtom {
     Drill {
@@ -142,6 +221,12 @@ pub enum InnerAtom {
         radius: f64,
         plated: bool,
     },
+    Slot {
+        start: Coordinate<f64>,
+        end: Coordinate<f64>,
+        width: f64,
+        plated: bool,
+    },
     Circle {
         center: Coordinate<f64>,
         radius: f64,
@@ -151,39 +236,223 @@ pub enum InnerAtom {
         rect: geo::Rect<f64>,
         layer: super::Layer,
     },
+    Line {
+        start: Coordinate<f64>,
+        end: Coordinate<f64>,
+        width: f64,
+        layer: super::Layer,
+    },
     VScoreH(f64),
     VScoreV(f64),
 }
 
+/// Compares two floats such that NaN equals NaN, consistent with
+/// [`hash_f64`].
+fn eq_f64(a: f64, b: f64) -> bool {
+    a == b || (a.is_nan() && b.is_nan())
+}
+
+fn eq_coord(a: &Coordinate<f64>, b: &Coordinate<f64>) -> bool {
+    eq_f64(a.x, b.x) && eq_f64(a.y, b.y)
+}
+
+impl PartialEq for InnerAtom {
+    fn eq(&self, other: &Self) -> bool {
+        use InnerAtom::*;
+        match (self, other) {
+            (
+                Drill {
+                    center: c1,
+                    radius: r1,
+                    plated: p1,
+                },
+                Drill {
+                    center: c2,
+                    radius: r2,
+                    plated: p2,
+                },
+            ) => eq_coord(c1, c2) && eq_f64(*r1, *r2) && p1 == p2,
+            (
+                Slot {
+                    start: s1,
+                    end: e1,
+                    width: w1,
+                    plated: p1,
+                },
+                Slot {
+                    start: s2,
+                    end: e2,
+                    width: w2,
+                    plated: p2,
+                },
+            ) => eq_coord(s1, s2) && eq_coord(e1, e2) && eq_f64(*w1, *w2) && p1 == p2,
+            (
+                Circle {
+                    center: c1,
+                    radius: r1,
+                    layer: l1,
+                },
+                Circle {
+                    center: c2,
+                    radius: r2,
+                    layer: l2,
+                },
+            ) => eq_coord(c1, c2) && eq_f64(*r1, *r2) && l1 == l2,
+            (
+                Rect {
+                    rect: r1,
+                    layer: l1,
+                },
+                Rect {
+                    rect: r2,
+                    layer: l2,
+                },
+            ) => eq_coord(&r1.min(), &r2.min()) && eq_coord(&r1.max(), &r2.max()) && l1 == l2,
+            (
+                Line {
+                    start: s1,
+                    end: e1,
+                    width: w1,
+                    layer: l1,
+                },
+                Line {
+                    start: s2,
+                    end: e2,
+                    width: w2,
+                    layer: l2,
+                },
+            ) => eq_coord(s1, s2) && eq_coord(e1, e2) && eq_f64(*w1, *w2) && l1 == l2,
+            (VScoreH(a), VScoreH(b)) | (VScoreV(a), VScoreV(b)) => eq_f64(*a, *b),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for InnerAtom {}
+
+/// Hashes a float consistently with [`eq_f64`], by mapping negative
+/// zero onto positive zero and every NaN onto a single value.
+fn hash_f64<H: std::hash::Hasher>(v: f64, state: &mut H) {
+    use std::hash::Hash;
+    let v = if v.is_nan() { f64::NAN } else { v + 0. };
+    v.to_bits().hash(state);
+}
+
+fn hash_coord<H: std::hash::Hasher>(c: &Coordinate<f64>, state: &mut H) {
+    hash_f64(c.x, state);
+    hash_f64(c.y, state);
+}
+
+impl std::hash::Hash for InnerAtom {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            InnerAtom::Drill {
+                center,
+                radius,
+                plated,
+            } => {
+                hash_coord(center, state);
+                hash_f64(*radius, state);
+                plated.hash(state);
+            }
+            InnerAtom::Slot {
+                start,
+                end,
+                width,
+                plated,
+            } => {
+                hash_coord(start, state);
+                hash_coord(end, state);
+                hash_f64(*width, state);
+                plated.hash(state);
+            }
+            InnerAtom::Circle {
+                center,
+                radius,
+                layer,
+            } => {
+                hash_coord(center, state);
+                hash_f64(*radius, state);
+                layer.hash(state);
+            }
+            InnerAtom::Rect { rect, layer } => {
+                hash_coord(&rect.min(), state);
+                hash_coord(&rect.max(), state);
+                layer.hash(state);
+            }
+            InnerAtom::Line {
+                start,
+                end,
+                width,
+                layer,
+            } => {
+                hash_coord(start, state);
+                hash_coord(end, state);
+                hash_f64(*width, state);
+                layer.hash(state);
+            }
+            InnerAtom::VScoreH(v) | InnerAtom::VScoreV(v) => hash_f64(*v, state),
+        }
+    }
+}
+
 impl InnerAtom {
     pub fn stroke(&self) -> Option<usvg::Stroke> {
-        match self {
-            InnerAtom::VScoreH(_) | InnerAtom::VScoreV(_) => Some(usvg::Stroke {
-                paint: usvg::Paint::Color(usvg::Color::new(0x6, 0x6, 0x6)),
-                width: usvg::StrokeWidth::new(0.1),
-                opacity: usvg::Opacity::new(0.5),
-                dasharray: Some(vec![0.8, 0.8]),
+        let (r, g, b) = self.stroke_color_rgb()?;
+        if let InnerAtom::Line { width, .. } = self {
+            return Some(usvg::Stroke {
+                paint: usvg::Paint::Color(usvg::Color::new(r, g, b)),
+                width: usvg::StrokeWidth::new(*width),
+                linecap: usvg::LineCap::Round,
                 ..usvg::Stroke::default()
-            }),
-            _ => None,
+            });
         }
+        Some(usvg::Stroke {
+            paint: usvg::Paint::Color(usvg::Color::new(r, g, b)),
+            width: usvg::StrokeWidth::new(0.1),
+            opacity: usvg::Opacity::new(0.5),
+            dasharray: Some(vec![0.8, 0.8]),
+            ..usvg::Stroke::default()
+        })
     }
 
     pub fn fill(&self) -> Option<usvg::Fill> {
+        let (r, g, b) = self.fill_color_rgb()?;
+        Some(usvg::Fill {
+            paint: usvg::Paint::Color(usvg::Color::new(r, g, b)),
+            ..usvg::Fill::default()
+        })
+    }
+
+    /// Returns the color the atom is stroked with when rendered, if any.
+    pub fn stroke_color_rgb(&self) -> Option<(u8, u8, u8)> {
         match self {
-            InnerAtom::Drill { .. } => Some(usvg::Fill {
-                paint: usvg::Paint::Color(usvg::Color::new(0x25, 0x25, 0x25)),
-                ..usvg::Fill::default()
-            }),
-            InnerAtom::Circle { layer, .. } => Some(usvg::Fill {
-                paint: usvg::Paint::Color(layer.color()),
-                ..usvg::Fill::default()
-            }),
-            InnerAtom::Rect { layer, .. } => Some(usvg::Fill {
-                paint: usvg::Paint::Color(layer.color()),
-                ..usvg::Fill::default()
-            }),
-            InnerAtom::VScoreH(_) | InnerAtom::VScoreV(_) => None,
+            InnerAtom::VScoreH(_) | InnerAtom::VScoreV(_) => Some((0x6, 0x6, 0x6)),
+            InnerAtom::Line { layer, .. } => Some(layer.rgb()),
+            _ => None,
+        }
+    }
+
+    /// Returns the color the atom is filled with when rendered, if any.
+    pub fn fill_color_rgb(&self) -> Option<(u8, u8, u8)> {
+        match self {
+            InnerAtom::Drill { .. } | InnerAtom::Slot { .. } => Some((0x25, 0x25, 0x25)),
+            InnerAtom::Circle { layer, .. } | InnerAtom::Rect { layer, .. } => Some(layer.rgb()),
+            InnerAtom::Line { .. } | InnerAtom::VScoreH(_) | InnerAtom::VScoreV(_) => None,
+        }
+    }
+
+    /// Returns the layer the atom is drawn on, if any.
+    pub fn layer(&self) -> Option<super::Layer> {
+        match self {
+            InnerAtom::Circle { layer, .. }
+            | InnerAtom::Rect { layer, .. }
+            | InnerAtom::Line { layer, .. } => Some(layer.clone()),
+            InnerAtom::Drill { .. }
+            | InnerAtom::Slot { .. }
+            | InnerAtom::VScoreH(_)
+            | InnerAtom::VScoreV(_) => None,
         }
     }
 
@@ -210,6 +479,21 @@ impl InnerAtom {
                 },
             )),
             InnerAtom::Rect { rect, .. } => Some(rect.clone()),
+            InnerAtom::Slot {
+                start, end, width, ..
+            }
+            | InnerAtom::Line {
+                start, end, width, ..
+            } => Some(geo::Rect::new(
+                Coordinate {
+                    x: start.x.min(end.x) - width / 2.,
+                    y: start.y.min(end.y) - width / 2.,
+                },
+                Coordinate {
+                    x: start.x.max(end.x) + width / 2.,
+                    y: start.y.max(end.y) + width / 2.,
+                },
+            )),
             InnerAtom::VScoreH(_) | InnerAtom::VScoreV(_) => None,
         }
     }
@@ -226,6 +510,10 @@ impl InnerAtom {
                 use geo::algorithm::translate::Translate;
                 rect.translate_inplace(x, y);
             }
+            InnerAtom::Slot { start, end, .. } | InnerAtom::Line { start, end, .. } => {
+                *start = *start + Coordinate { x, y };
+                *end = *end + Coordinate { x, y };
+            }
             InnerAtom::VScoreH(ref mut y2) => {
                 *y2 = *y2 + y;
             }
@@ -234,4 +522,150 @@ impl InnerAtom {
             }
         }
     }
+
+    /// Scales the atom about the origin. Circular atoms have their
+    /// radius, and slots and lines their width, scaled by the average of both factors.
+    pub fn scale(&mut self, sx: f64, sy: f64) {
+        match self {
+            InnerAtom::Drill { center, radius, .. } | InnerAtom::Circle { center, radius, .. } => {
+                center.x *= sx;
+                center.y *= sy;
+                *radius *= (sx + sy) / 2.0;
+            }
+            InnerAtom::Rect { rect, .. } => {
+                *rect = geo::Rect::new(
+                    Coordinate {
+                        x: rect.min().x * sx,
+                        y: rect.min().y * sy,
+                    },
+                    Coordinate {
+                        x: rect.max().x * sx,
+                        y: rect.max().y * sy,
+                    },
+                );
+            }
+            InnerAtom::Slot {
+                start, end, width, ..
+            }
+            | InnerAtom::Line {
+                start, end, width, ..
+            } => {
+                start.x *= sx;
+                start.y *= sy;
+                end.x *= sx;
+                end.y *= sy;
+                *width *= (sx + sy) / 2.0;
+            }
+            InnerAtom::VScoreH(y) => {
+                *y *= sy;
+            }
+            InnerAtom::VScoreV(x) => {
+                *x *= sx;
+            }
+        }
+    }
+
+    /// Scales the atom about the origin by the same factor in both axes.
+    pub fn scale_uniform(&mut self, factor: f64) {
+        self.scale(factor, factor)
+    }
+
+    /// Rotates the atom about the given point. Rectangles are replaced
+    /// by the bounds of the rotated rectangle, and v-scores are only
+    /// rotated in multiples of 90 degrees.
+    pub fn rotate_around(&mut self, degrees: f64, pivot: Coordinate<f64>) {
+        use geo::algorithm::rotate::RotatePoint;
+        let rotate = |c: Coordinate<f64>| -> Coordinate<f64> {
+            geo::Point::from(c)
+                .rotate_around_point(degrees, pivot.into())
+                .into()
+        };
+        // Whether lines along one axis end up along the other axis.
+        let swaps_axes = {
+            let quarter_turns = (degrees / 90.).round() as i64;
+            quarter_turns.rem_euclid(2) == 1
+        };
+
+        match self {
+            InnerAtom::Drill { center, .. } | InnerAtom::Circle { center, .. } => {
+                *center = rotate(*center);
+            }
+            InnerAtom::Rect { rect, .. } => {
+                let (min, max) = (rect.min(), rect.max());
+                let corners = [
+                    rotate(min),
+                    rotate(Coordinate { x: max.x, y: min.y }),
+                    rotate(max),
+                    rotate(Coordinate { x: min.x, y: max.y }),
+                ];
+                let fold = |f: fn(f64, f64) -> f64| {
+                    corners[1..].iter().fold(corners[0], |acc, c| Coordinate {
+                        x: f(acc.x, c.x),
+                        y: f(acc.y, c.y),
+                    })
+                };
+                *rect = geo::Rect::new(fold(f64::min), fold(f64::max));
+            }
+            InnerAtom::Slot { start, end, .. } | InnerAtom::Line { start, end, .. } => {
+                *start = rotate(*start);
+                *end = rotate(*end);
+            }
+            InnerAtom::VScoreH(y) => {
+                let p = rotate(Coordinate { x: pivot.x, y: *y });
+                *self = if swaps_axes {
+                    InnerAtom::VScoreV(p.x)
+                } else {
+                    InnerAtom::VScoreH(p.y)
+                };
+            }
+            InnerAtom::VScoreV(x) => {
+                let p = rotate(Coordinate { x: *x, y: pivot.y });
+                *self = if swaps_axes {
+                    InnerAtom::VScoreH(p.y)
+                } else {
+                    InnerAtom::VScoreV(p.x)
+                };
+            }
+        }
+    }
+}
+
+/// Writes the spec for a feature constructed about the origin, wrapped
+/// in a translation to the given offset when it has been moved.
+fn fmt_translated(
+    f: &mut fmt::Formatter,
+    offset: Coordinate<f64>,
+    spec: fmt::Arguments,
+) -> fmt::Result {
+    if offset.x == 0. && offset.y == 0. {
+        f.write_fmt(spec)
+    } else {
+        write!(f, "translate({}, {}) {{ {} }}", offset.x, offset.y, spec)
+    }
+}
+
+/// Writes each of the items separated by commas.
+fn fmt_list<T: fmt::Display>(
+    f: &mut fmt::Formatter,
+    items: impl IntoIterator<Item = T>,
+) -> fmt::Result {
+    for (i, item) in items.into_iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{}", item)?;
+    }
+    Ok(())
+}
+
+/// Returns the spec for an inner feature positioned relative to the
+/// given origin, including the surrounding parentheses. An empty string
+/// is returned when there is no inner feature.
+fn fmt_inner<U: InnerFeature + Clone>(inner: &U, origin: Coordinate<f64>) -> String {
+    let mut inner = inner.clone();
+    inner.translate(-origin);
+    match inner.to_string() {
+        s if s.is_empty() => s,
+        s => format!("({})", s),
+    }
 }
@@ -7,6 +7,8 @@ pub struct Column<U = super::Unit> {
     array: Vec<U>,
     align: crate::Align,
     bbox: bool,
+    separator_gap: Option<f64>,
+    gap: f64,
 }
 
 impl<U: super::Feature + fmt::Debug + Clone> Column<U> {
@@ -43,46 +45,45 @@ impl<U: super::Feature + fmt::Debug + Clone> Column<U> {
             align,
             array,
             bbox: true,
+            separator_gap: None,
+            gap: 0.,
         }
     }
 
-    fn all_bounds(&self) -> Vec<geo::Rect<f64>> {
-        self.array
-            .iter()
-            .map(|f| {
-                let add_b = match f.edge_union() {
-                    Some(edge) => {
-                        use geo::bounding_rect::BoundingRect;
-                        edge.bounding_rect()
-                    }
-                    None => None,
-                };
-                let sub_b = match f.edge_subtract() {
-                    Some(edge) => {
-                        use geo::bounding_rect::BoundingRect;
-                        edge.bounding_rect()
-                    }
-                    None => None,
-                };
+    /// Leaves a gap of `gap_mm` between each element.
+    pub fn with_gap(self, gap_mm: f64) -> Self {
+        Self {
+            gap: gap_mm,
+            ..self
+        }
+    }
 
-                match (add_b, sub_b) {
-                    (Some(b), None) => b,
-                    (None, Some(b)) => b,
-                    (Some(u), Some(s)) => {
-                        use geo::bounding_rect::BoundingRect;
-                        use geo_booleanop::boolean::BooleanOp;
-                        u.to_polygon()
-                            .union(&s.to_polygon())
-                            .bounding_rect()
-                            .unwrap()
-                    }
-                    (None, None) => geo::Rect::new(
-                        Coordinate::<f64> { x: 0., y: 0. },
-                        Coordinate::<f64> { x: 0., y: 0. },
-                    ),
+    /// Inserts a copy of `separator` between every pair of elements,
+    /// leaving a gap of `gap_mm` above and below each separator.
+    pub fn with_separator(mut self, mut separator: U, gap_mm: f64) -> Self {
+        if let Some(b) = separator.edge_union() {
+            use geo::bounding_rect::BoundingRect;
+            let v = b.bounding_rect().unwrap().min();
+            separator.translate(-v);
+        }
+
+        let n = self.array.len();
+        self.array = self.array.into_iter().enumerate().fold(
+            Vec::with_capacity(2 * n),
+            |mut acc, (i, e)| {
+                if i > 0 {
+                    acc.push(separator.clone());
                 }
-            })
-            .collect()
+                acc.push(e);
+                acc
+            },
+        );
+        self.separator_gap = Some(gap_mm);
+        self
+    }
+
+    fn all_bounds(&self) -> Vec<geo::Rect<f64>> {
+        all_bounds(&self.array)
     }
 
     fn largest(&self) -> geo::Rect<f64> {
@@ -99,9 +100,17 @@ impl<U: super::Feature + fmt::Debug + Clone> Column<U> {
         Box::new(
             self.all_bounds()
                 .iter()
-                .scan(0f64, |y_off, b| {
+                .enumerate()
+                .scan(0f64, move |y_off, (i, b)| {
+                    // Separators occupy the odd indices, and are padded
+                    // above and below by the separator gap.
+                    let gap = match self.separator_gap {
+                        Some(gap) if i % 2 == 1 => gap,
+                        _ => 0.,
+                    };
+                    *y_off = *y_off + gap;
                     let out = Some((b, *y_off));
-                    *y_off = *y_off + b.height();
+                    *y_off = *y_off + b.height() + gap + self.gap;
                     out
                 })
                 .map(move |(bounds, y_off)| {
@@ -119,7 +128,15 @@ impl<U: super::Feature + fmt::Debug + Clone> Column<U> {
 
 impl<U: super::Feature + fmt::Debug> fmt::Display for Column<U> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Column(align = {:?}, {:?})", self.align, self.array)
+        let align = match self.align {
+            crate::Align::Start => "left",
+            crate::Align::Center => "center",
+            crate::Align::End => "right",
+        };
+        // Separators are padded equally above and below, so are written
+        // as ordinary elements with the padding folded into the gap.
+        let gap = self.gap + self.separator_gap.unwrap_or(0.);
+        fmt_layout(f, "column", align, gap, &self.array)
     }
 }
 
@@ -236,6 +253,301 @@ impl<U: super::Feature + fmt::Debug + Clone> super::Feature for Column<U> {
                 acc
             })
     }
+
+    fn parts(&self) -> Vec<&'static str> {
+        self.array.iter().map(|f| f.parts()).flatten().collect()
+    }
+
+    fn all_names(&self) -> Vec<&'static str> {
+        self.array.iter().fold(vec![self.name()], |mut acc, f| {
+            acc.extend(f.all_names());
+            acc
+        })
+    }
+}
+
+/// A feature which aligns a sequence of features horizontally.
+#[derive(Debug, Clone)]
+pub struct Row<U = super::Unit> {
+    array: Vec<U>,
+    align: crate::Align,
+    bbox: bool,
+}
+
+impl<U: super::Feature + fmt::Debug + Clone> Row<U> {
+    /// Lays out the given features in an array going rightwards, with
+    /// their topmost elements aligned.
+    pub fn align_top(array: Vec<U>) -> Self {
+        Self::new(array, crate::Align::Start)
+    }
+
+    /// Lays out the given features in an array going rightwards, with
+    /// their bottommost elements aligned.
+    pub fn align_bottom(array: Vec<U>) -> Self {
+        Self::new(array, crate::Align::End)
+    }
+
+    /// Lays out the given features in an array going rightwards, with
+    /// each element aligned to the center.
+    pub fn align_center(array: Vec<U>) -> Self {
+        Self::new(array, crate::Align::Center)
+    }
+
+    fn new(mut array: Vec<U>, align: crate::Align) -> Self {
+        // Position any containing geometry to exist entirely in positive
+        // (x>=0, y>=0) coordinate space.
+        for e in array.iter_mut() {
+            if let Some(b) = e.edge_union() {
+                use geo::bounding_rect::BoundingRect;
+                let v = b.bounding_rect().unwrap().min();
+                e.translate(-v);
+            }
+        }
+
+        Self {
+            align,
+            array,
+            bbox: true,
+        }
+    }
+
+    fn all_bounds(&self) -> Vec<geo::Rect<f64>> {
+        all_bounds(&self.array)
+    }
+
+    fn largest(&self) -> geo::Rect<f64> {
+        self.all_bounds()
+            .into_iter()
+            .max_by(|x, y| x.height().partial_cmp(&y.height()).unwrap())
+            .unwrap()
+    }
+
+    fn translations<'a>(
+        &'a self,
+        largest: geo::Rect<f64>,
+    ) -> Box<dyn Iterator<Item = Option<(f64, f64)>> + 'a> {
+        Box::new(
+            self.all_bounds()
+                .iter()
+                .scan(0f64, |x_off, b| {
+                    let out = Some((b, *x_off));
+                    *x_off = *x_off + b.width();
+                    out
+                })
+                .map(move |(bounds, x_off)| {
+                    Some(match self.align {
+                        crate::Align::Start => (x_off, largest.min().y - bounds.min().y),
+                        crate::Align::End => (x_off, largest.max().y - bounds.max().y),
+                        crate::Align::Center => (x_off, largest.center().y - bounds.center().y),
+                    })
+                })
+                .collect::<Vec<_>>()
+                .into_iter(),
+        )
+    }
+}
+
+impl<U: super::Feature + fmt::Debug> fmt::Display for Row<U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let align = match self.align {
+            crate::Align::Start => "top",
+            crate::Align::Center => "center",
+            crate::Align::End => "bottom",
+        };
+        fmt_layout(f, "row", align, 0., &self.array)
+    }
+}
+
+impl<U: super::Feature + fmt::Debug + Clone> super::Feature for Row<U> {
+    fn name(&self) -> &'static str {
+        "Row"
+    }
+
+    fn edge_union(&self) -> Option<MultiPolygon<f64>> {
+        let out = self
+            .array
+            .iter()
+            .map(|f| match f.edge_union() {
+                Some(edge_geo) => Some(edge_geo.clone()),
+                None => None,
+            })
+            .zip(self.translations(self.largest()).into_iter())
+            .filter(|(f, t)| f.is_some() && t.is_some())
+            .map(|(f, t)| (f.unwrap(), t.unwrap()))
+            .fold(None, |mut acc, (g, (tx, ty))| {
+                use geo::translate::Translate;
+                use geo_booleanop::boolean::BooleanOp;
+                if let Some(current) = acc {
+                    acc = Some(g.translate(tx, ty).union(&current));
+                } else {
+                    acc = Some(g.translate(tx, ty));
+                };
+                acc
+            });
+
+        // If we are in bbox mode, all we need to do is compute the bounding
+        // box and use that as our outer geometry.
+        if self.bbox {
+            match out {
+                None => None,
+                Some(poly) => {
+                    use geo::bounding_rect::BoundingRect;
+                    Some(poly.bounding_rect().unwrap().to_polygon().into())
+                }
+            }
+        } else {
+            out
+        }
+    }
+
+    fn edge_subtract(&self) -> Option<MultiPolygon<f64>> {
+        let out = self
+            .array
+            .iter()
+            .map(|f| match f.edge_subtract() {
+                Some(edge_geo) => Some(edge_geo.clone()),
+                None => None,
+            })
+            .zip(self.translations(self.largest()).into_iter())
+            .filter(|(f, t)| f.is_some() && t.is_some())
+            .map(|(f, t)| (f.unwrap(), t.unwrap()))
+            .fold(None, |mut acc, (g, (tx, ty))| {
+                use geo::translate::Translate;
+                use geo_booleanop::boolean::BooleanOp;
+                if let Some(current) = acc {
+                    acc = Some(g.translate(tx, ty).union(&current));
+                } else {
+                    acc = Some(g.translate(tx, ty));
+                };
+                acc
+            });
+
+        out
+    }
+
+    fn translate(&mut self, v: Coordinate<f64>) {
+        for e in self.array.iter_mut() {
+            e.translate(v);
+        }
+    }
+
+    fn interior(&self) -> Vec<super::InnerAtom> {
+        let largest = self.largest();
+
+        self.array
+            .iter()
+            .map(|f| f.interior())
+            .zip(self.translations(largest).into_iter())
+            .map(|(f, t)| {
+                let (tx, ty) = match t {
+                    Some((tx, ty)) => (tx, ty),
+                    None => (0., 0.),
+                };
+
+                f.into_iter().map(move |mut a| {
+                    a.translate(tx, ty);
+                    a
+                })
+            })
+            .flatten()
+            .collect()
+    }
+
+    /// named_info returns information about named geometry.
+    fn named_info(&self) -> Vec<super::NamedInfo> {
+        self.array
+            .iter()
+            .map(|f| f.named_info())
+            .zip(self.translations(self.largest()).into_iter())
+            .filter(|(_infos, t)| t.is_some())
+            .map(|(infos, t)| (infos, t.unwrap()))
+            .enumerate()
+            .fold(vec![], |mut acc, (i, (infos, (tx, ty)))| {
+                for mut info in infos {
+                    info.translate(tx, ty);
+                    info.name_index(i);
+                    acc.push(info);
+                }
+                acc
+            })
+    }
+
+    fn parts(&self) -> Vec<&'static str> {
+        self.array.iter().map(|f| f.parts()).flatten().collect()
+    }
+
+    fn all_names(&self) -> Vec<&'static str> {
+        self.array.iter().fold(vec![self.name()], |mut acc, f| {
+            acc.extend(f.all_names());
+            acc
+        })
+    }
+}
+
+/// Returns the bounds of each feature, including any geometry it subtracts.
+/// Writes the spec for a column or row. Elements are normalized to the
+/// origin when laid out, so have all been translated by the same amount
+/// as the layout itself.
+fn fmt_layout<U: super::Feature>(
+    f: &mut fmt::Formatter,
+    kind: &str,
+    align: &str,
+    gap: f64,
+    array: &[U],
+) -> fmt::Result {
+    use geo::bounding_rect::BoundingRect;
+    let origin = array
+        .iter()
+        .find_map(|e| e.edge_union().and_then(|b| b.bounding_rect()))
+        .map(|b| b.min())
+        .unwrap_or(Coordinate { x: 0., y: 0. });
+
+    write!(f, "{} {} @({}, {}) ", kind, align, origin.x, origin.y)?;
+    if gap != 0. {
+        write!(f, "gap = {} ", gap)?;
+    }
+    write!(f, "{{ ")?;
+    super::fmt_list(f, array)?;
+    write!(f, " }}")
+}
+
+fn all_bounds<U: super::Feature>(array: &[U]) -> Vec<geo::Rect<f64>> {
+    array
+        .iter()
+        .map(|f| {
+            let add_b = match f.edge_union() {
+                Some(edge) => {
+                    use geo::bounding_rect::BoundingRect;
+                    edge.bounding_rect()
+                }
+                None => None,
+            };
+            let sub_b = match f.edge_subtract() {
+                Some(edge) => {
+                    use geo::bounding_rect::BoundingRect;
+                    edge.bounding_rect()
+                }
+                None => None,
+            };
+
+            match (add_b, sub_b) {
+                (Some(b), None) => b,
+                (None, Some(b)) => b,
+                (Some(u), Some(s)) => {
+                    use geo::bounding_rect::BoundingRect;
+                    use geo_booleanop::boolean::BooleanOp;
+                    u.to_polygon()
+                        .union(&s.to_polygon())
+                        .bounding_rect()
+                        .unwrap()
+                }
+                (None, None) => geo::Rect::new(
+                    Coordinate::<f64> { x: 0., y: 0. },
+                    Coordinate::<f64> { x: 0., y: 0. },
+                ),
+            }
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -306,4 +618,39 @@ mod tests {
         let a = Column::align_center(inners);
         assert_eq!(a.translations(a.largest()).collect::<Vec<_>>(), want,);
     }
+
+    #[test]
+    fn separator() {
+        use crate::features::Feature;
+        use geo::bounding_rect::BoundingRect;
+        let a = Column::align_center(vec![
+            Rect::new([0., 0.].into(), [10., 5.].into()),
+            Rect::new([0., 0.].into(), [10., 5.].into()),
+        ])
+        .with_separator(Rect::new([0., 0.].into(), [10., 0.5].into()), 0.5);
+
+        let bounds = a.edge_union().unwrap().bounding_rect().unwrap();
+        assert!(bounds.height() > 11.49 && bounds.height() < 11.51);
+
+        let t: Vec<_> = a.translations(a.largest()).collect();
+        assert_eq!(t.len(), 3);
+        let (_, bar_y) = t[1].unwrap();
+        let bar_center = bar_y + 0.25;
+        assert!(bar_center > bounds.height() / 2. - 0.01);
+        assert!(bar_center < bounds.height() / 2. + 0.01);
+    }
+
+    #[test]
+    fn gap() {
+        use crate::features::Feature;
+        use geo::bounding_rect::BoundingRect;
+        let a = Column::align_left(vec![
+            Rect::with_center([0., 0.].into(), 5., 5.),
+            Rect::with_center([0., 0.].into(), 5., 5.),
+        ])
+        .with_gap(1.);
+
+        let bounds = a.edge_union().unwrap().bounding_rect().unwrap();
+        assert!(bounds.height() > 10.99 && bounds.height() < 11.01);
+    }
 }
@@ -0,0 +1,184 @@
+use geo::{Coordinate, MultiPolygon, Polygon};
+use std::fmt;
+
+/// A region described by a bezier curve, either as a stroke of some
+/// width along the curve, or as the filled region it outlines.
+#[derive(Debug, Clone)]
+pub struct BezierCurve {
+    points: Vec<Coordinate<f64>>,
+    width: Option<f64>,
+    segments: usize,
+}
+
+impl BezierCurve {
+    /// Constructs a stroke of the given width along a cubic bezier curve.
+    pub fn cubic(
+        p0: Coordinate<f64>,
+        p1: Coordinate<f64>,
+        p2: Coordinate<f64>,
+        p3: Coordinate<f64>,
+        width: f64,
+    ) -> Self {
+        Self {
+            points: vec![p0, p1, p2, p3],
+            width: Some(width),
+            segments: 32,
+        }
+    }
+
+    /// Constructs a stroke of the given width along a quadratic bezier curve.
+    pub fn quadratic(
+        p0: Coordinate<f64>,
+        p1: Coordinate<f64>,
+        p2: Coordinate<f64>,
+        width: f64,
+    ) -> Self {
+        Self {
+            points: vec![p0, p1, p2],
+            width: Some(width),
+            segments: 32,
+        }
+    }
+
+    /// Constructs a filled region, outlined by the bezier curve described by
+    /// the control points and closed by a straight line from the last point
+    /// back to the first.
+    pub fn fill_region(control_points: Vec<Coordinate<f64>>) -> Self {
+        Self {
+            points: control_points,
+            width: None,
+            segments: 32,
+        }
+    }
+
+    /// Returns a new curve which is approximated using the given number
+    /// of line segments.
+    pub fn segments(self, segments: usize) -> Self {
+        Self {
+            segments: segments.max(1),
+            ..self
+        }
+    }
+
+    /// Evaluates the curve at t (0 <= t <= 1) using de Casteljau's algorithm.
+    fn point_at(&self, t: f64) -> Coordinate<f64> {
+        let mut pts = self.points.clone();
+        for n in (1..pts.len()).rev() {
+            for i in 0..n {
+                pts[i] = Coordinate {
+                    x: pts[i].x * (1. - t) + pts[i + 1].x * t,
+                    y: pts[i].y * (1. - t) + pts[i + 1].y * t,
+                };
+            }
+        }
+        pts[0]
+    }
+
+    fn samples(&self) -> Vec<Coordinate<f64>> {
+        (0..=self.segments)
+            .map(|i| self.point_at(i as f64 / self.segments as f64))
+            .collect()
+    }
+
+    fn stroke(&self, samples: Vec<Coordinate<f64>>, width: f64) -> Polygon<f64> {
+        let offsets: Vec<_> = (0..samples.len())
+            .map(|i| {
+                let (a, b) = (
+                    samples[i.saturating_sub(1)],
+                    samples[(i + 1).min(samples.len() - 1)],
+                );
+                let (dx, dy) = (b.x - a.x, b.y - a.y);
+                let len = (dx * dx + dy * dy).sqrt();
+                if len > 0. {
+                    Coordinate {
+                        x: -dy / len * width / 2.,
+                        y: dx / len * width / 2.,
+                    }
+                } else {
+                    Coordinate { x: 0., y: 0. }
+                }
+            })
+            .collect();
+
+        let left = samples.iter().zip(offsets.iter()).map(|(p, o)| *p + *o);
+        let right = samples.iter().zip(offsets.iter()).map(|(p, o)| *p - *o);
+        let mut outline: Vec<_> = left.collect();
+        outline.extend(right.rev());
+        Polygon::new(geo::LineString(outline), vec![])
+    }
+}
+
+impl fmt::Display for BezierCurve {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(width) = self.width {
+            // Strokes along a curve cannot be described by a spec.
+            return write!(f, "/* bezier stroke, width = {} */", width);
+        }
+        write!(f, "bezier[")?;
+        for (i, p) in self.points.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "({}, {})", p.x, p.y)?;
+        }
+        write!(f, "]")
+    }
+}
+
+impl super::Feature for BezierCurve {
+    fn name(&self) -> &'static str {
+        "bezier"
+    }
+
+    fn edge_union(&self) -> Option<MultiPolygon<f64>> {
+        if self.points.len() < 2 {
+            return None;
+        }
+
+        let samples = self.samples();
+        Some(match self.width {
+            Some(width) => self.stroke(samples, width).into(),
+            None => Polygon::new(geo::LineString(samples), vec![]).into(),
+        })
+    }
+
+    fn translate(&mut self, v: Coordinate<f64>) {
+        for p in self.points.iter_mut() {
+            *p = *p + v;
+        }
+    }
+
+    fn interior(&self) -> Vec<super::InnerAtom> {
+        vec![]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::Feature;
+    use geo::prelude::Contains;
+
+    #[test]
+    fn cubic_midpoint() {
+        let b = BezierCurve::cubic(
+            [0., 0.].into(),
+            [0., 10.].into(),
+            [10., 10.].into(),
+            [10., 0.].into(),
+            1.,
+        );
+        let poly = b.edge_union().unwrap();
+        assert!(poly.contains(&Coordinate::from([5., 7.5])));
+        assert!(!poly.contains(&Coordinate::from([5., 5.])));
+    }
+
+    #[test]
+    fn fill_region() {
+        let b = BezierCurve::fill_region(vec![[0., 0.].into(), [5., 10.].into(), [10., 0.].into()])
+            .segments(16);
+        let poly = b.edge_union().unwrap();
+        assert!(poly.contains(&Coordinate::from([5., 2.])));
+        assert!(!poly.contains(&Coordinate::from([5., 6.])));
+    }
+}
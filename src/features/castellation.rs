@@ -0,0 +1,139 @@
+use super::{Circle, Drill, Feature, InnerAtom, InnerFeature};
+use crate::Direction;
+use geo::{Coordinate, MultiPolygon};
+use std::fmt;
+
+/// A feature representing a row of holes which are cut in half by the
+/// edge of the board, such that the board can be soldered flat onto
+/// another. Positioned along an edge, the holes form semicircular notches.
+#[derive(Debug, Clone)]
+pub struct Castellation {
+    center: Coordinate<f64>,
+    count: usize,
+    hole_diameter: f64,
+    pitch: f64,
+    side: Direction,
+    plated: bool,
+}
+
+impl Castellation {
+    /// Creates a row of plated holes spaced 2.54mm apart, to be
+    /// positioned along the left side of the board.
+    pub fn new(count: usize, hole_diameter: f64) -> Self {
+        Self {
+            center: [0., 0.].into(),
+            count,
+            hole_diameter,
+            pitch: 2.54,
+            side: Direction::Left,
+            plated: true,
+        }
+    }
+
+    /// Returns a row to be positioned along the given side of the board.
+    pub fn side(self, side: Direction) -> Self {
+        Self { side, ..self }
+    }
+
+    /// Returns a row with the given distance between hole centers.
+    pub fn pitch(self, pitch: f64) -> Self {
+        Self { pitch, ..self }
+    }
+
+    /// Returns a row of non-plated holes, without any copper.
+    pub fn non_plated(self) -> Self {
+        Self {
+            plated: false,
+            ..self
+        }
+    }
+
+    fn hole_centers(&self) -> impl Iterator<Item = Coordinate<f64>> + '_ {
+        let offset = (self.count as f64 - 1.) * self.pitch / 2.;
+        (0..self.count).map(move |i| {
+            let d = i as f64 * self.pitch - offset;
+            match self.side {
+                Direction::Left | Direction::Right => self.center + Coordinate { x: 0., y: d },
+                Direction::Up | Direction::Down => self.center + Coordinate { x: d, y: 0. },
+            }
+        })
+    }
+}
+
+impl fmt::Display for Castellation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        super::fmt_translated(
+            f,
+            self.center,
+            format_args!(
+                "castellation<{}, {}, side = {}{}>",
+                self.count,
+                self.hole_diameter,
+                self.side,
+                if self.plated { "" } else { ", npth" }
+            ),
+        )
+    }
+}
+
+impl Feature for Castellation {
+    fn name(&self) -> &'static str {
+        "castellation"
+    }
+
+    fn edge_union(&self) -> Option<MultiPolygon<f64>> {
+        None
+    }
+
+    fn edge_subtract(&self) -> Option<MultiPolygon<f64>> {
+        self.hole_centers()
+            .filter_map(|c| Circle::new(c, self.hole_diameter / 2.).edge_union())
+            .fold(None, |acc, g| {
+                use geo_booleanop::boolean::BooleanOp;
+                match acc {
+                    Some(current) => Some(g.union(&current)),
+                    None => Some(g),
+                }
+            })
+    }
+
+    fn translate(&mut self, v: Coordinate<f64>) {
+        self.center = self.center + v;
+    }
+
+    fn interior(&self) -> Vec<InnerAtom> {
+        self.hole_centers()
+            .map(|c| {
+                let mut hole = match self.plated {
+                    true => Drill::with_copper_ring(self.hole_diameter, 0.3),
+                    false => Drill::new(self.hole_diameter),
+                };
+                hole.translate(c);
+                hole.atoms()
+            })
+            .flatten()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geo::bounding_rect::BoundingRect;
+
+    #[test]
+    fn notches() {
+        let c = Castellation::new(4, 1.);
+        let b = c.edge_subtract().unwrap().bounding_rect().unwrap();
+        assert!(b.width() > 0.95 && b.width() < 1.01);
+        assert!(b.height() > 8.55 && b.height() < 8.63);
+
+        let drills = c
+            .interior()
+            .into_iter()
+            .filter(|a| matches!(a, InnerAtom::Drill { plated: true, .. }))
+            .count();
+        assert_eq!(drills, 4);
+        assert_eq!(c.non_plated().interior().len(), 4);
+    }
+}
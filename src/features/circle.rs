@@ -6,15 +6,25 @@ use std::fmt;
 pub struct Circle<U = super::Unit> {
     center: Coordinate<f64>,
     radius: f64,
+    sector: Option<Sector>,
     inner: U,
 }
 
+/// Describes the portion of a circle which makes up a sector.
+#[derive(Debug, Clone)]
+struct Sector {
+    start_angle: f64,
+    end_angle: f64,
+    inner_radius: f64,
+}
+
 impl Circle {
     /// Constructs a new circle using the provided center and radius.
     pub fn new(center: Coordinate<f64>, radius: f64) -> Self {
         Self {
             center,
             radius,
+            sector: None,
             inner: super::Unit,
         }
     }
@@ -24,6 +34,38 @@ impl Circle {
     pub fn with_radius(radius: f64) -> Self {
         Self::new([0.0, 0.0].into(), radius)
     }
+
+    /// Constructs a pie-slice shaped region of a circle, spanning
+    /// clockwise from the start angle to the end angle (in degrees).
+    pub fn arc_sector(
+        center: Coordinate<f64>,
+        radius: f64,
+        start_angle_deg: f64,
+        end_angle_deg: f64,
+    ) -> Self {
+        Self::arc_ring_sector(center, 0., radius, start_angle_deg, end_angle_deg)
+    }
+
+    /// Constructs a region of a ring between the inner and outer radius,
+    /// spanning clockwise from the start angle to the end angle (in degrees).
+    pub fn arc_ring_sector(
+        center: Coordinate<f64>,
+        inner_radius: f64,
+        outer_radius: f64,
+        start_angle_deg: f64,
+        end_angle_deg: f64,
+    ) -> Self {
+        Self {
+            center,
+            radius: outer_radius,
+            sector: Some(Sector {
+                start_angle: start_angle_deg,
+                end_angle: end_angle_deg,
+                inner_radius,
+            }),
+            inner: super::Unit,
+        }
+    }
 }
 
 impl<U: super::InnerFeature + Clone> Circle<U> {
@@ -36,6 +78,7 @@ impl<U: super::InnerFeature + Clone> Circle<U> {
         Self {
             center,
             radius,
+            sector: None,
             inner,
         }
     }
@@ -45,18 +88,62 @@ impl<U: super::InnerFeature + Clone> Circle<U> {
         Self {
             radius,
             inner,
+            sector: None,
             center: [0.0, 0.0].into(),
         }
     }
 }
 
-impl<U: super::InnerFeature> fmt::Display for Circle<U> {
+impl<U> Circle<U> {
+    fn sector_polygon(&self, sector: &Sector) -> Polygon<f64> {
+        let sweep = sector.end_angle - sector.start_angle;
+        let arc = |radius: f64| {
+            let num_points = ((radius * 20.0 * sweep.abs() / 360.0).ceil() as usize).max(2);
+            (0..=num_points)
+                .map(|i| {
+                    let a =
+                        (sector.start_angle + sweep * i as f64 / num_points as f64).to_radians();
+                    Coordinate {
+                        x: self.center.x + radius * a.cos(),
+                        y: self.center.y + radius * a.sin(),
+                    }
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let mut points = arc(self.radius);
+        if sector.inner_radius > 0. {
+            points.extend(arc(sector.inner_radius).into_iter().rev());
+        } else {
+            points.push(self.center);
+        }
+        Polygon::new(geo::LineString(points), vec![])
+    }
+}
+
+impl<U: super::InnerFeature + Clone> fmt::Display for Circle<U> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            f,
-            "circle({:?}, r = {:?}, U = {})",
-            self.center, self.radius, self.inner
-        )
+        match &self.sector {
+            // Sectors of a ring cannot be described by a spec.
+            Some(s) if s.inner_radius > 0. => write!(
+                f,
+                "/* ring sector at ({}, {}), r = {}..{} */",
+                self.center.x, self.center.y, s.inner_radius, self.radius
+            ),
+            Some(s) => write!(
+                f,
+                "sector<@({}, {}), {}, {}, {}>",
+                self.center.x, self.center.y, self.radius, s.start_angle, s.end_angle
+            ),
+            None => write!(
+                f,
+                "C<@({}, {}), {}>{}",
+                self.center.x,
+                self.center.y,
+                self.radius,
+                super::fmt_inner(&self.inner, self.center)
+            ),
+        }
     }
 }
 
@@ -66,6 +153,10 @@ impl<U: super::InnerFeature + Clone + std::fmt::Debug> super::Feature for Circle
     }
 
     fn edge_union(&self) -> Option<MultiPolygon<f64>> {
+        if let Some(sector) = &self.sector {
+            return Some(self.sector_polygon(sector).into());
+        }
+
         use geo::algorithm::rotate::RotatePoint;
         let right_edge: Point<_> = (self.center.x + self.radius, self.center.y).into();
         let mut out = Vec::with_capacity(361);
@@ -91,4 +182,33 @@ impl<U: super::InnerFeature + Clone + std::fmt::Debug> super::Feature for Circle
     fn interior(&self) -> Vec<super::InnerAtom> {
         self.inner.atoms()
     }
+
+    fn parts(&self) -> Vec<&'static str> {
+        match self.inner.name() {
+            "unit" => vec![self.name()],
+            inner => vec![self.name(), inner],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::Feature;
+    use geo::algorithm::area::Area;
+
+    #[test]
+    fn sector_area() {
+        let c = Circle::arc_sector([0., 0.].into(), 5., 0., 90.);
+        let area = c.edge_union().unwrap().unsigned_area();
+        assert!(area > 19.55 && area < 19.64);
+    }
+
+    #[test]
+    fn ring_sector_area() {
+        let c = Circle::arc_ring_sector([0., 0.].into(), 3., 5., 0., 180.);
+        let area = c.edge_union().unwrap().unsigned_area();
+        let want = std::f64::consts::PI * (25. - 9.) / 2.;
+        assert!((area - want).abs() < 0.1);
+    }
 }
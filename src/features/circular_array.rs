@@ -0,0 +1,161 @@
+use geo::{Coordinate, MultiPolygon};
+use std::fmt;
+
+/// A feature which is repeated at equally-spaced angles around a point.
+#[derive(Debug, Clone)]
+pub struct CircularArray<U = super::Unit> {
+    inner: U,
+    count: usize,
+    radius: f64,
+    start_angle_deg: f64,
+}
+
+impl<U: super::Feature> CircularArray<U> {
+    /// Constructs a new circular array, where copies of the inner feature
+    /// are positioned on a circle of the given radius about the origin
+    /// of the inner feature. Copies are not rotated.
+    pub fn new(inner: U, count: usize, radius: f64) -> Self {
+        Self {
+            inner,
+            count,
+            radius,
+            start_angle_deg: 0.,
+        }
+    }
+
+    /// Returns a new circular array where the first copy is positioned
+    /// at the given angle, in degrees.
+    pub fn start_angle(mut self, deg: f64) -> Self {
+        self.start_angle_deg = deg;
+        self
+    }
+
+    /// Returns the offset of each copy from the inner feature.
+    fn offsets(&self) -> Vec<(f64, f64)> {
+        (0..self.count)
+            .map(|i| {
+                let a = (self.start_angle_deg + 360. * i as f64 / self.count as f64).to_radians();
+                (self.radius * a.cos(), self.radius * a.sin())
+            })
+            .collect()
+    }
+
+    fn union_copies(&self, geo: MultiPolygon<f64>) -> Option<MultiPolygon<f64>> {
+        self.offsets().into_iter().fold(None, |acc, (x, y)| {
+            use geo::translate::Translate;
+            use geo_booleanop::boolean::BooleanOp;
+            let next = geo.translate(x, y);
+            Some(match acc {
+                Some(current) => next.union(&current),
+                None => next,
+            })
+        })
+    }
+}
+
+impl<U: super::Feature> fmt::Display for CircularArray<U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "circle_array({}, {}, {}) {{ {} }}",
+            self.count, self.radius, self.start_angle_deg, self.inner
+        )
+    }
+}
+
+impl<U: super::Feature + Clone> super::Feature for CircularArray<U> {
+    fn name(&self) -> &'static str {
+        "circular_array"
+    }
+
+    fn edge_union(&self) -> Option<MultiPolygon<f64>> {
+        self.union_copies(self.inner.edge_union()?)
+    }
+
+    fn edge_subtract(&self) -> Option<MultiPolygon<f64>> {
+        self.union_copies(self.inner.edge_subtract()?)
+    }
+
+    fn translate(&mut self, v: Coordinate<f64>) {
+        self.inner.translate(v)
+    }
+
+    fn interior(&self) -> Vec<super::InnerAtom> {
+        let inner = self.inner.interior();
+
+        self.offsets()
+            .into_iter()
+            .map(|(x, y)| {
+                inner.iter().cloned().map(move |mut a| {
+                    a.translate(x, y);
+                    a
+                })
+            })
+            .flatten()
+            .collect()
+    }
+
+    /// named_info returns information about named geometry.
+    fn named_info(&self) -> Vec<super::NamedInfo> {
+        let inner = self.inner.named_info();
+
+        self.offsets()
+            .into_iter()
+            .enumerate()
+            .fold(vec![], |mut acc, (i, (x, y))| {
+                for mut info in inner.iter().cloned() {
+                    info.translate(x, y);
+                    info.name_index(i);
+                    acc.push(info);
+                }
+                acc
+            })
+    }
+
+    fn parts(&self) -> Vec<&'static str> {
+        let inner = self.inner.parts();
+        (0..self.count).map(|_| inner.clone()).flatten().collect()
+    }
+
+    fn all_names(&self) -> Vec<&'static str> {
+        let inner = self.inner.all_names();
+        let mut out = vec![self.name()];
+        for _ in 0..self.count {
+            out.extend(inner.iter());
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::{Circle, Feature, InnerAtom, ScrewHole};
+
+    #[test]
+    fn equidistant_drills() {
+        let a = CircularArray::new(Circle::wrap_with_radius(ScrewHole::default(), 2.), 8, 10.);
+        let centers: Vec<_> = a
+            .interior()
+            .into_iter()
+            .filter_map(|a| match a {
+                InnerAtom::Drill { center, .. } => Some(center),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(centers.len(), 8);
+        for c in centers {
+            let d = (c.x * c.x + c.y * c.y).sqrt();
+            assert!(d > 9.99 && d < 10.01);
+        }
+    }
+
+    #[test]
+    fn start_angle() {
+        let a = CircularArray::new(Circle::with_radius(1.), 4, 5.).start_angle(90.);
+        let (x, y) = a.offsets()[0];
+        assert!(x.abs() < 1e-9);
+        assert!(y > 4.99 && y < 5.01);
+    }
+}
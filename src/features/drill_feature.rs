@@ -0,0 +1,116 @@
+use super::InnerAtom;
+use crate::Layer;
+use geo::Coordinate;
+use std::fmt;
+
+/// An interior feature representing a plain hole, such as for
+/// alignment pins.
+#[derive(Debug, Clone)]
+pub struct Drill {
+    center: Coordinate<f64>,
+    radius: f64,
+    plated: bool,
+    ring_width: Option<f64>,
+}
+
+impl Drill {
+    /// Creates a non-plated hole with the specified diameter.
+    pub fn new(diameter: f64) -> Self {
+        Self {
+            center: [0., 0.].into(),
+            radius: diameter / 2.,
+            plated: false,
+            ring_width: None,
+        }
+    }
+
+    /// Creates a plated hole with the specified diameter, without
+    /// any copper around it.
+    pub fn plated(diameter: f64) -> Self {
+        Self {
+            plated: true,
+            ..Self::new(diameter)
+        }
+    }
+
+    /// Creates a plated hole with the specified diameter, surrounded
+    /// by a ring of exposed copper on both sides.
+    pub fn with_copper_ring(diameter: f64, ring_width: f64) -> Self {
+        Self {
+            ring_width: Some(ring_width),
+            ..Self::plated(diameter)
+        }
+    }
+}
+
+impl fmt::Display for Drill {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "drill<{}{}>",
+            self.radius * 2.,
+            if self.plated { "" } else { ", npth" }
+        )
+    }
+}
+
+impl super::InnerFeature for Drill {
+    fn name(&self) -> &'static str {
+        "drill"
+    }
+
+    fn translate(&mut self, v: Coordinate<f64>) {
+        self.center = self.center + v;
+    }
+
+    fn atoms(&self) -> Vec<InnerAtom> {
+        let mut out = match self.ring_width {
+            Some(width) => vec![
+                Layer::BackCopper,
+                Layer::BackMask,
+                Layer::FrontCopper,
+                Layer::FrontMask,
+            ]
+            .into_iter()
+            .map(|layer| InnerAtom::Circle {
+                center: self.center,
+                radius: self.radius + width,
+                layer,
+            })
+            .collect(),
+            None => vec![],
+        };
+
+        out.push(InnerAtom::Drill {
+            center: self.center,
+            radius: self.radius,
+            plated: self.plated,
+        });
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::InnerFeature;
+
+    #[test]
+    fn npth() {
+        let atoms = Drill::new(3.).atoms();
+        assert_eq!(atoms.len(), 1);
+        assert!(
+            matches!(atoms[0], InnerAtom::Drill { plated: false, radius, .. } if radius == 1.5)
+        );
+    }
+
+    #[test]
+    fn copper_ring() {
+        let atoms = Drill::with_copper_ring(2., 0.5).atoms();
+        assert_eq!(atoms.len(), 5);
+        assert!(matches!(atoms[4], InnerAtom::Drill { plated: true, .. }));
+        assert!(
+            matches!(atoms[0], InnerAtom::Circle { radius, .. } if radius > 1.49 && radius < 1.51)
+        );
+    }
+}
@@ -0,0 +1,149 @@
+use geo::{Coordinate, MultiPolygon, Polygon};
+use std::fmt;
+
+/// An elliptical region, with its major axis along the x axis.
+#[derive(Debug, Clone)]
+pub struct Ellipse<U = super::Unit> {
+    center: Coordinate<f64>,
+    semi_major: f64,
+    semi_minor: f64,
+    steps: usize,
+    inner: U,
+}
+
+impl Ellipse {
+    /// Constructs a new ellipse using the provided center and radii.
+    pub fn new(center: Coordinate<f64>, semi_major: f64, semi_minor: f64) -> Self {
+        Self {
+            center,
+            semi_major,
+            semi_minor,
+            steps: 128,
+            inner: super::Unit,
+        }
+    }
+
+    /// Constructs a new ellipse with the provided radii, centered on
+    /// the origin.
+    pub fn with_radii(semi_major: f64, semi_minor: f64) -> Self {
+        Self::new([0.0, 0.0].into(), semi_major, semi_minor)
+    }
+}
+
+impl<U: super::InnerFeature + Clone> Ellipse<U> {
+    /// Constructs an ellipse surrounding the inner feature. The
+    /// origin of the inner feature will be positioned at the
+    /// center of the ellipse.
+    pub fn with_inner(
+        mut inner: U,
+        center: Coordinate<f64>,
+        semi_major: f64,
+        semi_minor: f64,
+    ) -> Self {
+        inner.translate(center);
+
+        Self {
+            center,
+            semi_major,
+            semi_minor,
+            steps: 128,
+            inner,
+        }
+    }
+
+    /// Constructs an ellipse with the given radii surrounding the
+    /// inner feature.
+    pub fn wrap_with_radii(inner: U, semi_major: f64, semi_minor: f64) -> Self {
+        Self {
+            center: [0.0, 0.0].into(),
+            semi_major,
+            semi_minor,
+            steps: 128,
+            inner,
+        }
+    }
+
+    /// Returns a new ellipse which is approximated using the given
+    /// number of line segments.
+    pub fn resolution(self, steps: usize) -> Self {
+        Self {
+            steps: steps.max(3),
+            ..self
+        }
+    }
+}
+
+impl<U: super::InnerFeature + Clone> fmt::Display for Ellipse<U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "E<@({}, {}), {}, {}>{}",
+            self.center.x,
+            self.center.y,
+            self.semi_major,
+            self.semi_minor,
+            super::fmt_inner(&self.inner, self.center)
+        )
+    }
+}
+
+impl<U: super::InnerFeature + Clone + std::fmt::Debug> super::Feature for Ellipse<U> {
+    fn name(&self) -> &'static str {
+        "ellipse"
+    }
+
+    fn edge_union(&self) -> Option<MultiPolygon<f64>> {
+        let points = (0..=self.steps)
+            .map(|i| {
+                let a = (360.0 * i as f64 / self.steps as f64).to_radians();
+                Coordinate {
+                    x: self.center.x + self.semi_major * a.cos(),
+                    y: self.center.y + self.semi_minor * a.sin(),
+                }
+            })
+            .collect();
+
+        Some(Polygon::new(geo::LineString(points), vec![]).into())
+    }
+
+    fn translate(&mut self, v: Coordinate<f64>) {
+        self.center = self.center + v;
+        self.inner.translate(v);
+    }
+
+    fn interior(&self) -> Vec<super::InnerAtom> {
+        self.inner.atoms()
+    }
+
+    fn parts(&self) -> Vec<&'static str> {
+        match self.inner.name() {
+            "unit" => vec![self.name()],
+            inner => vec![self.name(), inner],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::Feature;
+    use geo::algorithm::{area::Area, bounding_rect::BoundingRect};
+
+    #[test]
+    fn bounds() {
+        let e = Ellipse::new([1., 2.].into(), 10., 5.);
+        let b = e.edge_union().unwrap().bounding_rect().unwrap();
+        assert!(b.width() > 19.99 && b.width() < 20.01);
+        assert!(b.height() > 9.99 && b.height() < 10.01);
+        assert!(b.center().x > 0.99 && b.center().x < 1.01);
+        assert!(b.center().y > 1.99 && b.center().y < 2.01);
+    }
+
+    #[test]
+    fn area() {
+        let e = Ellipse::with_radii(4., 2.).resolution(256);
+        let area = e.edge_union().unwrap().unsigned_area();
+        let want = std::f64::consts::PI * 8.;
+        assert!((area - want).abs() < 0.05);
+    }
+}
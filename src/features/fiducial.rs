@@ -0,0 +1,105 @@
+use super::InnerAtom;
+use crate::Layer;
+use geo::Coordinate;
+use std::fmt;
+
+/// An interior feature representing a fiducial mark: a bare copper pad
+/// surrounded by a clearing in the soldermask, used by machines to
+/// align to the board.
+#[derive(Debug, Clone)]
+pub struct Fiducial {
+    center: Coordinate<f64>,
+    copper_radius: f64,
+    mask_opening_radius: f64,
+}
+
+impl Default for Fiducial {
+    fn default() -> Self {
+        Self {
+            center: [0., 0.].into(),
+            copper_radius: 0.5,
+            mask_opening_radius: 1.5,
+        }
+    }
+}
+
+impl Fiducial {
+    /// Creates a fiducial of the default size at the given position.
+    pub fn new(center: Coordinate<f64>) -> Self {
+        Self {
+            center,
+            ..Self::default()
+        }
+    }
+
+    /// Returns a fiducial with the given radius of the copper pad and
+    /// of the opening in the soldermask around it.
+    pub fn with_radii(self, copper_radius: f64, mask_opening_radius: f64) -> Self {
+        Self {
+            copper_radius,
+            mask_opening_radius,
+            ..self
+        }
+    }
+
+    /// Returns the radius of the opening in the soldermask.
+    pub fn mask_opening_radius(&self) -> f64 {
+        self.mask_opening_radius
+    }
+}
+
+impl fmt::Display for Fiducial {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "fiducial<@({}, {})>", self.center.x, self.center.y)
+    }
+}
+
+impl super::InnerFeature for Fiducial {
+    fn name(&self) -> &'static str {
+        "fiducial"
+    }
+
+    fn translate(&mut self, v: Coordinate<f64>) {
+        self.center = self.center + v;
+    }
+
+    fn atoms(&self) -> Vec<InnerAtom> {
+        vec![
+            (Layer::FrontCopper, self.copper_radius),
+            (Layer::BackCopper, self.copper_radius),
+            (Layer::FrontMask, self.mask_opening_radius),
+            (Layer::BackMask, self.mask_opening_radius),
+        ]
+        .into_iter()
+        .map(|(layer, radius)| InnerAtom::Circle {
+            center: self.center,
+            radius,
+            layer,
+        })
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::InnerFeature;
+
+    #[test]
+    fn mask_opening() {
+        let atoms = Fiducial::default().atoms();
+        assert_eq!(atoms.len(), 4);
+
+        let radius = |want: Layer| {
+            atoms
+                .iter()
+                .find_map(|a| match a {
+                    InnerAtom::Circle { radius, layer, .. } if *layer == want => Some(*radius),
+                    _ => None,
+                })
+                .unwrap()
+        };
+        assert!(radius(Layer::FrontMask) > radius(Layer::FrontCopper));
+        assert!(radius(Layer::BackMask) > radius(Layer::BackCopper));
+    }
+}
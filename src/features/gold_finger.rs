@@ -0,0 +1,191 @@
+use super::InnerAtom;
+use crate::Layer;
+use geo::{Coordinate, MultiPolygon, Polygon};
+use std::fmt;
+
+/// Distance between the end of the pads and the edge of the board.
+const EDGE_CLEARANCE: f64 = 0.5;
+/// Length of the strip between the pads and the rest of the board.
+const RAIL_LENGTH: f64 = 1.0;
+/// Size of the bevel on the corners of the edge of the board.
+const CHAMFER: f64 = 0.5;
+
+/// A row of edge-connector pads along the X axis, on a strip of board
+/// with a chamfered edge. The pads extend downwards towards the edge.
+#[derive(Debug, Clone)]
+pub struct GoldFinger {
+    center: Coordinate<f64>,
+    count: usize,
+    pitch: f64,
+    pad_width: f64,
+    pad_length: f64,
+    double_sided: bool,
+}
+
+impl GoldFinger {
+    /// Constructs a row of `count` pads spaced `pitch` apart, centered
+    /// on the origin.
+    pub fn new(count: usize, pitch: f64, pad_width: f64, pad_length: f64) -> Self {
+        Self {
+            center: [0., 0.].into(),
+            count,
+            pitch,
+            pad_width,
+            pad_length,
+            double_sided: false,
+        }
+    }
+
+    /// Returns a new set of gold fingers with pads on both sides
+    /// of the board.
+    pub fn double_sided(self) -> Self {
+        Self {
+            double_sided: true,
+            ..self
+        }
+    }
+
+    /// Returns the centers of each pad.
+    pub fn pad_centers(&self) -> Vec<Coordinate<f64>> {
+        let start = -(self.count.saturating_sub(1) as f64) * self.pitch / 2.;
+        (0..self.count)
+            .map(|i| Coordinate {
+                x: self.center.x + start + i as f64 * self.pitch,
+                y: self.center.y,
+            })
+            .collect()
+    }
+
+    fn pad(&self, center: Coordinate<f64>) -> geo::Rect<f64> {
+        geo::Rect::new(
+            Coordinate {
+                x: center.x - self.pad_width / 2.,
+                y: center.y - self.pad_length / 2.,
+            },
+            Coordinate {
+                x: center.x + self.pad_width / 2.,
+                y: center.y + self.pad_length / 2.,
+            },
+        )
+    }
+}
+
+impl fmt::Display for GoldFinger {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        super::fmt_translated(
+            f,
+            self.center,
+            format_args!(
+                "gold_fingers<{}, {}, {}, {}{}>",
+                self.count,
+                self.pitch,
+                self.pad_width,
+                self.pad_length,
+                if self.double_sided { ", double" } else { "" }
+            ),
+        )
+    }
+}
+
+impl super::Feature for GoldFinger {
+    fn name(&self) -> &'static str {
+        "gold_fingers"
+    }
+
+    fn edge_union(&self) -> Option<MultiPolygon<f64>> {
+        if self.count == 0 {
+            return None;
+        }
+
+        let half_width = (self.count as f64 * self.pitch).max(self.pad_width) / 2.;
+        let (top, bottom) = (
+            self.center.y - self.pad_length / 2. - RAIL_LENGTH,
+            self.center.y + self.pad_length / 2. + EDGE_CLEARANCE,
+        );
+        let (left, right) = (self.center.x - half_width, self.center.x + half_width);
+
+        Some(
+            Polygon::new(
+                geo::LineString(vec![
+                    [left, top].into(),
+                    [right, top].into(),
+                    [right, bottom - CHAMFER].into(),
+                    [right - CHAMFER, bottom].into(),
+                    [left + CHAMFER, bottom].into(),
+                    [left, bottom - CHAMFER].into(),
+                    [left, top].into(),
+                ]),
+                vec![],
+            )
+            .into(),
+        )
+    }
+
+    fn translate(&mut self, v: Coordinate<f64>) {
+        self.center = self.center + v;
+    }
+
+    fn interior(&self) -> Vec<InnerAtom> {
+        let layers = if self.double_sided {
+            vec![
+                Layer::FrontCopper,
+                Layer::FrontMask,
+                Layer::BackCopper,
+                Layer::BackMask,
+            ]
+        } else {
+            vec![Layer::FrontCopper, Layer::FrontMask]
+        };
+
+        self.pad_centers()
+            .into_iter()
+            .map(|c| {
+                let rect = self.pad(c);
+                layers.iter().map(move |layer| InnerAtom::Rect {
+                    rect,
+                    layer: layer.clone(),
+                })
+            })
+            .flatten()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::Feature;
+
+    #[test]
+    fn pad_positions() {
+        let mut g = GoldFinger::new(4, 2.54, 1.5, 5.);
+        g.translate([10., 0.].into());
+
+        let xs: Vec<_> = g
+            .interior()
+            .into_iter()
+            .filter(|a| {
+                matches!(
+                    a,
+                    InnerAtom::Rect {
+                        layer: Layer::FrontCopper,
+                        ..
+                    }
+                )
+            })
+            .map(|a| a.bounds().unwrap().center().x)
+            .collect();
+        let want = [6.19, 8.73, 11.27, 13.81];
+        assert_eq!(xs.len(), want.len());
+        for (x, want) in xs.iter().zip(want.iter()) {
+            assert!((x - want).abs() < 0.001);
+        }
+    }
+
+    #[test]
+    fn double_sided() {
+        let g = GoldFinger::new(3, 1., 0.5, 3.);
+        assert_eq!(g.interior().len(), 6);
+        assert_eq!(g.double_sided().interior().len(), 12);
+    }
+}
@@ -0,0 +1,217 @@
+use geo::{Coordinate, MultiPolygon};
+use std::fmt;
+
+/// A feature which is repeated in rows and columns.
+#[derive(Debug, Clone)]
+pub struct Grid<U = super::Rect> {
+    inner: U,
+    rows: usize,
+    cols: usize,
+    row_gap: f64,
+    col_gap: f64,
+}
+
+impl<U: super::Feature> Grid<U> {
+    /// Constructs a new grid, where the inner feature is the top-left
+    /// element and copies extend rightwards and downwards.
+    pub fn new(inner: U, rows: usize, cols: usize) -> Self {
+        Self {
+            inner,
+            rows,
+            cols,
+            row_gap: 0.,
+            col_gap: 0.,
+        }
+    }
+
+    /// Returns a new grid with the given spacing between rows.
+    pub fn row_gap(mut self, gap: f64) -> Self {
+        self.row_gap = gap;
+        self
+    }
+
+    /// Returns a new grid with the given spacing between columns.
+    pub fn col_gap(mut self, gap: f64) -> Self {
+        self.col_gap = gap;
+        self
+    }
+
+    fn bounds(&self) -> Option<geo::Rect<f64>> {
+        use geo::bounding_rect::BoundingRect;
+        self.inner.edge_union().and_then(|e| e.bounding_rect())
+    }
+
+    /// Returns the offset of each element of the grid, in row-major order.
+    fn offsets(&self, bounds: geo::Rect<f64>) -> Vec<(f64, f64)> {
+        let (dx, dy) = (
+            bounds.width() + self.col_gap,
+            bounds.height() + self.row_gap,
+        );
+        (0..self.rows)
+            .map(|r| (0..self.cols).map(move |c| (c as f64 * dx, r as f64 * dy)))
+            .flatten()
+            .collect()
+    }
+}
+
+impl<U: super::Feature> fmt::Display for Grid<U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.row_gap != self.col_gap {
+            // Specs can only describe grids with a uniform gap.
+            return write!(
+                f,
+                "/* {}x{} grid, gap = ({}, {}) */",
+                self.rows, self.cols, self.row_gap, self.col_gap
+            );
+        }
+        write!(f, "[{}x{}", self.rows, self.cols)?;
+        if self.row_gap != 0. {
+            write!(f, "; gap = {}", self.row_gap)?;
+        }
+        write!(f, "] {}", self.inner)
+    }
+}
+
+impl<U: super::Feature + Clone> super::Feature for Grid<U> {
+    fn name(&self) -> &'static str {
+        "grid"
+    }
+
+    fn edge_union(&self) -> Option<MultiPolygon<f64>> {
+        let edge_geo = self.inner.edge_union()?;
+        let bounds = self.bounds()?;
+
+        self.offsets(bounds).into_iter().fold(None, |acc, (x, y)| {
+            use geo::translate::Translate;
+            use geo_booleanop::boolean::BooleanOp;
+            let next = edge_geo.translate(x, y);
+            Some(match acc {
+                Some(current) => next.union(&current),
+                None => next,
+            })
+        })
+    }
+
+    fn edge_subtract(&self) -> Option<MultiPolygon<f64>> {
+        let sub_geo = self.inner.edge_subtract()?;
+        let bounds = match self.bounds() {
+            Some(b) => b,
+            None => {
+                use geo::bounding_rect::BoundingRect;
+                sub_geo.bounding_rect()?
+            }
+        };
+
+        self.offsets(bounds).into_iter().fold(None, |acc, (x, y)| {
+            use geo::translate::Translate;
+            use geo_booleanop::boolean::BooleanOp;
+            let next = sub_geo.translate(x, y);
+            Some(match acc {
+                Some(current) => next.union(&current),
+                None => next,
+            })
+        })
+    }
+
+    fn translate(&mut self, v: Coordinate<f64>) {
+        self.inner.translate(v)
+    }
+
+    fn interior(&self) -> Vec<super::InnerAtom> {
+        let bounds = match self.bounds() {
+            Some(b) => b,
+            None => return vec![],
+        };
+        let inner = self.inner.interior();
+
+        self.offsets(bounds)
+            .into_iter()
+            .map(|(x, y)| {
+                inner.iter().cloned().map(move |mut a| {
+                    a.translate(x, y);
+                    a
+                })
+            })
+            .flatten()
+            .collect()
+    }
+
+    /// named_info returns information about named geometry.
+    fn named_info(&self) -> Vec<super::NamedInfo> {
+        let bounds = match self.bounds() {
+            Some(b) => b,
+            None => return vec![],
+        };
+        let inner = self.inner.named_info();
+
+        self.offsets(bounds)
+            .into_iter()
+            .enumerate()
+            .fold(vec![], |mut acc, (i, (x, y))| {
+                for mut info in inner.iter().cloned() {
+                    info.translate(x, y);
+                    info.name_index(i);
+                    acc.push(info);
+                }
+                acc
+            })
+    }
+
+    fn parts(&self) -> Vec<&'static str> {
+        let inner = self.inner.parts();
+        (0..self.rows * self.cols)
+            .map(|_| inner.clone())
+            .flatten()
+            .collect()
+    }
+
+    fn all_names(&self) -> Vec<&'static str> {
+        let inner = self.inner.all_names();
+        let mut out = vec![self.name()];
+        for _ in 0..self.rows * self.cols {
+            out.extend(inner.iter());
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::{Feature, Rect, ScrewHole};
+    use geo::bounding_rect::BoundingRect;
+
+    #[test]
+    fn bounds() {
+        let g = Grid::new(Rect::new([0., 0.].into(), [5., 5.].into()), 3, 4);
+        let b = g.edge_union().unwrap().bounding_rect().unwrap();
+        assert!(b.width() > 19.99 && b.width() < 20.01);
+        assert!(b.height() > 14.99 && b.height() < 15.01);
+    }
+
+    #[test]
+    fn gaps() {
+        let g = Grid::new(Rect::new([0., 0.].into(), [5., 5.].into()), 3, 4)
+            .row_gap(1.)
+            .col_gap(2.);
+        let b = g.edge_union().unwrap().bounding_rect().unwrap();
+        assert!(b.width() > 25.99 && b.width() < 26.01);
+        assert!(b.height() > 16.99 && b.height() < 17.01);
+    }
+
+    #[test]
+    fn interior() {
+        let g = Grid::new(
+            Rect::with_inner(ScrewHole::default()).bounds([0., 0.].into(), [5., 5.].into()),
+            2,
+            3,
+        );
+        let drills: Vec<_> = g
+            .interior()
+            .into_iter()
+            .filter(|a| matches!(a, super::super::InnerAtom::Drill { .. }))
+            .collect();
+        assert_eq!(drills.len(), 6);
+        assert_eq!(g.parts().len(), 12);
+    }
+}
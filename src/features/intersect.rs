@@ -0,0 +1,145 @@
+use geo::{Coordinate, MultiPolygon};
+use geo_booleanop::boolean::BooleanOp;
+use std::fmt;
+
+/// A feature which is the region common to both of its contained
+/// features.
+#[derive(Debug, Clone)]
+pub struct Intersect<U = super::Unit, V = super::Unit> {
+    left: U,
+    right: V,
+}
+
+impl<U, V> Intersect<U, V>
+where
+    U: super::Feature + fmt::Debug + Clone,
+    V: super::Feature + fmt::Debug + Clone,
+{
+    pub fn new(left: U, right: V) -> Self {
+        Self { left, right }
+    }
+}
+
+fn intersection(
+    a: Option<MultiPolygon<f64>>,
+    b: Option<MultiPolygon<f64>>,
+) -> Option<MultiPolygon<f64>> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.intersection(&b)),
+        _ => None,
+    }
+}
+
+impl<U, V> fmt::Display for Intersect<U, V>
+where
+    U: super::Feature + fmt::Debug + Clone,
+    V: super::Feature + fmt::Debug + Clone,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "intersect {{ {} {} }}", self.left, self.right)
+    }
+}
+
+impl<U, V> super::Feature for Intersect<U, V>
+where
+    U: super::Feature + fmt::Debug + Clone,
+    V: super::Feature + fmt::Debug + Clone,
+{
+    fn name(&self) -> &'static str {
+        "intersect"
+    }
+
+    fn edge_union(&self) -> Option<MultiPolygon<f64>> {
+        intersection(self.left.edge_union(), self.right.edge_union())
+    }
+
+    fn edge_subtract(&self) -> Option<MultiPolygon<f64>> {
+        intersection(self.left.edge_subtract(), self.right.edge_subtract())
+    }
+
+    fn translate(&mut self, v: Coordinate<f64>) {
+        self.left.translate(v);
+        self.right.translate(v);
+    }
+
+    /// Returns the interior atoms of both features which are centered
+    /// within the intersection. Atoms without a position, such as
+    /// v-scores, are always kept.
+    fn interior(&self) -> Vec<super::InnerAtom> {
+        use geo::algorithm::contains::Contains;
+        let edge = self.edge_union();
+
+        self.left
+            .interior()
+            .into_iter()
+            .chain(self.right.interior().into_iter())
+            .filter(|a| match (a.bounds(), &edge) {
+                (None, _) => true,
+                (Some(b), Some(edge)) => edge.contains(&geo::Point::from(b.center())),
+                (Some(_), None) => false,
+            })
+            .collect()
+    }
+
+    /// named_info returns information about named geometry.
+    fn named_info(&self) -> Vec<super::NamedInfo> {
+        let mut out = self.left.named_info();
+        out.extend(self.right.named_info());
+        out
+    }
+
+    fn parts(&self) -> Vec<&'static str> {
+        let mut out = self.left.parts();
+        out.extend(self.right.parts());
+        out
+    }
+
+    fn all_names(&self) -> Vec<&'static str> {
+        let mut out = vec![self.name()];
+        out.extend(self.left.all_names());
+        out.extend(self.right.all_names());
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::{Circle, Feature, Rect, ScrewHole};
+    use geo::algorithm::area::Area;
+
+    #[test]
+    fn smaller_than_inputs() {
+        let r = Rect::with_center([0., 0.].into(), 5., 5.);
+        let c = Circle::with_radius(3.);
+        let i = Intersect::new(r.clone(), c.clone());
+
+        let area = i.edge_union().unwrap().unsigned_area();
+        assert!(area > 0.);
+        assert!(area < r.edge_union().unwrap().unsigned_area());
+        assert!(area < c.edge_union().unwrap().unsigned_area());
+    }
+
+    #[test]
+    fn disjoint() {
+        let i = Intersect::new(
+            Rect::with_center([0., 0.].into(), 2., 2.),
+            Rect::with_center([5., 0.].into(), 2., 2.),
+        );
+        assert_eq!(i.edge_union().unwrap().unsigned_area(), 0.);
+        assert_eq!(i.edge_subtract(), None);
+    }
+
+    #[test]
+    fn interior() {
+        let inside =
+            Rect::with_inner(ScrewHole::default()).bounds([0., 0.].into(), [4., 4.].into());
+        let outside =
+            Rect::with_inner(ScrewHole::default()).bounds([1., 0.].into(), [10., 4.].into());
+        let i = Intersect::new(inside.clone(), outside);
+
+        // Only the hole of the first rectangle, at (2, 2), lies within
+        // the 3x4 intersection.
+        assert_eq!(i.interior(), inside.interior());
+    }
+}
@@ -1,5 +1,5 @@
 use super::InnerAtom;
-use crate::Layer;
+use crate::{Layer, Side};
 use geo::{Coordinate, Rect};
 use std::fmt;
 
@@ -9,6 +9,9 @@ pub struct MechanicalSolderPoint {
     center: Coordinate<f64>,
     size: (f64, f64),
     drill_radius: f64,
+    plated: bool,
+    suppress_paste: bool,
+    layer: Option<Layer>,
 }
 
 impl MechanicalSolderPoint {
@@ -20,6 +23,31 @@ impl MechanicalSolderPoint {
         }
     }
 
+    /// Returns an MSP with a non-plated drill and no copper or mask.
+    pub fn non_plated(self) -> Self {
+        Self {
+            plated: false,
+            ..self
+        }
+    }
+
+    /// Returns an MSP which does not emit any paste.
+    pub fn no_paste(self) -> Self {
+        Self {
+            suppress_paste: true,
+            ..self
+        }
+    }
+
+    /// Returns an MSP with copper and mask only on the side of the
+    /// given layer.
+    pub fn single_sided(self, layer: Layer) -> Self {
+        Self {
+            layer: Some(layer),
+            ..self
+        }
+    }
+
     fn rect(&self) -> Rect<f64> {
         Rect::new(
             self.center
@@ -42,13 +70,23 @@ impl Default for MechanicalSolderPoint {
             center: [0., 0.].into(),
             size: (1.175, 1.45),
             drill_radius: 0.15,
+            plated: true,
+            suppress_paste: false,
+            layer: None,
         }
     }
 }
 
 impl fmt::Display for MechanicalSolderPoint {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "msp({:?})", self.size)
+        write!(
+            f,
+            "msp<{}, {}{}{}>",
+            self.size.0,
+            self.size.1,
+            if self.plated { "" } else { ", npth" },
+            if self.suppress_paste { ", nopaste" } else { "" }
+        )
     }
 }
 
@@ -62,28 +100,102 @@ impl super::InnerFeature for MechanicalSolderPoint {
     }
 
     fn atoms(&self) -> Vec<InnerAtom> {
-        vec![
-            InnerAtom::Rect {
-                layer: Layer::BackCopper,
-                rect: self.rect(),
-            },
-            InnerAtom::Rect {
-                layer: Layer::BackMask,
-                rect: self.rect(),
-            },
-            InnerAtom::Rect {
-                layer: Layer::FrontCopper,
-                rect: self.rect(),
-            },
-            InnerAtom::Rect {
-                layer: Layer::FrontMask,
+        if !self.plated {
+            return vec![InnerAtom::Drill {
+                center: self.center,
+                radius: self.drill_radius,
+                plated: false,
+            }];
+        }
+
+        let side = self.layer.as_ref().and_then(|l| l.side());
+        let mut layers = match side {
+            Some(Side::Front) => vec![Layer::FrontCopper, Layer::FrontMask],
+            Some(Side::Back) => vec![Layer::BackCopper, Layer::BackMask],
+            None => vec![
+                Layer::BackCopper,
+                Layer::BackMask,
+                Layer::FrontCopper,
+                Layer::FrontMask,
+            ],
+        };
+        if !self.suppress_paste {
+            layers.extend(match side {
+                Some(Side::Front) => vec![Layer::PasteFront],
+                Some(Side::Back) => vec![Layer::PasteBack],
+                None => vec![Layer::PasteBack, Layer::PasteFront],
+            });
+        }
+
+        layers
+            .into_iter()
+            .map(|layer| InnerAtom::Rect {
+                layer,
                 rect: self.rect(),
-            },
-            InnerAtom::Drill {
+            })
+            .chain(std::iter::once(InnerAtom::Drill {
                 center: self.center,
                 radius: self.drill_radius,
                 plated: true,
-            },
-        ]
+            }))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::InnerFeature;
+
+    #[test]
+    fn default_atoms() {
+        let atoms = MechanicalSolderPoint::default().atoms();
+        assert_eq!(atoms.len(), 7);
+        assert!(matches!(atoms[6], InnerAtom::Drill { plated: true, .. }));
+        assert!(matches!(
+            atoms[5],
+            InnerAtom::Rect {
+                layer: Layer::PasteFront,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn no_paste() {
+        let atoms = MechanicalSolderPoint::default().no_paste().atoms();
+        assert_eq!(atoms.len(), 5);
+        assert!(atoms
+            .iter()
+            .all(|a| a.layer() != Some(Layer::PasteFront) && a.layer() != Some(Layer::PasteBack)));
+    }
+
+    #[test]
+    fn single_sided() {
+        let atoms = MechanicalSolderPoint::default()
+            .single_sided(Layer::BackCopper)
+            .atoms();
+        assert_eq!(atoms.len(), 4);
+        assert!(matches!(
+            atoms[0],
+            InnerAtom::Rect {
+                layer: Layer::BackCopper,
+                ..
+            }
+        ));
+        assert!(matches!(
+            atoms[1],
+            InnerAtom::Rect {
+                layer: Layer::BackMask,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn non_plated() {
+        let atoms = MechanicalSolderPoint::default().non_plated().atoms();
+        assert_eq!(atoms.len(), 1);
+        assert!(matches!(atoms[0], InnerAtom::Drill { plated: false, .. }));
     }
 }
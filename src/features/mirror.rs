@@ -0,0 +1,177 @@
+use super::repeating::{mirror_atom, mirror_geo};
+use crate::Direction;
+use geo::{Coordinate, MultiPolygon};
+use std::fmt;
+
+/// The coordinate which is negated when reflecting geometry.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MirrorAxis {
+    X,
+    Y,
+}
+
+impl MirrorAxis {
+    fn direction(&self) -> Direction {
+        match self {
+            MirrorAxis::X => Direction::Left,
+            MirrorAxis::Y => Direction::Up,
+        }
+    }
+}
+
+/// A feature which is the union of its contained geometry and the
+/// reflection of that geometry about the origin.
+#[derive(Debug, Clone)]
+pub struct Mirror<U = super::Unit> {
+    features: Vec<U>,
+    axis: MirrorAxis,
+}
+
+impl<U: super::Feature + fmt::Debug + Clone> Mirror<U> {
+    pub fn new(axis: MirrorAxis, features: Vec<U>) -> Self {
+        Self { features, axis }
+    }
+
+    fn reflected_union(&self, geo: Vec<MultiPolygon<f64>>) -> Option<MultiPolygon<f64>> {
+        let origin = Coordinate { x: 0., y: 0. };
+        let axis = self.axis.direction();
+
+        geo.into_iter()
+            .map(|g| {
+                let reflected = mirror_geo(&g, origin, &axis);
+                vec![g, reflected]
+            })
+            .flatten()
+            .fold(None, |acc, g| {
+                use geo_booleanop::boolean::BooleanOp;
+                match acc {
+                    Some(current) => Some(g.union(&current)),
+                    None => Some(g),
+                }
+            })
+    }
+}
+
+impl<U> fmt::Display for Mirror<U>
+where
+    U: super::Feature + fmt::Debug + Clone,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let axis = match self.axis {
+            MirrorAxis::X => "x",
+            MirrorAxis::Y => "y",
+        };
+        write!(f, "mirror({}) {{ ", axis)?;
+        super::fmt_list(f, &self.features)?;
+        write!(f, " }}")
+    }
+}
+
+impl<U> super::Feature for Mirror<U>
+where
+    U: super::Feature + fmt::Debug + Clone,
+{
+    fn name(&self) -> &'static str {
+        "mirror"
+    }
+
+    fn edge_union(&self) -> Option<MultiPolygon<f64>> {
+        self.reflected_union(
+            self.features
+                .iter()
+                .filter_map(|f| f.edge_union())
+                .collect(),
+        )
+    }
+
+    fn edge_subtract(&self) -> Option<MultiPolygon<f64>> {
+        self.reflected_union(
+            self.features
+                .iter()
+                .filter_map(|f| f.edge_subtract())
+                .collect(),
+        )
+    }
+
+    fn translate(&mut self, v: Coordinate<f64>) {
+        for e in self.features.iter_mut() {
+            e.translate(v);
+        }
+    }
+
+    fn interior(&self) -> Vec<super::InnerAtom> {
+        let origin = Coordinate { x: 0., y: 0. };
+        let axis = self.axis.direction();
+        let atoms: Vec<_> = self
+            .features
+            .iter()
+            .map(|f| f.interior())
+            .flatten()
+            .collect();
+
+        let reflected: Vec<_> = atoms
+            .iter()
+            .map(|a| mirror_atom(a, origin, &axis))
+            .collect();
+        atoms.into_iter().chain(reflected.into_iter()).collect()
+    }
+
+    /// named_info returns information about named geometry.
+    fn named_info(&self) -> Vec<super::NamedInfo> {
+        self.features
+            .iter()
+            .map(|f| f.named_info())
+            .flatten()
+            .collect()
+    }
+
+    fn parts(&self) -> Vec<&'static str> {
+        self.features.iter().map(|f| f.parts()).flatten().collect()
+    }
+
+    fn all_names(&self) -> Vec<&'static str> {
+        self.features.iter().fold(vec![self.name()], |mut acc, f| {
+            acc.extend(f.all_names());
+            acc
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::{Feature, Rect};
+
+    #[test]
+    fn reflects_y() {
+        let m = Mirror::new(
+            MirrorAxis::Y,
+            vec![Rect::new([0., 0.].into(), [3., 1.].into())],
+        );
+
+        use geo::bounding_rect::BoundingRect;
+        let bounds = m.edge_union().unwrap().bounding_rect().unwrap();
+        assert!(bounds.width() > 2.99 && bounds.width() < 3.01);
+        assert!(bounds.height() > 1.99 && bounds.height() < 2.01);
+        assert!(bounds.min().y > -1.01 && bounds.min().y < -0.99);
+    }
+
+    #[test]
+    fn interior() {
+        use crate::features::{InnerAtom, ScrewHole};
+        let r = Rect::with_inner(ScrewHole::default()).bounds([0., 2.].into(), [4., 6.].into());
+        let m = Mirror::new(MirrorAxis::X, vec![r]);
+
+        let drills: Vec<_> = m
+            .interior()
+            .into_iter()
+            .filter_map(|a| match a {
+                InnerAtom::Drill { center, .. } => Some(center),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(drills.len(), 2);
+        assert!((drills[0].x - 2.).abs() < 1e-9 && (drills[1].x + 2.).abs() < 1e-9);
+        assert!((drills[0].y - 4.).abs() < 1e-9 && (drills[1].y - 4.).abs() < 1e-9);
+    }
+}
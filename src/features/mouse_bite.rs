@@ -0,0 +1,139 @@
+use super::InnerAtom;
+use crate::Direction;
+use geo::{Coordinate, MultiPolygon};
+use std::fmt;
+
+/// A feature representing a row of small non-plated holes, which
+/// perforate the board so it can be snapped apart.
+#[derive(Debug, Clone)]
+pub struct MouseBite {
+    start: Coordinate<f64>,
+    length: f64,
+    hole_diameter: f64,
+    spacing: f64,
+    direction: Direction,
+}
+
+impl MouseBite {
+    /// Creates a row of holes of the given length, starting at the origin
+    /// and running to the right. Holes are spaced such that the web
+    /// between them is 60% of the hole diameter.
+    pub fn new(length: f64, hole_diameter: f64) -> Self {
+        Self {
+            start: [0., 0.].into(),
+            length,
+            hole_diameter,
+            spacing: hole_diameter * 1.6,
+            direction: Direction::Right,
+        }
+    }
+
+    /// Returns a row which starts at the given point.
+    pub fn at(self, start: Coordinate<f64>) -> Self {
+        Self { start, ..self }
+    }
+
+    /// Returns a row with the given distance between hole centers.
+    pub fn spacing(self, spacing: f64) -> Self {
+        Self { spacing, ..self }
+    }
+
+    /// Returns a row which runs in the given direction from its start.
+    pub fn direction(self, direction: Direction) -> Self {
+        Self { direction, ..self }
+    }
+}
+
+impl fmt::Display for MouseBite {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "mouse_bite<@({}, {}), {}, {}, {}, {}>",
+            self.start.x,
+            self.start.y,
+            self.length,
+            self.hole_diameter,
+            self.spacing,
+            self.direction
+        )
+    }
+}
+
+impl super::Feature for MouseBite {
+    fn name(&self) -> &'static str {
+        "mouse_bite"
+    }
+
+    fn edge_union(&self) -> Option<MultiPolygon<f64>> {
+        None
+    }
+
+    fn translate(&mut self, v: Coordinate<f64>) {
+        self.start = self.start + v;
+    }
+
+    fn interior(&self) -> Vec<InnerAtom> {
+        if self.spacing <= 0. {
+            return vec![];
+        }
+        let (dx, dy) = match self.direction {
+            Direction::Left => (-1., 0.),
+            Direction::Right => (1., 0.),
+            Direction::Up => (0., -1.),
+            Direction::Down => (0., 1.),
+        };
+        // Allow for float error when the length is a multiple of the spacing.
+        let count = (self.length / self.spacing + 1e-9).floor() as usize + 1;
+
+        (0..count)
+            .map(|i| {
+                let d = i as f64 * self.spacing;
+                InnerAtom::Drill {
+                    center: self.start
+                        + Coordinate {
+                            x: dx * d,
+                            y: dy * d,
+                        },
+                    radius: self.hole_diameter / 2.,
+                    plated: false,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::Feature;
+
+    #[test]
+    fn hole_count() {
+        let mb = MouseBite::new(10., 0.5).spacing(1.);
+        assert!(mb.edge_union().is_none());
+        assert!(mb.edge_subtract().is_none());
+
+        let holes = mb.interior();
+        assert_eq!(holes.len(), (10f64 / 1.).floor() as usize + 1);
+        assert!(holes
+            .iter()
+            .all(|h| matches!(h, InnerAtom::Drill { plated: false, .. })));
+        assert!(
+            matches!(holes[10], InnerAtom::Drill { center, .. } if center.x > 9.99 && center.x < 10.01)
+        );
+        assert_eq!(MouseBite::new(10., 0.5).interior().len(), 13);
+    }
+
+    #[test]
+    fn direction() {
+        let holes = MouseBite::new(2., 0.5)
+            .spacing(1.)
+            .direction(Direction::Up)
+            .at([1., 1.].into())
+            .interior();
+        assert_eq!(holes.len(), 3);
+        assert!(
+            matches!(holes[2], InnerAtom::Drill { center, .. } if center.x == 1. && center.y == -1.)
+        );
+    }
+}
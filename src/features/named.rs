@@ -19,7 +19,7 @@ where
     U: super::Feature + fmt::Debug + Clone,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Named({:?}, {:?})", self.name, self.feature)
+        write!(f, "({}) % {}", self.feature, self.name)
     }
 }
 
@@ -63,6 +63,20 @@ where
             _ => geo::Rect::new((0., 0.), (0., 0.)),
         };
 
-        vec![super::NamedInfo::new(self.name.clone(), bounds)]
+        vec![super::NamedInfo::new(
+            self.name.clone(),
+            self.feature.name(),
+            bounds,
+        )]
+    }
+
+    fn parts(&self) -> Vec<&'static str> {
+        self.feature.parts()
+    }
+
+    fn all_names(&self) -> Vec<&'static str> {
+        let mut out = vec![self.name()];
+        out.extend(self.feature.all_names());
+        out
     }
 }
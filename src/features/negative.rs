@@ -18,7 +18,9 @@ where
     U: super::Feature + fmt::Debug + Clone,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Negative({:?})", self.features)
+        write!(f, "negative {{ ")?;
+        super::fmt_list(f, &self.features)?;
+        write!(f, " }}")
     }
 }
 
@@ -89,6 +91,17 @@ where
             acc
         })
     }
+
+    fn parts(&self) -> Vec<&'static str> {
+        self.features.iter().map(|f| f.parts()).flatten().collect()
+    }
+
+    fn all_names(&self) -> Vec<&'static str> {
+        self.features.iter().fold(vec![self.name()], |mut acc, f| {
+            acc.extend(f.all_names());
+            acc
+        })
+    }
 }
 
 #[cfg(test)]
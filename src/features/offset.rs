@@ -0,0 +1,180 @@
+use geo::{Coordinate, LineString, MultiPolygon, Polygon};
+use geo_booleanop::boolean::BooleanOp;
+use std::fmt;
+
+/// The number of line segments used to approximate the rounded corners
+/// of grown geometry.
+const CORNER_SEGMENTS: usize = 32;
+
+/// A feature which grows or shrinks the geometry of its contained
+/// feature by a fixed distance, rounding any corners it passes.
+#[derive(Debug, Clone)]
+pub struct Offset<U = super::Unit> {
+    inner: U,
+    amount: f64,
+}
+
+impl<U: super::Feature + fmt::Debug + Clone> Offset<U> {
+    /// Constructs a new offset feature. A positive amount inflates the
+    /// geometry, and a negative amount deflates it.
+    pub fn new(amount: f64, inner: U) -> Self {
+        Self { inner, amount }
+    }
+}
+
+/// Returns the region within `r` of the boundary of the geometry, as
+/// the union of a rectangle swept along each edge and a circle around
+/// each vertex.
+fn boundary_sweep(edge: &MultiPolygon<f64>, r: f64) -> Option<MultiPolygon<f64>> {
+    let rings = edge
+        .iter()
+        .map(|p| std::iter::once(p.exterior()).chain(p.interiors().iter()))
+        .flatten();
+
+    let mut parts: Vec<Polygon<f64>> = Vec::new();
+    for ring in rings {
+        for line in ring.lines() {
+            let (dx, dy) = (line.dx(), line.dy());
+            let len = (dx * dx + dy * dy).sqrt();
+            if len == 0. {
+                continue;
+            }
+            let n = Coordinate {
+                x: -dy / len * r,
+                y: dx / len * r,
+            };
+            parts.push(Polygon::new(
+                LineString(vec![
+                    line.start + n,
+                    line.end + n,
+                    line.end - n,
+                    line.start - n,
+                    line.start + n,
+                ]),
+                vec![],
+            ));
+        }
+        for c in ring.0.iter() {
+            parts.push(Polygon::new(
+                LineString(
+                    (0..=CORNER_SEGMENTS)
+                        .map(|i| {
+                            let a = (360. * i as f64 / CORNER_SEGMENTS as f64).to_radians();
+                            Coordinate {
+                                x: c.x + r * a.cos(),
+                                y: c.y + r * a.sin(),
+                            }
+                        })
+                        .collect(),
+                ),
+                vec![],
+            ));
+        }
+    }
+
+    parts.into_iter().fold(None, |acc, p| match acc {
+        Some(current) => Some(MultiPolygon::from(p).union(&current)),
+        None => Some(p.into()),
+    })
+}
+
+impl<U: super::Feature + fmt::Debug + Clone> Offset<U> {
+    fn offset(&self, edge: MultiPolygon<f64>) -> Option<MultiPolygon<f64>> {
+        use geo::algorithm::area::Area;
+        if self.amount == 0. {
+            return Some(edge);
+        }
+
+        let out = match boundary_sweep(&edge, self.amount.abs()) {
+            Some(sweep) if self.amount > 0. => edge.union(&sweep),
+            Some(sweep) => edge.difference(&sweep),
+            None => edge,
+        };
+
+        // Deflating past the middle of the geometry leaves nothing.
+        if out.0.is_empty() || out.unsigned_area() < 1e-9 {
+            None
+        } else {
+            Some(out)
+        }
+    }
+}
+
+impl<U> fmt::Display for Offset<U>
+where
+    U: super::Feature + fmt::Debug + Clone,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "offset({}) {{ {} }}", self.amount, self.inner)
+    }
+}
+
+impl<U> super::Feature for Offset<U>
+where
+    U: super::Feature + fmt::Debug + Clone,
+{
+    fn name(&self) -> &'static str {
+        "offset"
+    }
+
+    fn edge_union(&self) -> Option<MultiPolygon<f64>> {
+        self.inner.edge_union().and_then(|e| self.offset(e))
+    }
+
+    fn edge_subtract(&self) -> Option<MultiPolygon<f64>> {
+        self.inner.edge_subtract().and_then(|e| self.offset(e))
+    }
+
+    fn translate(&mut self, v: Coordinate<f64>) {
+        self.inner.translate(v);
+    }
+
+    fn interior(&self) -> Vec<super::InnerAtom> {
+        self.inner.interior()
+    }
+
+    /// named_info returns information about named geometry.
+    fn named_info(&self) -> Vec<super::NamedInfo> {
+        self.inner.named_info()
+    }
+
+    fn parts(&self) -> Vec<&'static str> {
+        self.inner.parts()
+    }
+
+    fn all_names(&self) -> Vec<&'static str> {
+        let mut out = vec![self.name()];
+        out.extend(self.inner.all_names());
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::{Feature, Rect};
+    use geo::bounding_rect::BoundingRect;
+
+    #[test]
+    fn grow() {
+        let o = Offset::new(1., Rect::with_center([0., 0.].into(), 5., 5.));
+        let b = o.edge_union().unwrap().bounding_rect().unwrap();
+        assert!(b.width() > 6.99 && b.width() < 7.01);
+        assert!(b.height() > 6.99 && b.height() < 7.01);
+        assert!(b.center().x.abs() < 0.01 && b.center().y.abs() < 0.01);
+    }
+
+    #[test]
+    fn shrink() {
+        let o = Offset::new(-1., Rect::with_center([0., 0.].into(), 5., 5.));
+        let b = o.edge_union().unwrap().bounding_rect().unwrap();
+        assert!(b.width() > 2.99 && b.width() < 3.01);
+        assert!(b.height() > 2.99 && b.height() < 3.01);
+    }
+
+    #[test]
+    fn collapse() {
+        let o = Offset::new(-3., Rect::with_center([0., 0.].into(), 5., 5.));
+        assert_eq!(o.edge_union(), None);
+    }
+}
@@ -0,0 +1,352 @@
+use crate::Err;
+use geo::{Coordinate, LineString, MultiPolygon, Polygon};
+use std::fmt;
+
+/// The number of line segments used to approximate curves.
+const CURVE_SEGMENTS: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token {
+    Command(char),
+    Number(f64),
+}
+
+fn tokenize(d: &str) -> Result<Vec<Token>, Err> {
+    let mut out = Vec::new();
+    let chars: Vec<char> = d.chars().collect();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() || c == ',' {
+            i += 1;
+        } else if c.is_ascii_alphabetic() && c != 'e' && c != 'E' {
+            out.push(Token::Command(c));
+            i += 1;
+        } else if c.is_ascii_digit() || c == '-' || c == '+' || c == '.' {
+            let start = i;
+            let (mut seen_dot, mut seen_exp) = (false, false);
+            i += 1;
+            if c == '.' {
+                seen_dot = true;
+            }
+            while i < chars.len() {
+                match chars[i] {
+                    '0'..='9' => {}
+                    '.' if !seen_dot && !seen_exp => seen_dot = true,
+                    'e' | 'E' if !seen_exp => {
+                        seen_exp = true;
+                        if i + 1 < chars.len() && (chars[i + 1] == '-' || chars[i + 1] == '+') {
+                            i += 1;
+                        }
+                    }
+                    _ => break,
+                }
+                i += 1;
+            }
+            let s: String = chars[start..i].iter().collect();
+            out.push(Token::Number(s.parse().map_err(|_| {
+                Err::BadSVGPath(format!("invalid number {:?}", s))
+            })?));
+        } else {
+            return Err(Err::BadSVGPath(format!("unexpected character {:?}", c)));
+        }
+    }
+    Ok(out)
+}
+
+/// Returns the points along an elliptical arc, as described by the SVG
+/// arc command, excluding the start point.
+fn arc_points(
+    from: Coordinate<f64>,
+    to: Coordinate<f64>,
+    rx: f64,
+    ry: f64,
+    rotation: f64,
+    large_arc: bool,
+    sweep: bool,
+) -> Vec<Coordinate<f64>> {
+    let (mut rx, mut ry) = (rx.abs(), ry.abs());
+    if rx == 0. || ry == 0. || from == to {
+        return vec![to];
+    }
+
+    // Conversion from endpoint to center parameterization, as described
+    // in the appendix of the SVG specification.
+    let (sin_phi, cos_phi) = rotation.to_radians().sin_cos();
+    let (dx, dy) = ((from.x - to.x) / 2., (from.y - to.y) / 2.);
+    let x1 = cos_phi * dx + sin_phi * dy;
+    let y1 = -sin_phi * dx + cos_phi * dy;
+
+    let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+    if lambda > 1. {
+        rx *= lambda.sqrt();
+        ry *= lambda.sqrt();
+    }
+
+    let num = rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+    let den = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+    let coef = (num / den).max(0.).sqrt() * if large_arc == sweep { -1. } else { 1. };
+    let (cx1, cy1) = (coef * rx * y1 / ry, -coef * ry * x1 / rx);
+    let cx = cos_phi * cx1 - sin_phi * cy1 + (from.x + to.x) / 2.;
+    let cy = sin_phi * cx1 + cos_phi * cy1 + (from.y + to.y) / 2.;
+
+    let angle = |ux: f64, uy: f64, vx: f64, vy: f64| (ux * vy - uy * vx).atan2(ux * vx + uy * vy);
+    let (ux, uy) = ((x1 - cx1) / rx, (y1 - cy1) / ry);
+    let (vx, vy) = ((-x1 - cx1) / rx, (-y1 - cy1) / ry);
+    let start = angle(1., 0., ux, uy);
+    let mut delta = angle(ux, uy, vx, vy);
+    if !sweep && delta > 0. {
+        delta -= 2. * std::f64::consts::PI;
+    } else if sweep && delta < 0. {
+        delta += 2. * std::f64::consts::PI;
+    }
+
+    let mut out: Vec<_> = (1..CURVE_SEGMENTS)
+        .map(|i| {
+            let (sin_t, cos_t) = (start + delta * i as f64 / CURVE_SEGMENTS as f64).sin_cos();
+            Coordinate {
+                x: cx + rx * cos_phi * cos_t - ry * sin_phi * sin_t,
+                y: cy + rx * sin_phi * cos_t + ry * cos_phi * sin_t,
+            }
+        })
+        .collect();
+    out.push(to);
+    out
+}
+
+/// Returns the points along a cubic bezier curve, excluding the start point.
+fn cubic_points(
+    p0: Coordinate<f64>,
+    p1: Coordinate<f64>,
+    p2: Coordinate<f64>,
+    p3: Coordinate<f64>,
+) -> Vec<Coordinate<f64>> {
+    (1..=CURVE_SEGMENTS)
+        .map(|i| {
+            let t = i as f64 / CURVE_SEGMENTS as f64;
+            let mt = 1. - t;
+            let (a, b, c, d) = (mt * mt * mt, 3. * mt * mt * t, 3. * mt * t * t, t * t * t);
+            Coordinate {
+                x: a * p0.x + b * p1.x + c * p2.x + d * p3.x,
+                y: a * p0.y + b * p1.y + c * p2.y + d * p3.y,
+            }
+        })
+        .collect()
+}
+
+/// Parses the `d` attribute of an SVG path into a sequence of rings, one
+/// for each subpath.
+fn parse_path(d: &str) -> Result<Vec<Vec<Coordinate<f64>>>, Err> {
+    let tokens = tokenize(d)?;
+    let mut rings: Vec<Vec<Coordinate<f64>>> = Vec::new();
+    let mut current: Vec<Coordinate<f64>> = Vec::new();
+    let mut pos = Coordinate { x: 0., y: 0. };
+    let mut cmd: Option<char> = None;
+    let mut i = 0;
+
+    let args = |i: &mut usize, n: usize| -> Result<Vec<f64>, Err> {
+        let mut out = Vec::with_capacity(n);
+        for _ in 0..n {
+            match tokens.get(*i) {
+                Some(Token::Number(v)) => out.push(*v),
+                _ => return Err(Err::BadSVGPath("missing command arguments".to_string())),
+            }
+            *i += 1;
+        }
+        Ok(out)
+    };
+
+    while i < tokens.len() {
+        let c = match tokens[i] {
+            Token::Command(c) => {
+                i += 1;
+                c
+            }
+            // Repeated arguments imply a repetition of the previous
+            // command, with moves being followed by lines.
+            Token::Number(_) => match cmd {
+                Some('M') => 'L',
+                Some('m') => 'l',
+                Some(c) if c != 'Z' && c != 'z' => c,
+                _ => return Err(Err::BadSVGPath("expected a command".to_string())),
+            },
+        };
+        cmd = Some(c);
+
+        let rel = c.is_ascii_lowercase();
+        let offset = if rel {
+            pos
+        } else {
+            Coordinate { x: 0., y: 0. }
+        };
+        match c.to_ascii_uppercase() {
+            'M' => {
+                let a = args(&mut i, 2)?;
+                if !current.is_empty() {
+                    rings.push(std::mem::take(&mut current));
+                }
+                pos = offset + Coordinate { x: a[0], y: a[1] };
+                current.push(pos);
+            }
+            'L' => {
+                let a = args(&mut i, 2)?;
+                pos = offset + Coordinate { x: a[0], y: a[1] };
+                current.push(pos);
+            }
+            'H' => {
+                let a = args(&mut i, 1)?;
+                pos.x = offset.x + a[0];
+                current.push(pos);
+            }
+            'V' => {
+                let a = args(&mut i, 1)?;
+                pos.y = offset.y + a[0];
+                current.push(pos);
+            }
+            'C' => {
+                let a = args(&mut i, 6)?;
+                let p1 = offset + Coordinate { x: a[0], y: a[1] };
+                let p2 = offset + Coordinate { x: a[2], y: a[3] };
+                let p3 = offset + Coordinate { x: a[4], y: a[5] };
+                current.extend(cubic_points(pos, p1, p2, p3));
+                pos = p3;
+            }
+            'A' => {
+                let a = args(&mut i, 7)?;
+                let to = offset + Coordinate { x: a[5], y: a[6] };
+                current.extend(arc_points(
+                    pos,
+                    to,
+                    a[0],
+                    a[1],
+                    a[2],
+                    a[3] != 0.,
+                    a[4] != 0.,
+                ));
+                pos = to;
+            }
+            'Z' => {
+                if let Some(start) = current.first() {
+                    pos = *start;
+                }
+                if !current.is_empty() {
+                    rings.push(std::mem::take(&mut current));
+                }
+            }
+            _ => {
+                return Err(Err::BadSVGPath(format!("unsupported command {:?}", c)));
+            }
+        }
+    }
+    if !current.is_empty() {
+        rings.push(current);
+    }
+
+    if rings.iter().any(|r| r.len() < 3) {
+        return Err(Err::BadSVGPath(
+            "subpaths must have at least 3 points".to_string(),
+        ));
+    }
+    Ok(rings)
+}
+
+/// A feature whose outer geometry is provided by an existing drawing,
+/// such as an SVG path.
+#[derive(Debug, Clone)]
+pub struct Outline {
+    poly: MultiPolygon<f64>,
+}
+
+impl Outline {
+    /// Constructs an outline from the `d` attribute of an SVG path. The
+    /// first subpath describes the exterior of the outline, and any
+    /// further subpaths describe holes within it. Arcs and curves are
+    /// approximated with line segments.
+    pub fn from_svg_path(d: &str) -> Result<Self, Err> {
+        let mut rings = parse_path(d)?.into_iter().map(LineString::from);
+        let exterior = rings
+            .next()
+            .ok_or_else(|| Err::BadSVGPath("path is empty".to_string()))?;
+
+        Ok(Self {
+            poly: Polygon::new(exterior, rings.collect()).into(),
+        })
+    }
+
+    /// Constructs an outline from a WKT `POLYGON` or `MULTIPOLYGON`.
+    pub fn from_wkt(wkt: &str) -> Result<Self, Err> {
+        Ok(Self {
+            poly: crate::wkt::parse(wkt).map_err(|e| Err::BadWKT(e))?,
+        })
+    }
+}
+
+impl fmt::Display for Outline {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "/* outline ({} polygons) */", self.poly.0.len())
+    }
+}
+
+impl super::Feature for Outline {
+    fn name(&self) -> &'static str {
+        "outline"
+    }
+
+    fn edge_union(&self) -> Option<MultiPolygon<f64>> {
+        if self.poly.0.len() > 0 {
+            Some(self.poly.clone())
+        } else {
+            None
+        }
+    }
+
+    fn translate(&mut self, v: Coordinate<f64>) {
+        use geo::algorithm::translate::Translate;
+        self.poly.translate_inplace(v.x, v.y);
+    }
+
+    fn interior(&self) -> Vec<super::InnerAtom> {
+        vec![]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::Feature;
+    use geo::bounding_rect::BoundingRect;
+
+    #[test]
+    fn square() {
+        let o = Outline::from_svg_path("M 0 0 L 10 0 L 10 10 L 0 10 Z").unwrap();
+        let b = o.edge_union().unwrap().bounding_rect().unwrap();
+        assert_eq!(b.min(), Coordinate { x: 0., y: 0. });
+        assert_eq!(b.max(), Coordinate { x: 10., y: 10. });
+    }
+
+    #[test]
+    fn relative_and_implicit() {
+        let o = Outline::from_svg_path("m1,1 4,0 0,2 h-4z").unwrap();
+        let b = o.edge_union().unwrap().bounding_rect().unwrap();
+        assert_eq!(b.min(), Coordinate { x: 1., y: 1. });
+        assert_eq!(b.max(), Coordinate { x: 5., y: 3. });
+    }
+
+    #[test]
+    fn arc() {
+        // A semicircle of radius 5, on one side of the x axis.
+        let o = Outline::from_svg_path("M -5 0 A 5 5 0 0 1 5 0 Z").unwrap();
+        let b = o.edge_union().unwrap().bounding_rect().unwrap();
+        assert!(b.width() > 9.99 && b.width() < 10.01);
+        assert!(b.height() > 4.9 && b.height() < 5.01);
+    }
+
+    #[test]
+    fn errors() {
+        assert!(Outline::from_svg_path("").is_err());
+        assert!(Outline::from_svg_path("M 0 0 L 1").is_err());
+        assert!(Outline::from_svg_path("M 0 0 Q 1 1 2 2 Z").is_err());
+        assert!(Outline::from_wkt("POLYGON ((0 0, 1 0").is_err());
+    }
+}
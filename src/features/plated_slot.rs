@@ -0,0 +1,50 @@
+use super::InnerAtom;
+use geo::Coordinate;
+use std::fmt;
+
+/// An interior feature representing an oblong plated hole, such as
+/// for the tabs of a connector.
+#[derive(Debug, Clone)]
+pub struct PlatedSlot {
+    start: Coordinate<f64>,
+    end: Coordinate<f64>,
+    width: f64,
+}
+
+impl PlatedSlot {
+    /// Creates a slot of the given width, whose ends are centered on the
+    /// given points relative to the center of the parent feature.
+    pub fn new(start: Coordinate<f64>, end: Coordinate<f64>, width: f64) -> Self {
+        Self { start, end, width }
+    }
+}
+
+impl fmt::Display for PlatedSlot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "slot<@({}, {}), @({}, {}), {}>",
+            self.start.x, self.start.y, self.end.x, self.end.y, self.width
+        )
+    }
+}
+
+impl super::InnerFeature for PlatedSlot {
+    fn name(&self) -> &'static str {
+        "slot"
+    }
+
+    fn translate(&mut self, v: Coordinate<f64>) {
+        self.start = self.start + v;
+        self.end = self.end + v;
+    }
+
+    fn atoms(&self) -> Vec<InnerAtom> {
+        vec![InnerAtom::Slot {
+            start: self.start,
+            end: self.end,
+            width: self.width,
+            plated: true,
+        }]
+    }
+}
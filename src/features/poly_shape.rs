@@ -0,0 +1,45 @@
+use geo::{Coordinate, MultiPolygon};
+use std::fmt;
+
+/// A feature described by arbitrary polygons, such as geometry
+/// imported from another tool.
+#[derive(Debug, Clone)]
+pub struct PolyShape {
+    poly: MultiPolygon<f64>,
+}
+
+impl PolyShape {
+    /// Constructs a feature with the given outer geometry.
+    pub fn new(poly: MultiPolygon<f64>) -> Self {
+        Self { poly }
+    }
+}
+
+impl fmt::Display for PolyShape {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "/* poly_shape ({} polygons) */", self.poly.0.len())
+    }
+}
+
+impl super::Feature for PolyShape {
+    fn name(&self) -> &'static str {
+        "poly_shape"
+    }
+
+    fn edge_union(&self) -> Option<MultiPolygon<f64>> {
+        if self.poly.0.len() > 0 {
+            Some(self.poly.clone())
+        } else {
+            None
+        }
+    }
+
+    fn translate(&mut self, v: Coordinate<f64>) {
+        use geo::algorithm::translate::Translate;
+        self.poly.translate_inplace(v.x, v.y);
+    }
+
+    fn interior(&self) -> Vec<super::InnerAtom> {
+        vec![]
+    }
+}
@@ -19,9 +19,49 @@ pub enum Positioning {
         degrees: f64,
         amount: f64,
     },
+    /// Places the center of the feature at the given coordinates in the
+    /// panel's coordinate system, ignoring the bounds of the inner feature.
+    AbsoluteXY {
+        x: f64,
+        y: f64,
+    },
+    /// Places the feature `gap` beyond the previous feature stacked on
+    /// the same side, or beyond the inner feature if there is none, so
+    /// successive features form a line.
+    Stack {
+        side: Direction,
+        gap: f64,
+    },
 }
 
 impl Positioning {
+    /// Returns `count` cardinal positionings on the given side, spaced
+    /// evenly from one end of the side to the other.
+    pub fn distributed(side: Direction, count: usize) -> Vec<Positioning> {
+        (0..count)
+            .map(|i| Positioning::Cardinal {
+                side,
+                centerline_adjustment: if count > 1 {
+                    i as f64 / (count - 1) as f64 - 0.5
+                } else {
+                    0.0
+                },
+                align: Align::Center,
+            })
+            .collect()
+    }
+
+    /// Returns `count` angle positionings at the given distance from the
+    /// center, spaced evenly around a full circle starting at 0 degrees.
+    pub fn radial(count: usize, radius: f64) -> Vec<Positioning> {
+        (0..count)
+            .map(|i| Positioning::Angle {
+                degrees: i as f64 * 360. / count as f64,
+                amount: radius,
+            })
+            .collect()
+    }
+
     fn compute_translation(&self, bounds: geo::Rect<f64>, feature: geo::Rect<f64>) -> (f64, f64) {
         match self {
             Positioning::Cardinal {
@@ -91,6 +131,25 @@ impl Positioning {
                     bounds.center().y + (amount * r.sin()),
                 )
             }
+            Positioning::AbsoluteXY { x, y } => (x - feature.center().x, y - feature.center().y),
+            Positioning::Stack { side, gap } => match side {
+                Direction::Left => (
+                    bounds.min().x - gap - feature.max().x,
+                    bounds.center().y - feature.center().y,
+                ),
+                Direction::Right => (
+                    bounds.max().x + gap - feature.min().x,
+                    bounds.center().y - feature.center().y,
+                ),
+                Direction::Up => (
+                    bounds.center().x - feature.center().x,
+                    bounds.min().y - gap - feature.max().y,
+                ),
+                Direction::Down => (
+                    bounds.center().x - feature.center().x,
+                    bounds.max().y + gap - feature.min().y,
+                ),
+            },
         }
     }
 
@@ -148,7 +207,9 @@ impl Positioning {
                     Align::End => feature.min().y,
                 },
             },
-            Positioning::Angle { .. } => unreachable!(),
+            Positioning::Angle { .. }
+            | Positioning::AbsoluteXY { .. }
+            | Positioning::Stack { .. } => unreachable!(),
         }
     }
 }
@@ -248,18 +309,25 @@ where
         self.elements.push((feature, pos));
     }
 
-    fn feature_bounds(&self, feature: &S) -> Option<geo::Rect<f64>> {
-        let union_bounds = if let Some(geo) = feature.edge_union() {
-            Some(compute_bounds(geo.clone()))
-        } else {
-            None
-        };
+    /// Adds `count` copies of a feature, spaced evenly along the given
+    /// side of the inner feature.
+    pub fn distribute_along(&mut self, side: Direction, count: usize, feature: S) {
+        for pos in Positioning::distributed(side, count) {
+            self.elements.push((feature.clone(), pos));
+        }
+    }
 
-        let subtract_bounds = if let Some(geo) = feature.edge_subtract() {
-            Some(compute_bounds(geo.clone()))
-        } else {
-            None
-        };
+    /// Adds `count` copies of a feature, spaced evenly around a circle
+    /// of the given radius about the center of the inner feature.
+    pub fn push_radial(&mut self, count: usize, radius: f64, feature: S) {
+        for pos in Positioning::radial(count, radius) {
+            self.elements.push((feature.clone(), pos));
+        }
+    }
+
+    fn feature_bounds(&self, feature: &S) -> Option<geo::Rect<f64>> {
+        let union_bounds = feature.edge_union().and_then(compute_bounds);
+        let subtract_bounds = feature.edge_subtract().and_then(compute_bounds);
 
         match (union_bounds, subtract_bounds) {
             (Some(b), None) => Some(b),
@@ -277,11 +345,76 @@ where
             (None, None) => None,
         }
     }
+
+    /// Returns the translation of each positioned feature, or None if the
+    /// feature has no geometry to position by.
+    fn translations(&self, bounds: geo::Rect<f64>) -> Vec<Option<(f64, f64)>> {
+        // The bounds of the last feature placed on each side of a stack.
+        let mut stacks: Vec<(Direction, geo::Rect<f64>)> = Vec::new();
+
+        self.elements
+            .iter()
+            .map(|(feature, position)| {
+                let feature_bounds = self.feature_bounds(feature)?;
+                let side = match position {
+                    Positioning::Stack { side, .. } => side,
+                    _ => return Some(position.compute_translation(bounds, feature_bounds)),
+                };
+
+                let reference = stacks
+                    .iter()
+                    .rev()
+                    .find(|(s, _)| s == side)
+                    .map(|(_, b)| *b)
+                    .unwrap_or(bounds);
+                let t = position.compute_translation(reference, feature_bounds);
+                let t_coord = Coordinate { x: t.0, y: t.1 };
+                stacks.push((
+                    *side,
+                    geo::Rect::new(
+                        feature_bounds.min() + t_coord,
+                        feature_bounds.max() + t_coord,
+                    ),
+                ));
+                Some(t)
+            })
+            .collect()
+    }
+
+    /// Returns the bounds other features are positioned relative to: the
+    /// outer geometry of the primary feature, or its subtracted geometry
+    /// if it has no outer geometry.
+    fn primary_bounds(&self) -> Option<geo::Rect<f64>> {
+        self.inner
+            .edge_union()
+            .or_else(|| self.inner.edge_subtract())
+            .and_then(compute_bounds)
+    }
+}
+
+impl<'a> AtPos<Box<dyn super::Feature + 'a>, Box<dyn super::Feature + 'a>> {
+    /// Wraps a boxed feature so other boxed features can be positioned
+    /// around it.
+    pub fn new_dyn(primary: Box<dyn super::Feature + 'a>) -> Self {
+        Self::new(primary)
+    }
+
+    /// Adds a feature to be positioned relative to the inner feature, if
+    /// one is provided. Does nothing if the feature is None.
+    pub fn with_optional_position(
+        &mut self,
+        feature: Option<Box<dyn super::Feature + 'a>>,
+        pos: Positioning,
+    ) {
+        if let Some(feature) = feature {
+            self.elements.push((feature, pos));
+        }
+    }
 }
 
-fn compute_bounds(poly: MultiPolygon<f64>) -> geo::Rect<f64> {
+fn compute_bounds(poly: MultiPolygon<f64>) -> Option<geo::Rect<f64>> {
     use geo::bounding_rect::BoundingRect;
-    poly.bounding_rect().unwrap()
+    poly.bounding_rect()
 }
 
 impl<U, S> fmt::Display for AtPos<U, S>
@@ -290,7 +423,55 @@ where
     S: super::Feature + std::fmt::Debug + Clone,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "pos(U = {}, S = {:?})", self.inner, self.elements,)
+        if self.elements.is_empty() {
+            return write!(f, "{}", self.inner);
+        }
+
+        write!(f, "wrap ({}) with {{ ", self.inner)?;
+        super::fmt_list(
+            f,
+            self.elements
+                .iter()
+                .map(|(feature, pos)| format!("{} => {}", pos, feature)),
+        )?;
+        write!(f, " }}")
+    }
+}
+
+impl fmt::Display for Positioning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let align = |a: &Align| match a {
+            Align::Start => "interior",
+            Align::Center => "center",
+            Align::End => "exterior",
+        };
+        match self {
+            Positioning::Cardinal {
+                side,
+                centerline_adjustment,
+                align: a,
+            } => {
+                write!(f, "{}", side)?;
+                if *centerline_adjustment != 0. {
+                    write!(f, "{:+}", centerline_adjustment)?;
+                }
+                write!(f, " align {}", align(a))
+            }
+            Positioning::Corner {
+                side,
+                opposite,
+                align: a,
+            } => write!(
+                f,
+                "{}{} align {}",
+                if *opposite { "max-" } else { "min-" },
+                side,
+                align(a)
+            ),
+            Positioning::Angle { degrees, amount } => write!(f, "angle({}){:+}", degrees, amount),
+            Positioning::AbsoluteXY { x, y } => write!(f, "absolute({}, {})", x, y),
+            Positioning::Stack { side, gap } => write!(f, "stack({}, {})", side, gap),
+        }
     }
 }
 
@@ -311,11 +492,13 @@ where
             Some(p) => p,
             None => MultiPolygon(vec![]),
         };
-        let bounds = compute_bounds(out.clone());
+        let bounds = match self.primary_bounds() {
+            Some(b) => b,
+            None => return self.inner.edge_union(),
+        };
 
-        for (feature, position) in &self.elements {
-            if let Some(mut geo) = feature.edge_union() {
-                let t = position.compute_translation(bounds, self.feature_bounds(feature).unwrap());
+        for ((feature, _), t) in self.elements.iter().zip(self.translations(bounds)) {
+            if let (Some(mut geo), Some(t)) = (feature.edge_union(), t) {
                 geo.translate_inplace(t.0, t.1);
                 out = out.union(&geo)
             }
@@ -329,21 +512,20 @@ where
     }
 
     fn edge_subtract(&self) -> Option<MultiPolygon<f64>> {
-        let bounds = compute_bounds(match self.inner.edge_union() {
-            Some(p) => p,
-            None => MultiPolygon(vec![]),
-        });
+        let bounds = match self.primary_bounds() {
+            Some(b) => b,
+            None => return self.inner.edge_subtract(),
+        };
 
         let mut out = match self.inner.edge_subtract() {
             Some(p) => p,
             None => MultiPolygon(vec![]),
         };
 
-        for (feature, position) in &self.elements {
-            if let Some(mut geo) = feature.edge_subtract() {
+        for ((feature, _), t) in self.elements.iter().zip(self.translations(bounds)) {
+            if let (Some(mut geo), Some(t)) = (feature.edge_subtract(), t) {
                 use geo::algorithm::translate::Translate;
                 use geo_booleanop::boolean::BooleanOp;
-                let t = position.compute_translation(bounds, self.feature_bounds(feature).unwrap());
                 geo.translate_inplace(t.0, t.1);
                 out = out.union(&geo)
             }
@@ -362,10 +544,10 @@ where
     }
 
     fn interior(&self) -> Vec<super::InnerAtom> {
-        let bounds = compute_bounds(match self.inner.edge_union() {
-            Some(p) => p,
-            None => MultiPolygon(vec![]),
-        });
+        let bounds = match self.primary_bounds() {
+            Some(b) => b,
+            None => return self.inner.interior(),
+        };
 
         self.inner
             .interior()
@@ -373,9 +555,9 @@ where
             .chain(
                 self.elements
                     .iter()
-                    .map(|(feature, position)| {
-                        if let Some(feature_bounds) = self.feature_bounds(feature) {
-                            let t = position.compute_translation(bounds, feature_bounds);
+                    .zip(self.translations(bounds))
+                    .map(|((feature, _), t)| {
+                        if let Some(t) = t {
                             let mut out = feature.interior();
                             for a in out.iter_mut() {
                                 a.translate(t.0, t.1);
@@ -392,21 +574,39 @@ where
 
     /// named_info returns information about named geometry.
     fn named_info(&self) -> Vec<super::NamedInfo> {
-        use geo_booleanop::boolean::BooleanOp;
-        let bounds = compute_bounds(match self.inner.edge_union() {
-            Some(p) => p,
-            None => MultiPolygon(vec![]),
-        });
+        let bounds = match self.primary_bounds() {
+            Some(b) => b,
+            None => return self.inner.named_info(),
+        };
 
-        self.elements
-            .iter()
-            .fold(self.inner.named_info(), |mut acc, (feature, position)| {
-                let t = position.compute_translation(bounds, self.feature_bounds(feature).unwrap());
+        self.elements.iter().zip(self.translations(bounds)).fold(
+            self.inner.named_info(),
+            |mut acc, ((feature, _), t)| {
+                let t = t.unwrap();
                 for mut info in feature.named_info() {
                     info.translate(t.0, t.1);
                     acc.push(info);
                 }
                 acc
+            },
+        )
+    }
+
+    fn parts(&self) -> Vec<&'static str> {
+        self.elements
+            .iter()
+            .fold(self.inner.parts(), |mut acc, (feature, _)| {
+                acc.extend(feature.parts());
+                acc
             })
     }
+
+    fn all_names(&self) -> Vec<&'static str> {
+        let mut out = vec![self.name()];
+        out.extend(self.inner.all_names());
+        for (feature, _) in &self.elements {
+            out.extend(feature.all_names());
+        }
+        out
+    }
 }
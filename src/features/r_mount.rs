@@ -32,7 +32,20 @@ impl RMount {
 
 impl fmt::Display for RMount {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "rmount({:?}, {:?})", self.rect.min(), self.rect.max(),)
+        super::fmt_translated(
+            f,
+            self.rect.center(),
+            format_args!(
+                "mount_cut{}<{}>",
+                match self.direction {
+                    crate::Direction::Up => "",
+                    crate::Direction::Down => "_down",
+                    crate::Direction::Left => "_left",
+                    crate::Direction::Right => "_right",
+                },
+                self.depth
+            ),
+        )
     }
 }
 
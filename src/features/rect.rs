@@ -1,11 +1,15 @@
-use geo::{Coordinate, MultiPolygon};
+use geo::{Coordinate, LineString, MultiPolygon, Polygon};
 use std::fmt;
 
-/// A rectangular region with square edges.
+/// The number of line segments used to approximate each rounded corner.
+const CORNER_SEGMENTS: usize = 8;
+
+/// A rectangular region with square or rounded edges.
 #[derive(Debug, Clone)]
 pub struct Rect<U = super::Unit> {
     rect: geo::Rect<f64>,
     inner: U,
+    rounding: Option<f64>,
 }
 
 impl Rect {
@@ -14,6 +18,7 @@ impl Rect {
         Self {
             rect: geo::Rect::new(top_left, bottom_right),
             inner: super::Unit,
+            rounding: None,
         }
     }
 
@@ -33,6 +38,7 @@ impl Rect {
                     },
             ),
             inner: super::Unit,
+            rounding: None,
         }
     }
 }
@@ -45,7 +51,11 @@ impl<U: super::InnerFeature + Clone + std::fmt::Debug> Rect<U> {
         let tl: Coordinate<f64> = [-1f64, -1f64].into();
         let br: Coordinate<f64> = [1f64, 1f64].into();
         let rect = geo::Rect::new(tl, br);
-        Self { rect, inner }
+        Self {
+            rect,
+            inner,
+            rounding: None,
+        }
     }
 
     /// Returns a new rectangle around the provided center.
@@ -66,6 +76,7 @@ impl<U: super::InnerFeature + Clone + std::fmt::Debug> Rect<U> {
         Self {
             rect,
             inner: self.inner,
+            rounding: self.rounding,
         }
     }
 
@@ -76,19 +87,69 @@ impl<U: super::InnerFeature + Clone + std::fmt::Debug> Rect<U> {
         Self {
             rect,
             inner: self.inner,
+            rounding: self.rounding,
+        }
+    }
+}
+
+impl<U> Rect<U> {
+    /// Returns a new rectangle with its corners rounded to the given
+    /// radius. The radius is limited to half the shortest side.
+    pub fn rounded(self, radius: f64) -> Self {
+        Self {
+            rounding: if radius > 0. { Some(radius) } else { None },
+            ..self
         }
     }
+
+    fn polygon(&self) -> Polygon<f64> {
+        let r = match self.rounding {
+            Some(r) => r.min(self.rect.width() / 2.).min(self.rect.height() / 2.),
+            None => return self.rect.clone().to_polygon(),
+        };
+        let (min, max) = (self.rect.min(), self.rect.max());
+
+        // Each corner is an arc about a center inset by the radius, with
+        // the corners visited in order of increasing angle.
+        let corners = [
+            (max.x - r, min.y + r, -90f64),
+            (max.x - r, max.y - r, 0.),
+            (min.x + r, max.y - r, 90.),
+            (min.x + r, min.y + r, 180.),
+        ];
+        let points: Vec<Coordinate<f64>> = corners
+            .iter()
+            .map(|(cx, cy, start)| {
+                (0..=CORNER_SEGMENTS).map(move |i| {
+                    let a = (start + 90. * i as f64 / CORNER_SEGMENTS as f64).to_radians();
+                    Coordinate {
+                        x: cx + r * a.cos(),
+                        y: cy + r * a.sin(),
+                    }
+                })
+            })
+            .flatten()
+            .collect();
+
+        Polygon::new(LineString::from(points), vec![])
+    }
 }
 
-impl<U: super::InnerFeature> fmt::Display for Rect<U> {
+impl<U: super::InnerFeature + Clone> fmt::Display for Rect<U> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let center = self.rect.center();
         write!(
             f,
-            "rect({:?}, {:?}, U = {})",
-            self.rect.min(),
-            self.rect.max(),
-            self.inner
-        )
+            "R<@({}, {}), {}, {}",
+            center.x,
+            center.y,
+            self.rect.width(),
+            self.rect.height()
+        )?;
+        if let Some(r) = self.rounding {
+            write!(f, ", round = {}", r)?;
+        }
+        write!(f, ">{}", super::fmt_inner(&self.inner, center))
     }
 }
 
@@ -98,7 +159,7 @@ impl<U: super::InnerFeature + Clone + std::fmt::Debug> super::Feature for Rect<U
     }
 
     fn edge_union(&self) -> Option<MultiPolygon<f64>> {
-        Some(self.rect.clone().to_polygon().into())
+        Some(self.polygon().into())
     }
 
     fn translate(&mut self, v: Coordinate<f64>) {
@@ -110,4 +171,58 @@ impl<U: super::InnerFeature + Clone + std::fmt::Debug> super::Feature for Rect<U
     fn interior(&self) -> Vec<super::InnerAtom> {
         self.inner.atoms()
     }
+
+    fn parts(&self) -> Vec<&'static str> {
+        match self.inner.name() {
+            "unit" => vec![self.name()],
+            inner => vec![self.name(), inner],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::Feature;
+
+    #[test]
+    fn square_corners() {
+        let r = Rect::new([0., 0.].into(), [4., 2.].into());
+        assert_eq!(r.edge_union().unwrap().0[0].exterior().0.len(), 5);
+    }
+
+    #[test]
+    fn rounded_corners() {
+        let r = Rect::new([0., 0.].into(), [10., 6.].into()).rounded(2.);
+        let poly = r.edge_union().unwrap();
+        let ring = &poly.0[0].exterior().0;
+        // Each corner contributes its arc endpoints and the points
+        // between them, plus the point closing the ring.
+        assert_eq!(ring.len(), 4 * (CORNER_SEGMENTS + 1) + 1);
+
+        let near =
+            |c: Coordinate<f64>, x: f64, y: f64| (c.x - x).abs() < 1e-9 && (c.y - y).abs() < 1e-9;
+        assert!(near(ring[0], 8., 0.));
+        assert!(near(ring[CORNER_SEGMENTS], 10., 2.));
+        // The midpoint of the arc lies on the 45 degree line from the
+        // center of the corner.
+        let mid = ring[CORNER_SEGMENTS / 2];
+        let d = 2. / 2f64.sqrt();
+        assert!(near(mid, 8. + d, 2. - d));
+
+        use geo::bounding_rect::BoundingRect;
+        let b = poly.bounding_rect().unwrap();
+        assert!(near(b.min(), 0., 0.));
+        assert!(near(b.max(), 10., 6.));
+    }
+
+    #[test]
+    fn rounding_limited() {
+        use geo::algorithm::area::Area;
+        let r = Rect::new([0., 0.].into(), [4., 2.].into()).rounded(5.);
+        // Limited to a radius of 1: a 2x2 square plus two semicircles.
+        let area = r.edge_union().unwrap().unsigned_area();
+        let want = 4. + std::f64::consts::PI;
+        assert!((area - want).abs() < 0.1);
+    }
 }
@@ -0,0 +1,128 @@
+use geo::{Coordinate, MultiPolygon, Polygon};
+use std::fmt;
+
+/// A region in the shape of a regular polygon, oriented such that its
+/// bottom edge is horizontal.
+#[derive(Debug, Clone)]
+pub struct RegularPolygon<U = super::Unit> {
+    center: Coordinate<f64>,
+    sides: u32,
+    circumradius: f64,
+    inner: U,
+}
+
+impl RegularPolygon {
+    /// Constructs a regular polygon with the given number of sides, whose
+    /// vertices lie on a circle of the given radius.
+    pub fn new(center: Coordinate<f64>, sides: u32, circumradius: f64) -> Self {
+        Self {
+            center,
+            sides: sides.max(3),
+            circumradius,
+            inner: super::Unit,
+        }
+    }
+}
+
+impl<U: super::InnerFeature + Clone> RegularPolygon<U> {
+    /// Constructs a regular polygon surrounding the inner feature. The
+    /// origin of the inner feature will be positioned at the
+    /// center of the polygon.
+    pub fn with_inner(
+        mut inner: U,
+        center: Coordinate<f64>,
+        sides: u32,
+        circumradius: f64,
+    ) -> Self {
+        inner.translate(center);
+
+        Self {
+            center,
+            sides: sides.max(3),
+            circumradius,
+            inner,
+        }
+    }
+}
+
+impl<U: super::InnerFeature + Clone> fmt::Display for RegularPolygon<U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "P<@({}, {}), {}, {}>{}",
+            self.center.x,
+            self.center.y,
+            self.sides,
+            self.circumradius,
+            super::fmt_inner(&self.inner, self.center)
+        )
+    }
+}
+
+impl<U: super::InnerFeature + Clone + std::fmt::Debug> super::Feature for RegularPolygon<U> {
+    fn name(&self) -> &'static str {
+        "regular_polygon"
+    }
+
+    fn edge_union(&self) -> Option<MultiPolygon<f64>> {
+        let step = 360.0 / self.sides as f64;
+        let start = 90.0 + step / 2.0;
+
+        let points = (0..self.sides)
+            .map(|i| {
+                let a = (start + step * i as f64).to_radians();
+                Coordinate {
+                    x: self.center.x + self.circumradius * a.cos(),
+                    y: self.center.y + self.circumradius * a.sin(),
+                }
+            })
+            .collect();
+
+        Some(Polygon::new(geo::LineString(points), vec![]).into())
+    }
+
+    fn translate(&mut self, v: Coordinate<f64>) {
+        self.center = self.center + v;
+        self.inner.translate(v);
+    }
+
+    fn interior(&self) -> Vec<super::InnerAtom> {
+        self.inner.atoms()
+    }
+
+    fn parts(&self) -> Vec<&'static str> {
+        match self.inner.name() {
+            "unit" => vec![self.name()],
+            inner => vec![self.name(), inner],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::Feature;
+    use geo::algorithm::bounding_rect::BoundingRect;
+
+    #[test]
+    fn square() {
+        let p = RegularPolygon::new([0., 0.].into(), 4, 2.);
+        let poly = p.edge_union().unwrap();
+        assert_eq!(poly.0[0].exterior().0.len(), 5);
+
+        let b = poly.bounding_rect().unwrap();
+        let side = 2. * 2f64.sqrt();
+        assert!((b.width() - side).abs() < 1e-9);
+        assert!((b.height() - side).abs() < 1e-9);
+    }
+
+    #[test]
+    fn hexagon() {
+        let p = RegularPolygon::new([1., 1.].into(), 6, 5.);
+        let b = p.edge_union().unwrap().bounding_rect().unwrap();
+        // Vertices lie on the left and right of the center, with
+        // flat edges at the top and bottom.
+        assert!((b.width() - 10.).abs() < 1e-9);
+        assert!((b.height() - 5. * 3f64.sqrt()).abs() < 1e-9);
+    }
+}
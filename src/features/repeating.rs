@@ -8,6 +8,9 @@ pub struct Tile<U = super::Rect> {
     direction: crate::Direction,
     amt: usize,
     v_score: bool,
+    reversed: bool,
+    flip: Option<crate::Direction>,
+    spacing: f64,
 }
 
 impl<U: super::Feature> Tile<U> {
@@ -19,6 +22,9 @@ impl<U: super::Feature> Tile<U> {
             direction,
             amt,
             v_score,
+            reversed: false,
+            flip: None,
+            spacing: 0.,
         }
     }
 
@@ -27,15 +33,172 @@ impl<U: super::Feature> Tile<U> {
         self.v_score = v_score;
         self
     }
+
+    /// Returns a new tiling feature which numbers its copies from
+    /// last to first.
+    pub fn reversed(mut self) -> Self {
+        self.reversed = true;
+        self
+    }
+
+    /// Returns a new tiling feature where every other copy is mirrored.
+    /// Copies are mirrored horizontally if the axis is left or right,
+    /// and vertically if the axis is up or down.
+    pub fn alternating_flip(mut self, flip_axis: crate::Direction) -> Self {
+        self.flip = Some(flip_axis);
+        self
+    }
+
+    /// Returns a new tiling feature which leaves a gap of `gap_mm`
+    /// between each copy.
+    pub fn with_spacing(mut self, gap_mm: f64) -> Self {
+        self.spacing = gap_mm;
+        self
+    }
+
+    /// Returns the position along the tiling direction of the given copy.
+    fn position(&self, i: usize) -> usize {
+        if self.reversed {
+            self.amt - 1 - i
+        } else {
+            i
+        }
+    }
+
+    /// Returns the offset between adjacent copies of geometry with the
+    /// given bounds.
+    fn step(&self, bounds: geo::Rect<f64>) -> (f64, f64) {
+        let (x, y) = self.direction.offset(bounds);
+        match self.direction {
+            crate::Direction::Left => (x - self.spacing, y),
+            crate::Direction::Right => (x + self.spacing, y),
+            crate::Direction::Up => (x, y - self.spacing),
+            crate::Direction::Down => (x, y + self.spacing),
+        }
+    }
+
+    /// Returns the axis the copy at the given position is mirrored on, if any.
+    fn flip_at(&self, pos: usize) -> Option<&crate::Direction> {
+        match &self.flip {
+            Some(axis) if pos % 2 == 1 => Some(axis),
+            _ => None,
+        }
+    }
+}
+
+/// Mirrors the coordinate around the center, on the given axis.
+fn mirror(c: Coordinate<f64>, center: Coordinate<f64>, axis: &crate::Direction) -> Coordinate<f64> {
+    match axis {
+        crate::Direction::Left | crate::Direction::Right => Coordinate {
+            x: 2. * center.x - c.x,
+            y: c.y,
+        },
+        crate::Direction::Up | crate::Direction::Down => Coordinate {
+            x: c.x,
+            y: 2. * center.y - c.y,
+        },
+    }
+}
+
+pub(super) fn mirror_geo(
+    geo: &MultiPolygon<f64>,
+    center: Coordinate<f64>,
+    axis: &crate::Direction,
+) -> MultiPolygon<f64> {
+    use geo::algorithm::map_coords::MapCoords;
+    geo.map_coords(|&(x, y)| mirror(Coordinate { x, y }, center, axis).x_y())
+}
+
+fn mirror_rect(
+    rect: geo::Rect<f64>,
+    center: Coordinate<f64>,
+    axis: &crate::Direction,
+) -> geo::Rect<f64> {
+    geo::Rect::new(
+        mirror(rect.min(), center, axis),
+        mirror(rect.max(), center, axis),
+    )
+}
+
+pub(super) fn mirror_atom(
+    atom: &super::InnerAtom,
+    center: Coordinate<f64>,
+    axis: &crate::Direction,
+) -> super::InnerAtom {
+    use super::InnerAtom;
+    match atom.clone() {
+        InnerAtom::Drill {
+            center: c,
+            radius,
+            plated,
+        } => InnerAtom::Drill {
+            center: mirror(c, center, axis),
+            radius,
+            plated,
+        },
+        InnerAtom::Slot {
+            start,
+            end,
+            width,
+            plated,
+        } => InnerAtom::Slot {
+            start: mirror(start, center, axis),
+            end: mirror(end, center, axis),
+            width,
+            plated,
+        },
+        InnerAtom::Circle {
+            center: c,
+            radius,
+            layer,
+        } => InnerAtom::Circle {
+            center: mirror(c, center, axis),
+            radius,
+            layer,
+        },
+        InnerAtom::Rect { rect, layer } => InnerAtom::Rect {
+            rect: mirror_rect(rect, center, axis),
+            layer,
+        },
+        InnerAtom::Line {
+            start,
+            end,
+            width,
+            layer,
+        } => InnerAtom::Line {
+            start: mirror(start, center, axis),
+            end: mirror(end, center, axis),
+            width,
+            layer,
+        },
+        InnerAtom::VScoreH(y) => InnerAtom::VScoreH(mirror([0., y].into(), center, axis).y),
+        InnerAtom::VScoreV(x) => InnerAtom::VScoreV(mirror([x, 0.].into(), center, axis).x),
+    }
 }
 
 impl<U: super::Feature> fmt::Display for Tile<U> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "repeating::Tile<{}>({} = {})",
-            self.inner, self.direction, self.amt
-        )
+            "[{}; {}",
+            self.amt,
+            match self.direction {
+                crate::Direction::Left => 'L',
+                crate::Direction::Right => 'R',
+                crate::Direction::Up => 'U',
+                crate::Direction::Down => 'D',
+            }
+        )?;
+        if self.v_score {
+            write!(f, "; v-score")?;
+        }
+        if self.flip.is_some() {
+            write!(f, "; flip")?;
+        }
+        if self.spacing != 0. {
+            write!(f, "; gap = {}", self.spacing)?;
+        }
+        write!(f, "] {}", self.inner)
     }
 }
 
@@ -56,9 +219,13 @@ impl<U: super::Feature + Clone> super::Feature for Tile<U> {
                 };
 
                 for i in 0..self.amt {
-                    let mut next = sub_geo.clone();
-                    let (x, y) = self.direction.offset(bounds);
-                    next.translate_inplace(i as f64 * x, i as f64 * y);
+                    let pos = self.position(i);
+                    let mut next = match self.flip_at(pos) {
+                        Some(axis) => mirror_geo(&sub_geo, bounds.center(), axis),
+                        None => sub_geo.clone(),
+                    };
+                    let (x, y) = self.step(bounds);
+                    next.translate_inplace(pos as f64 * x, pos as f64 * y);
 
                     use geo_booleanop::boolean::BooleanOp;
                     out = out.union(&next);
@@ -78,9 +245,13 @@ impl<U: super::Feature + Clone> super::Feature for Tile<U> {
                 let bounds = edge_geo.bounding_rect().unwrap();
 
                 for i in 0..self.amt {
-                    let mut next = edge_geo.clone();
-                    let (x, y) = self.direction.offset(bounds);
-                    next.translate_inplace(i as f64 * x, i as f64 * y);
+                    let pos = self.position(i);
+                    let mut next = match self.flip_at(pos) {
+                        Some(axis) => mirror_geo(&edge_geo, bounds.center(), axis),
+                        None => edge_geo.clone(),
+                    };
+                    let (x, y) = self.step(bounds);
+                    next.translate_inplace(pos as f64 * x, pos as f64 * y);
 
                     use geo_booleanop::boolean::BooleanOp;
                     out = out.union(&next);
@@ -103,9 +274,13 @@ impl<U: super::Feature + Clone> super::Feature for Tile<U> {
         let mut out = vec![];
 
         for i in 0..self.amt {
+            let pos = self.position(i);
             for mut info in self.inner.named_info() {
-                let (x, y) = self.direction.offset(bounds);
-                info.translate(i as f64 * x, i as f64 * y);
+                if let Some(axis) = self.flip_at(pos) {
+                    info.bounds = mirror_rect(info.bounds, bounds.center(), axis);
+                }
+                let (x, y) = self.step(bounds);
+                info.translate(pos as f64 * x, pos as f64 * y);
                 info.name_index(i);
                 out.push(info);
             }
@@ -114,6 +289,20 @@ impl<U: super::Feature + Clone> super::Feature for Tile<U> {
         out
     }
 
+    fn parts(&self) -> Vec<&'static str> {
+        let inner = self.inner.parts();
+        (0..self.amt).map(|_| inner.clone()).flatten().collect()
+    }
+
+    fn all_names(&self) -> Vec<&'static str> {
+        let inner = self.inner.all_names();
+        let mut out = vec![self.name()];
+        for _ in 0..self.amt {
+            out.extend(inner.iter());
+        }
+        out
+    }
+
     fn translate(&mut self, v: Coordinate<f64>) {
         self.inner.translate(v)
     }
@@ -142,17 +331,24 @@ impl<U: super::Feature + Clone> super::Feature for Tile<U> {
         };
 
         for i in 0..self.amt {
-            let (x, y) = self.direction.offset(bounds);
-            let (x, y) = (i as f64 * x, i as f64 * y);
+            let pos = self.position(i);
+            let (x, y) = self.step(bounds);
+            let (x, y) = (pos as f64 * x, pos as f64 * y);
 
             for v in inner.iter() {
-                let mut v = v.clone();
+                let mut v = match self.flip_at(pos) {
+                    Some(axis) => mirror_atom(v, bounds.center(), axis),
+                    None => v.clone(),
+                };
                 v.translate(x, y);
                 out.push(v);
             }
 
-            if self.v_score && i < self.amt - 1 {
-                let (x, y) = (x + bounds.width() / 2., y + bounds.height() / 2.);
+            if self.v_score && pos < self.amt - 1 {
+                let (x, y) = (
+                    x + (bounds.width() + self.spacing) / 2.,
+                    y + (bounds.height() + self.spacing) / 2.,
+                );
 
                 out.push(match self.direction {
                     crate::Direction::Left | crate::Direction::Right => {
@@ -164,4 +360,100 @@ impl<U: super::Feature + Clone> super::Feature for Tile<U> {
         }
         out
     }
+
+    fn tile_boundaries(&self) -> Vec<super::InnerAtom> {
+        use geo::bounding_rect::BoundingRect;
+        let bounds = match self.inner.edge_union().and_then(|e| e.bounding_rect()) {
+            Some(b) => b,
+            None => return vec![],
+        };
+
+        // Boundaries lie in the middle of any spacing between copies.
+        let (w, h) = (
+            bounds.width() + self.spacing,
+            bounds.height() + self.spacing,
+        );
+        let half = self.spacing / 2.;
+        (0..self.amt.saturating_sub(1))
+            .map(|i| {
+                let i = i as f64;
+                match self.direction {
+                    crate::Direction::Right => {
+                        super::InnerAtom::VScoreV(bounds.max().x + half + i * w)
+                    }
+                    crate::Direction::Left => {
+                        super::InnerAtom::VScoreV(bounds.min().x - half - i * w)
+                    }
+                    crate::Direction::Down => {
+                        super::InnerAtom::VScoreH(bounds.max().y + half + i * h)
+                    }
+                    crate::Direction::Up => {
+                        super::InnerAtom::VScoreH(bounds.min().y - half - i * h)
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::{Feature, Named, Rect, Triangle};
+    use crate::Direction;
+
+    #[test]
+    fn reversed() {
+        use geo::bounding_rect::BoundingRect;
+        let inner = Named::new("a".into(), Rect::with_center([0., 0.].into(), 2., 2.));
+        let normal = Tile::new(inner.clone(), Direction::Right, 3);
+        let reversed = Tile::new(inner, Direction::Right, 3).reversed();
+
+        let (a, b) = (
+            normal.edge_union().unwrap().bounding_rect().unwrap(),
+            reversed.edge_union().unwrap().bounding_rect().unwrap(),
+        );
+        assert!((a.width() - b.width()).abs() < 0.001);
+        assert!((a.min().x - b.min().x).abs() < 0.001);
+
+        let (normal, reversed) = (normal.named_info(), reversed.named_info());
+        assert!(normal[0].bounds.center().x < 0.01);
+        assert!(reversed[0].bounds.center().x > 3.99);
+        assert!(reversed[2].bounds.center().x < 0.01);
+    }
+
+    #[test]
+    fn alternating_flip() {
+        use geo::prelude::Contains;
+        let inner = Triangle::right_angle(2., 2.);
+        let normal = Tile::new(inner.clone(), Direction::Right, 2);
+        let flipped = Tile::new(inner, Direction::Right, 2).alternating_flip(Direction::Right);
+
+        let pt = Coordinate { x: 2.6, y: -0.2 };
+        assert!(!normal.edge_union().unwrap().contains(&pt));
+        assert!(flipped.edge_union().unwrap().contains(&pt));
+        // The first copy should not be mirrored.
+        let pt = Coordinate { x: -0.6, y: 0.2 };
+        assert!(flipped.edge_union().unwrap().contains(&pt));
+    }
+
+    #[test]
+    fn spacing() {
+        use geo::bounding_rect::BoundingRect;
+        let t = Tile::new(
+            Rect::with_center([0., 0.].into(), 5., 5.),
+            Direction::Right,
+            3,
+        )
+        .with_spacing(1.5);
+
+        let edges = t.edge_union().unwrap();
+        assert_eq!(edges.0.len(), 3);
+        let bounds = edges.bounding_rect().unwrap();
+        assert!(bounds.width() > 17.99 && bounds.width() < 18.01);
+        assert!(matches!(
+            t.tile_boundaries()[0],
+            crate::features::InnerAtom::VScoreV(x) if x > 3.24 && x < 3.26
+        ));
+    }
 }
@@ -19,7 +19,9 @@ where
     U: super::Feature + fmt::Debug + Clone,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Rotate({:?}, {:?})", self.rotate, self.features)
+        write!(f, "rotate({}) {{ ", self.rotate)?;
+        super::fmt_list(f, &self.features)?;
+        write!(f, " }}")
     }
 }
 
@@ -82,7 +84,24 @@ where
     }
 
     fn interior(&self) -> Vec<super::InnerAtom> {
-        vec![]
+        use geo::algorithm::centroid::Centroid;
+
+        self.features
+            .iter()
+            .map(|f| {
+                // Edge geometry is rotated about its centroid, so the
+                // interior must be rotated about the same point.
+                let pivot = match f.edge_union().and_then(|e| e.centroid()) {
+                    Some(p) => p.into(),
+                    None => Coordinate { x: 0., y: 0. },
+                };
+                f.interior().into_iter().map(move |mut a| {
+                    a.rotate_around(self.rotate, pivot);
+                    a
+                })
+            })
+            .flatten()
+            .collect()
     }
 
     /// named_info returns information about named geometry.
@@ -94,11 +113,26 @@ where
                 for info in infos {
                     use geo::algorithm::{bounding_rect::BoundingRect, rotate::Rotate};
                     let b = geo::Polygon::from(info.bounds).rotate(self.rotate);
-                    acc.push(super::NamedInfo::new(info.name, b.bounding_rect().unwrap()));
+                    acc.push(super::NamedInfo::new(
+                        info.name,
+                        info.kind,
+                        b.bounding_rect().unwrap(),
+                    ));
                 }
                 acc
             })
     }
+
+    fn parts(&self) -> Vec<&'static str> {
+        self.features.iter().map(|f| f.parts()).flatten().collect()
+    }
+
+    fn all_names(&self) -> Vec<&'static str> {
+        self.features.iter().fold(vec![self.name()], |mut acc, f| {
+            acc.extend(f.all_names());
+            acc
+        })
+    }
 }
 
 #[cfg(test)]
@@ -140,4 +174,19 @@ mod tests {
         assert!(centroid.x() > -0.01 && centroid.x() < 0.01);
         assert!(centroid.y() > -0.01 && centroid.y() < 0.01);
     }
+
+    #[test]
+    fn interior() {
+        use crate::features::{InnerAtom, ScrewHole};
+        let r = Rect::with_inner(ScrewHole::default()).bounds([0., 0.].into(), [4., 2.].into());
+        let a = Rotate::new(90.0, vec![r]);
+
+        let atoms = a.interior();
+        assert!(atoms.len() > 0);
+        // The hole is at the centroid, so remains in place.
+        assert!(atoms
+            .iter()
+            .any(|a| matches!(a, InnerAtom::Drill { center, .. }
+            if (center.x - 2.).abs() < 1e-9 && (center.y - 1.).abs() < 1e-9)));
+    }
 }
@@ -9,6 +9,14 @@ pub struct ScrewHole {
     center: Coordinate<f64>,
     drill_radius: f64,
     annular_ring_radius: f64,
+    counter_sink: Option<CounterSink>,
+}
+
+/// Describes the conical entry of a countersunk hole.
+#[derive(Debug, Clone)]
+struct CounterSink {
+    angle: f64,
+    outer_radius: f64,
 }
 
 impl ScrewHole {
@@ -20,6 +28,18 @@ impl ScrewHole {
             ..Self::default()
         }
     }
+
+    /// Returns a new screw hole which is countersunk on the front side,
+    /// with the given countersink angle and diameter at the surface.
+    pub fn with_counter_sink(self, angle_degrees: f64, outer_diameter: f64) -> Self {
+        Self {
+            counter_sink: Some(CounterSink {
+                angle: angle_degrees,
+                outer_radius: outer_diameter / 2.0,
+            }),
+            ..self
+        }
+    }
 }
 
 impl Default for ScrewHole {
@@ -28,17 +48,18 @@ impl Default for ScrewHole {
             center: [0., 0.].into(),
             drill_radius: 1.55,
             annular_ring_radius: 2.75,
+            counter_sink: None,
         }
     }
 }
 
 impl fmt::Display for ScrewHole {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            f,
-            "drill(center = {:?}, {}/{})",
-            self.center, self.drill_radius, self.annular_ring_radius
-        )
+        write!(f, "h{}", self.drill_radius * 2.)?;
+        if let Some(cs) = &self.counter_sink {
+            write!(f, ", cs={}", cs.angle)?;
+        }
+        Ok(())
     }
 }
 
@@ -52,7 +73,23 @@ impl super::InnerFeature for ScrewHole {
     }
 
     fn atoms(&self) -> Vec<InnerAtom> {
-        vec![
+        // The courtyard covers everything the fastener touches.
+        let courtyard_radius = match &self.counter_sink {
+            Some(cs) => cs.outer_radius.max(self.annular_ring_radius),
+            None => self.annular_ring_radius,
+        };
+
+        let mut out = vec![
+            InnerAtom::Circle {
+                center: self.center,
+                radius: courtyard_radius,
+                layer: Layer::CourtyardBack,
+            },
+            InnerAtom::Circle {
+                center: self.center,
+                radius: courtyard_radius,
+                layer: Layer::CourtyardFront,
+            },
             InnerAtom::Circle {
                 center: self.center,
                 radius: self.annular_ring_radius,
@@ -78,6 +115,43 @@ impl super::InnerFeature for ScrewHole {
                 radius: self.drill_radius,
                 plated: true,
             },
-        ]
+        ];
+
+        if let Some(cs) = &self.counter_sink {
+            out.push(InnerAtom::Circle {
+                center: self.center,
+                radius: cs.outer_radius,
+                layer: Layer::FrontCopper,
+            });
+            out.push(InnerAtom::Circle {
+                center: self.center,
+                radius: cs.outer_radius,
+                layer: Layer::FrontMask,
+            });
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::InnerFeature;
+
+    #[test]
+    fn counter_sink() {
+        let plain = ScrewHole::with_diameter(3.);
+        let cs = plain.clone().with_counter_sink(90., 6.);
+        assert!(cs.atoms().len() > plain.atoms().len());
+
+        let outer = cs
+            .atoms()
+            .into_iter()
+            .filter_map(|a| match a {
+                InnerAtom::Circle { radius, .. } => Some(radius),
+                _ => None,
+            })
+            .fold(0., f64::max);
+        assert!(outer > 2.99 && outer < 3.01);
     }
 }
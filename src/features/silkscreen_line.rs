@@ -0,0 +1,83 @@
+use super::InnerAtom;
+use crate::Layer;
+use geo::Coordinate;
+use std::fmt;
+
+/// An interior feature representing a straight line drawn on the
+/// front silkscreen.
+#[derive(Debug, Clone)]
+pub struct SilkscreenLine {
+    start: Coordinate<f64>,
+    end: Coordinate<f64>,
+    width: f64,
+    layer: Layer,
+}
+
+impl SilkscreenLine {
+    /// Creates a line between the given points, relative to the center
+    /// of the parent feature.
+    pub fn new(start: Coordinate<f64>, end: Coordinate<f64>, width: f64) -> Self {
+        Self {
+            start,
+            end,
+            width,
+            layer: Layer::FrontLegend,
+        }
+    }
+
+    /// Draws the line on the specified layer instead, such as a copper
+    /// layer to form a trace.
+    pub fn on_layer(mut self, layer: Layer) -> Self {
+        self.layer = layer;
+        self
+    }
+}
+
+impl fmt::Display for SilkscreenLine {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line<@({}, {}), @({}, {}), width = {}>",
+            self.start.x, self.start.y, self.end.x, self.end.y, self.width
+        )
+    }
+}
+
+impl super::InnerFeature for SilkscreenLine {
+    fn name(&self) -> &'static str {
+        "line"
+    }
+
+    fn translate(&mut self, v: Coordinate<f64>) {
+        self.start = self.start + v;
+        self.end = self.end + v;
+    }
+
+    fn atoms(&self) -> Vec<InnerAtom> {
+        vec![InnerAtom::Line {
+            start: self.start,
+            end: self.end,
+            width: self.width,
+            layer: self.layer.clone(),
+        }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::InnerFeature;
+
+    #[test]
+    fn bounds() {
+        let mut l = SilkscreenLine::new([-1., 0.].into(), [1., 2.].into(), 0.2);
+        l.translate([1., 1.].into());
+        let atoms = l.atoms();
+        assert_eq!(atoms.len(), 1);
+        assert_eq!(atoms[0].layer(), Some(Layer::FrontLegend));
+
+        let b = atoms[0].bounds().unwrap();
+        assert!(b.min().x > -0.11 && b.min().x < -0.09);
+        assert!(b.max().y > 3.09 && b.max().y < 3.11);
+    }
+}
@@ -0,0 +1,115 @@
+use super::InnerAtom;
+use crate::Layer;
+use geo::Coordinate;
+use std::fmt;
+
+/// An interior feature representing a line of text drawn on the
+/// silkscreen, using the 6x8 pixel font.
+#[derive(Debug, Clone)]
+pub struct SilkscreenText {
+    text: String,
+    position: Coordinate<f64>,
+    char_height: f64,
+    layer: Layer,
+}
+
+impl SilkscreenText {
+    /// Creates text with its top-left corner at the given position, relative
+    /// to the center of the parent feature. As with text in gerber output,
+    /// lines of pixels run from the top of each character towards negative y.
+    pub fn new(text: String, position: Coordinate<f64>, char_height: f64) -> Self {
+        Self {
+            text,
+            position,
+            char_height,
+            layer: Layer::FrontLegend,
+        }
+    }
+
+    /// Draws the text on the specified layer instead, such as the back
+    /// silkscreen.
+    pub fn on_layer(mut self, layer: Layer) -> Self {
+        self.layer = layer;
+        self
+    }
+}
+
+impl fmt::Display for SilkscreenText {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "text<@({}, {}), \"{}\", size = {}>",
+            self.position.x, self.position.y, self.text, self.char_height
+        )
+    }
+}
+
+impl super::InnerFeature for SilkscreenText {
+    fn name(&self) -> &'static str {
+        "text"
+    }
+
+    fn translate(&mut self, v: Coordinate<f64>) {
+        self.position = self.position + v;
+    }
+
+    #[cfg(feature = "text")]
+    fn atoms(&self) -> Vec<InnerAtom> {
+        let pixel = self.char_height / 8.;
+        let mut out = Vec::new();
+
+        for (i, c) in self.text.chars().enumerate() {
+            for y in 0..8 {
+                for x in 0..6 {
+                    if crate::text::character_pixel(c, x, y) {
+                        let min = Coordinate {
+                            x: self.position.x + (i * 6 + x as usize) as f64 * pixel,
+                            y: self.position.y - (y + 1) as f64 * pixel,
+                        };
+                        out.push(InnerAtom::Rect {
+                            rect: geo::Rect::new(min, min + Coordinate { x: pixel, y: pixel }),
+                            layer: self.layer.clone(),
+                        });
+                    }
+                }
+            }
+        }
+        out
+    }
+
+    #[cfg(not(feature = "text"))]
+    fn atoms(&self) -> Vec<InnerAtom> {
+        vec![]
+    }
+}
+
+#[cfg(all(test, feature = "text"))]
+mod tests {
+    use super::*;
+    use crate::features::InnerFeature;
+
+    #[test]
+    fn pixels() {
+        let t = SilkscreenText::new("REV1".into(), [0., 0.].into(), 1.6);
+        let set = "REV1"
+            .chars()
+            .map(|c| {
+                (0..8)
+                    .map(|y| {
+                        (0..6)
+                            .filter(|x| crate::text::character_pixel(c, *x, y))
+                            .count()
+                    })
+                    .sum::<usize>()
+            })
+            .sum::<usize>();
+
+        let atoms = t.atoms();
+        assert!(set > 0);
+        assert_eq!(atoms.len(), set);
+        assert!(
+            matches!(&atoms[0], InnerAtom::Rect { rect, layer: Layer::FrontLegend }
+            if (rect.width() - 0.2).abs() < 1e-9 && rect.max().y <= 0.)
+        );
+    }
+}
@@ -0,0 +1,109 @@
+use super::InnerAtom;
+use crate::{Layer, Side};
+use geo::{Coordinate, Rect};
+use std::fmt;
+
+/// An interior feature representing a rectangular surface-mount pad,
+/// exposed through the soldermask on one side of the board.
+#[derive(Debug, Clone)]
+pub struct SMDPad {
+    center: Coordinate<f64>,
+    size: (f64, f64),
+    side: Side,
+}
+
+impl SMDPad {
+    /// Creates a pad of the given width and height on the front of the
+    /// board, at a position relative to the center of the parent feature.
+    pub fn new(center: Coordinate<f64>, width: f64, height: f64) -> Self {
+        Self {
+            center,
+            size: (width, height),
+            side: Side::Front,
+        }
+    }
+
+    /// Returns a pad on the given side of the board.
+    pub fn on_side(self, side: Side) -> Self {
+        Self { side, ..self }
+    }
+
+    fn rect(&self) -> Rect<f64> {
+        let half = Coordinate {
+            x: self.size.0 / 2.,
+            y: self.size.1 / 2.,
+        };
+        Rect::new(self.center - half, self.center + half)
+    }
+}
+
+impl fmt::Display for SMDPad {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "pad<@({}, {}), {}, {}{}>",
+            self.center.x,
+            self.center.y,
+            self.size.0,
+            self.size.1,
+            if self.side == Side::Back {
+                ", back"
+            } else {
+                ""
+            }
+        )
+    }
+}
+
+impl super::InnerFeature for SMDPad {
+    fn name(&self) -> &'static str {
+        "pad"
+    }
+
+    fn translate(&mut self, v: Coordinate<f64>) {
+        self.center = self.center + v;
+    }
+
+    fn atoms(&self) -> Vec<InnerAtom> {
+        let layers = match self.side {
+            Side::Front => [Layer::FrontCopper, Layer::FrontMask],
+            Side::Back => [Layer::BackCopper, Layer::BackMask],
+        };
+        layers
+            .iter()
+            .map(|layer| InnerAtom::Rect {
+                rect: self.rect(),
+                layer: layer.clone(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::InnerFeature;
+
+    #[test]
+    fn front() {
+        let atoms = SMDPad::new([0., 0.].into(), 2., 1.).atoms();
+        assert_eq!(atoms.len(), 2);
+        assert_eq!(atoms[0].layer(), Some(Layer::FrontCopper));
+        assert_eq!(atoms[1].layer(), Some(Layer::FrontMask));
+        assert!(
+            matches!(atoms[0], InnerAtom::Rect { rect, .. } if rect.width() == 2. && rect.height() == 1.)
+        );
+    }
+
+    #[test]
+    fn back() {
+        let mut pad = SMDPad::new([0., 0.].into(), 2., 1.).on_side(Side::Back);
+        pad.translate([3., 1.].into());
+        let atoms = pad.atoms();
+        assert_eq!(atoms[0].layer(), Some(Layer::BackCopper));
+        assert_eq!(atoms[1].layer(), Some(Layer::BackMask));
+        assert!(
+            matches!(atoms[1], InnerAtom::Rect { rect, .. } if rect.center() == Coordinate { x: 3., y: 1. })
+        );
+    }
+}
@@ -19,7 +19,7 @@ impl Default for Smiley {
 
 impl fmt::Display for Smiley {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "smiley(center = {:?})", self.center,)
+        write!(f, "smiley")
     }
 }
 
@@ -0,0 +1,114 @@
+use geo::{Coordinate, MultiPolygon};
+use std::fmt;
+
+/// A feature which moves its inner feature so the center of its
+/// bounds lies on the nearest point of a grid.
+#[derive(Debug, Clone)]
+pub struct SnapToGrid<U = super::Unit> {
+    inner: U,
+    grid: f64,
+}
+
+impl<U: super::Feature + fmt::Debug + Clone> SnapToGrid<U> {
+    /// Wraps a feature such that it is snapped to a grid with the
+    /// given spacing.
+    pub fn new(inner: U, grid: f64) -> Self {
+        Self { inner, grid }
+    }
+
+    fn offset(&self) -> (f64, f64) {
+        use geo::bounding_rect::BoundingRect;
+        match self.inner.edge_union().and_then(|e| e.bounding_rect()) {
+            Some(b) => {
+                let c = b.center();
+                (
+                    crate::snap_coordinate(c.x, self.grid) - c.x,
+                    crate::snap_coordinate(c.y, self.grid) - c.y,
+                )
+            }
+            None => (0., 0.),
+        }
+    }
+}
+
+impl<U: super::Feature + fmt::Debug + Clone> fmt::Display for SnapToGrid<U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Specs have no notion of snapping, so the inner feature is
+        // written out at its snapped position.
+        let (x, y) = self.offset();
+        let mut inner = self.inner.clone();
+        inner.translate([x, y].into());
+        write!(f, "{}", inner)
+    }
+}
+
+impl<U: super::Feature + fmt::Debug + Clone> super::Feature for SnapToGrid<U> {
+    fn name(&self) -> &'static str {
+        "snap_to_grid"
+    }
+
+    fn edge_union(&self) -> Option<MultiPolygon<f64>> {
+        use geo::translate::Translate;
+        let (tx, ty) = self.offset();
+        self.inner.edge_union().map(|e| e.translate(tx, ty))
+    }
+
+    fn edge_subtract(&self) -> Option<MultiPolygon<f64>> {
+        use geo::translate::Translate;
+        let (tx, ty) = self.offset();
+        self.inner.edge_subtract().map(|e| e.translate(tx, ty))
+    }
+
+    fn translate(&mut self, v: Coordinate<f64>) {
+        self.inner.translate(v);
+    }
+
+    fn interior(&self) -> Vec<super::InnerAtom> {
+        let (tx, ty) = self.offset();
+        self.inner
+            .interior()
+            .into_iter()
+            .map(|mut a| {
+                a.translate(tx, ty);
+                a
+            })
+            .collect()
+    }
+
+    fn named_info(&self) -> Vec<super::NamedInfo> {
+        let (tx, ty) = self.offset();
+        self.inner
+            .named_info()
+            .into_iter()
+            .map(|mut info| {
+                info.translate(tx, ty);
+                info
+            })
+            .collect()
+    }
+
+    fn parts(&self) -> Vec<&'static str> {
+        self.inner.parts()
+    }
+
+    fn all_names(&self) -> Vec<&'static str> {
+        let mut out = vec![self.name()];
+        out.extend(self.inner.all_names());
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::{Feature, Rect};
+
+    #[test]
+    fn snaps_center() {
+        use geo::bounding_rect::BoundingRect;
+        let s = SnapToGrid::new(Rect::with_center([1.3, 2.7].into(), 2., 2.), 0.5);
+        let c = s.edge_union().unwrap().bounding_rect().unwrap().center();
+        assert!(c.x > 1.49 && c.x < 1.51);
+        assert!(c.y > 2.49 && c.y < 2.51);
+    }
+}
@@ -0,0 +1,174 @@
+use super::{InnerAtom, MouseBite};
+use crate::Direction;
+use geo::{Coordinate, MultiPolygon};
+use std::fmt;
+
+/// A feature representing a narrow bridge of board material, which
+/// connects a board to the rest of a panel and is perforated by mouse
+/// bites so it can be snapped off.
+#[derive(Debug, Clone)]
+pub struct Tab {
+    origin: Coordinate<f64>,
+    width: f64,
+    length: f64,
+    bite_diameter: f64,
+    direction: Direction,
+}
+
+impl Tab {
+    /// Creates a tab of the given width, which extends to the right of
+    /// the origin by the given length.
+    pub fn new(width: f64, length: f64) -> Self {
+        Self {
+            origin: [0., 0.].into(),
+            width,
+            length,
+            bite_diameter: 0.5,
+            direction: Direction::Right,
+        }
+    }
+
+    /// Returns a tab which extends in the given direction.
+    pub fn direction(self, direction: Direction) -> Self {
+        Self { direction, ..self }
+    }
+
+    /// Returns a tab perforated by holes of the given diameter.
+    pub fn bite_diameter(self, bite_diameter: f64) -> Self {
+        Self {
+            bite_diameter,
+            ..self
+        }
+    }
+
+    /// Returns unit vectors along and across the tab.
+    fn axes(&self) -> (Coordinate<f64>, Coordinate<f64>) {
+        match self.direction {
+            Direction::Left => ([-1., 0.].into(), [0., 1.].into()),
+            Direction::Right => ([1., 0.].into(), [0., 1.].into()),
+            Direction::Up => ([0., -1.].into(), [1., 0.].into()),
+            Direction::Down => ([0., 1.].into(), [1., 0.].into()),
+        }
+    }
+}
+
+impl fmt::Display for Tab {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        super::fmt_translated(
+            f,
+            self.origin,
+            format_args!(
+                "tab<{}, {}, dir = {}>",
+                self.width, self.length, self.direction
+            ),
+        )
+    }
+}
+
+impl super::Feature for Tab {
+    fn name(&self) -> &'static str {
+        "tab"
+    }
+
+    fn edge_union(&self) -> Option<MultiPolygon<f64>> {
+        let (along, across) = self.axes();
+        let a = self.origin
+            + Coordinate {
+                x: across.x * -self.width / 2.,
+                y: across.y * -self.width / 2.,
+            };
+        let b = self.origin
+            + Coordinate {
+                x: along.x * self.length + across.x * self.width / 2.,
+                y: along.y * self.length + across.y * self.width / 2.,
+            };
+        Some(geo::Rect::new(a, b).to_polygon().into())
+    }
+
+    fn translate(&mut self, v: Coordinate<f64>) {
+        self.origin = self.origin + v;
+    }
+
+    fn interior(&self) -> Vec<InnerAtom> {
+        let (along, across) = self.axes();
+        let center = self.origin
+            + Coordinate {
+                x: along.x * self.length / 2.,
+                y: along.y * self.length / 2.,
+            };
+        let start = center
+            - Coordinate {
+                x: across.x * self.width / 2.,
+                y: across.y * self.width / 2.,
+            };
+        let dir = match self.direction {
+            Direction::Left | Direction::Right => Direction::Down,
+            Direction::Up | Direction::Down => Direction::Right,
+        };
+        let mut holes = MouseBite::new(self.width, self.bite_diameter)
+            .at(start)
+            .direction(dir)
+            .interior();
+
+        // The width may not be a multiple of the spacing, so center the
+        // row across the tab.
+        if let (Some(first), Some(last)) = (
+            holes.first().and_then(|h| h.bounds()),
+            holes.last().and_then(|h| h.bounds()),
+        ) {
+            let (first, last) = (first.center(), last.center());
+            let (dx, dy) = (
+                center.x - (first.x + last.x) / 2.,
+                center.y - (first.y + last.y) / 2.,
+            );
+            for h in holes.iter_mut() {
+                h.translate(dx, dy);
+            }
+        }
+        holes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::Feature;
+    use geo::bounding_rect::BoundingRect;
+
+    #[test]
+    fn edge() {
+        let b = Tab::new(2., 5.)
+            .edge_union()
+            .unwrap()
+            .bounding_rect()
+            .unwrap();
+        assert_eq!(b.min(), Coordinate { x: 0., y: -1. });
+        assert_eq!(b.max(), Coordinate { x: 5., y: 1. });
+
+        let b = Tab::new(2., 5.)
+            .direction(Direction::Down)
+            .edge_union()
+            .unwrap()
+            .bounding_rect()
+            .unwrap();
+        assert_eq!(b.min(), Coordinate { x: -1., y: 0. });
+        assert_eq!(b.max(), Coordinate { x: 1., y: 5. });
+    }
+
+    #[test]
+    fn bites() {
+        let holes = Tab::new(2., 5.).interior();
+        assert_eq!(holes.len(), 3);
+        for h in &holes {
+            assert!(
+                matches!(h, InnerAtom::Drill { center, plated: false, .. } if (center.x - 2.5).abs() < 1e-9)
+            );
+        }
+        // The row is centered across the tab.
+        let ys: Vec<f64> = holes
+            .iter()
+            .map(|h| h.bounds().unwrap().center().y)
+            .collect();
+        assert!((ys.iter().sum::<f64>()).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,162 @@
+use geo::{Coordinate, LineString, MultiPolygon, Polygon};
+use std::fmt;
+
+/// A trapezoidal region with horizontal top and bottom edges, which is
+/// symmetric about its vertical center line.
+#[derive(Debug, Clone)]
+pub struct Trapezoid<U = super::Unit> {
+    top_width: f64,
+    bottom_width: f64,
+    height: f64,
+    center: Coordinate<f64>,
+    inner: U,
+}
+
+impl Trapezoid {
+    /// Constructs a new trapezoid centered on the origin, with the given
+    /// widths of its top and bottom edges.
+    pub fn new(top_width: f64, bottom_width: f64, height: f64) -> Self {
+        Self {
+            top_width,
+            bottom_width,
+            height,
+            center: [0., 0.].into(),
+            inner: super::Unit,
+        }
+    }
+}
+
+impl<U: super::InnerFeature + Clone> Trapezoid<U> {
+    /// Constructs a trapezoid surrounding the inner feature. The
+    /// origin of the inner feature will be positioned at the
+    /// center of the trapezoid.
+    pub fn with_inner(
+        mut inner: U,
+        center: Coordinate<f64>,
+        top_width: f64,
+        bottom_width: f64,
+        height: f64,
+    ) -> Self {
+        inner.translate(center);
+
+        Self {
+            top_width,
+            bottom_width,
+            height,
+            center,
+            inner,
+        }
+    }
+
+    fn points(&self) -> Vec<Coordinate<f64>> {
+        let (t, b, h) = (
+            self.top_width / 2.,
+            self.bottom_width / 2.,
+            self.height / 2.,
+        );
+        let mut points = vec![
+            Coordinate { x: -t, y: -h },
+            Coordinate { x: t, y: -h },
+            Coordinate { x: b, y: h },
+            Coordinate { x: -b, y: h },
+        ];
+        // A zero-width edge degenerates into a single point, making
+        // the shape a triangle.
+        points.dedup();
+        if points.len() > 1 && points[0] == points[points.len() - 1] {
+            points.pop();
+        }
+        points.into_iter().map(|p| p + self.center).collect()
+    }
+}
+
+impl<U: super::InnerFeature + Clone> fmt::Display for Trapezoid<U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        super::fmt_translated(
+            f,
+            self.center,
+            format_args!(
+                "Z<top = {}, bottom = {}, h = {}>{}",
+                self.top_width,
+                self.bottom_width,
+                self.height,
+                super::fmt_inner(&self.inner, self.center)
+            ),
+        )
+    }
+}
+
+impl<U: super::InnerFeature + Clone + std::fmt::Debug> super::Feature for Trapezoid<U> {
+    fn name(&self) -> &'static str {
+        "trapezoid"
+    }
+
+    fn edge_union(&self) -> Option<MultiPolygon<f64>> {
+        let mut points = self.points();
+        points.push(points[0]);
+        Some(Polygon::new(LineString(points), vec![]).into())
+    }
+
+    fn translate(&mut self, v: Coordinate<f64>) {
+        self.center = self.center + v;
+        self.inner.translate(v);
+    }
+
+    fn interior(&self) -> Vec<super::InnerAtom> {
+        self.inner.atoms()
+    }
+
+    fn parts(&self) -> Vec<&'static str> {
+        match self.inner.name() {
+            "unit" => vec![self.name()],
+            inner => vec![self.name(), inner],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::{Feature, Rect, Triangle};
+    use geo::algorithm::{area::Area, bounding_rect::BoundingRect};
+
+    fn same_vertices(a: &MultiPolygon<f64>, b: &MultiPolygon<f64>) -> bool {
+        let (a, b) = (a.0[0].exterior(), b.0[0].exterior());
+        a.0.len() == b.0.len()
+            && a.0.iter().all(|p| {
+                b.0.iter()
+                    .any(|q| (p.x - q.x).abs() < 1e-9 && (p.y - q.y).abs() < 1e-9)
+            })
+    }
+
+    #[test]
+    fn rectangle() {
+        let t = Trapezoid::new(4., 4., 3.).edge_union().unwrap();
+        let r = Rect::with_center([0., 0.].into(), 4., 3.)
+            .edge_union()
+            .unwrap();
+        assert!(same_vertices(&t, &r));
+        assert!((t.unsigned_area() - r.unsigned_area()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn triangle() {
+        let t = Trapezoid::new(0., 6., 4.).edge_union().unwrap();
+        let tri = Triangle::isoceles(6., 4.).edge_union().unwrap();
+        assert!(same_vertices(&t, &tri));
+        assert!((t.unsigned_area() - 12.).abs() < 1e-9);
+        assert_eq!(t.bounding_rect(), tri.bounding_rect());
+    }
+
+    #[test]
+    fn translated() {
+        let mut t = Trapezoid::new(3., 6., 4.);
+        t.translate([1., 2.].into());
+        let b = t.edge_union().unwrap().bounding_rect().unwrap();
+        assert!(b.width() > 5.99 && b.width() < 6.01);
+        assert!(b.height() > 3.99 && b.height() < 4.01);
+        assert!(b.center().x > 0.99 && b.center().x < 1.01);
+        assert!(b.center().y > 1.99 && b.center().y < 2.01);
+        assert!((t.edge_union().unwrap().unsigned_area() - 18.).abs() < 1e-9);
+    }
+}
@@ -41,6 +41,39 @@ impl Triangle {
             inner: super::Unit,
         }
     }
+
+    /// Constructs an isoceles triangle, centered at the origin and
+    /// with a horizontal base of the given width.
+    pub fn isoceles(base: f64, height: f64) -> Self {
+        Self {
+            right_angle: false,
+            triangle: isoceles_points(base, height),
+            inner: super::Unit,
+        }
+    }
+
+    /// Constructs an equilateral triangle centered at the origin, where
+    /// all sides have the given length.
+    pub fn equilateral(side: f64) -> Self {
+        Self::isoceles(side, side * 3f64.sqrt() / 2.)
+    }
+}
+
+fn isoceles_points(base: f64, height: f64) -> geo::Triangle<f64> {
+    geo::Triangle(
+        Coordinate {
+            x: -base / 2.,
+            y: height / 2.,
+        },
+        Coordinate {
+            x: 0.,
+            y: -height / 2.,
+        },
+        Coordinate {
+            x: base / 2.,
+            y: height / 2.,
+        },
+    )
 }
 
 impl<U: super::InnerFeature + Clone + std::fmt::Debug> Triangle<U> {
@@ -86,6 +119,17 @@ impl<U: super::InnerFeature + Clone + std::fmt::Debug> Triangle<U> {
         }
     }
 
+    /// Returns a new isoceles triangle around the provided center.
+    pub fn isoceles_dimensions(self, center: Coordinate<f64>, base: f64, height: f64) -> Self {
+        let t = isoceles_points(base, height);
+        self.bounds(center + t.0, center + t.1, center + t.2)
+    }
+
+    /// Returns a new equilateral triangle around the provided center.
+    pub fn equilateral_dimensions(self, center: Coordinate<f64>, side: f64) -> Self {
+        self.isoceles_dimensions(center, side, side * 3f64.sqrt() / 2.)
+    }
+
     /// Returns a new triangle using the provided points. The inner feature will
     /// be translated to the centeroid of the triangle.
     pub fn bounds(mut self, c1: Coordinate<f64>, c2: Coordinate<f64>, c3: Coordinate<f64>) -> Self {
@@ -101,13 +145,47 @@ impl<U: super::InnerFeature + Clone + std::fmt::Debug> Triangle<U> {
     }
 }
 
-impl<U: super::InnerFeature> fmt::Display for Triangle<U> {
+impl<U: super::InnerFeature + Clone> fmt::Display for Triangle<U> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
+        let geo::Triangle(c1, c2, c3) = self.triangle;
+        if self.right_angle {
+            let (w, h) = (c3.x - c1.x, c2.y - c1.y);
+            let center = c1
+                + Coordinate {
+                    x: w / 2.,
+                    y: h / 2.,
+                };
+            return super::fmt_translated(
+                f,
+                center,
+                format_args!("T<{}, {}>{}", w, h, super::fmt_inner(&self.inner, center)),
+            );
+        }
+
+        let isoceles = (c1.y - c3.y).abs() < 1e-9 && (c2.x - (c1.x + c3.x) / 2.).abs() < 1e-9;
+        if !isoceles {
+            return write!(
+                f,
+                "/* triangle ({}, {}), ({}, {}), ({}, {}) */",
+                c1.x, c1.y, c2.x, c2.y, c3.x, c3.y
+            );
+        }
+
+        use geo::algorithm::centroid::Centroid;
+        let (b, h) = (c3.x - c1.x, c1.y - c2.y);
+        let centroid = self.triangle.to_polygon().centroid().unwrap().into();
+        super::fmt_translated(
             f,
-            "triangle({:?}, U = {})",
-            self.triangle.to_array(),
-            self.inner
+            Coordinate {
+                x: c2.x,
+                y: c2.y + h / 2.,
+            },
+            format_args!(
+                "T<{}, {}, iso>{}",
+                b,
+                h,
+                super::fmt_inner(&self.inner, centroid)
+            ),
         )
     }
 }
@@ -130,4 +208,52 @@ impl<U: super::InnerFeature + Clone + std::fmt::Debug> super::Feature for Triang
     fn interior(&self) -> Vec<super::InnerAtom> {
         self.inner.atoms()
     }
+
+    fn parts(&self) -> Vec<&'static str> {
+        match self.inner.name() {
+            "unit" => vec![self.name()],
+            inner => vec![self.name(), inner],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geo::algorithm::euclidean_distance::EuclideanDistance;
+    use geo::Point;
+
+    fn sides(t: &geo::Triangle<f64>) -> [f64; 3] {
+        let (a, b, c) = (Point::from(t.0), Point::from(t.1), Point::from(t.2));
+        [
+            a.euclidean_distance(&b),
+            b.euclidean_distance(&c),
+            c.euclidean_distance(&a),
+        ]
+    }
+
+    #[test]
+    fn equilateral() {
+        let t = Triangle::equilateral(4.0);
+        assert!(!t.right_angle);
+        for side in sides(&t.triangle).iter() {
+            assert!(*side > 3.999 && *side < 4.001);
+        }
+    }
+
+    #[test]
+    fn isoceles() {
+        use geo::bounding_rect::BoundingRect;
+        let t = Triangle::isoceles(4.0, 3.0);
+        assert!(!t.right_angle);
+
+        let b = t.triangle.to_polygon().bounding_rect().unwrap();
+        assert!(b.width() > 3.999 && b.width() < 4.001);
+        assert!(b.height() > 2.999 && b.height() < 3.001);
+        assert!(b.center().x.abs() < 0.001 && b.center().y.abs() < 0.001);
+
+        let s = sides(&t.triangle);
+        assert!((s[0] - s[1]).abs() < 0.001);
+        assert!((s[2] - 4.0).abs() < 0.001);
+    }
 }
@@ -7,7 +7,7 @@ pub struct Unit;
 
 impl fmt::Display for Unit {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "()")
+        f.write_str("")
     }
 }
 
@@ -25,6 +25,14 @@ impl super::Feature for Unit {
     fn interior(&self) -> Vec<super::InnerAtom> {
         vec![]
     }
+
+    fn parts(&self) -> Vec<&'static str> {
+        vec![]
+    }
+
+    fn all_names(&self) -> Vec<&'static str> {
+        vec![]
+    }
 }
 
 impl super::InnerFeature for Unit {
@@ -0,0 +1,122 @@
+use super::InnerAtom;
+use crate::Layer;
+use geo::Coordinate;
+use std::fmt;
+
+/// An interior feature representing a plated via, such as for stitching
+/// or thermal relief, optionally surrounded by an annular copper ring.
+#[derive(Debug, Clone)]
+pub struct ViaHole {
+    center: Coordinate<f64>,
+    drill_radius: f64,
+    annular_ring_radius: f64,
+}
+
+impl ViaHole {
+    /// Creates a via with the specified drill diameter, at the given
+    /// position. The via has no copper around it.
+    pub fn new(center: Coordinate<f64>, diameter: f64) -> Self {
+        Self {
+            center,
+            drill_radius: diameter / 2.,
+            annular_ring_radius: 0.,
+        }
+    }
+
+    /// Returns a via surrounded by a ring of exposed copper of the given
+    /// width, on both sides. A width of zero removes the ring.
+    pub fn with_ring(self, ring_width: f64) -> Self {
+        Self {
+            annular_ring_radius: if ring_width > 0. {
+                self.drill_radius + ring_width
+            } else {
+                0.
+            },
+            ..self
+        }
+    }
+}
+
+impl fmt::Display for ViaHole {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "via<@({}, {}), {}",
+            self.center.x,
+            self.center.y,
+            self.drill_radius * 2.
+        )?;
+        if self.annular_ring_radius > 0. {
+            write!(
+                f,
+                ", ring = {}",
+                self.annular_ring_radius - self.drill_radius
+            )?;
+        }
+        write!(f, ">")
+    }
+}
+
+impl super::InnerFeature for ViaHole {
+    fn name(&self) -> &'static str {
+        "via"
+    }
+
+    fn translate(&mut self, v: Coordinate<f64>) {
+        self.center = self.center + v;
+    }
+
+    fn atoms(&self) -> Vec<InnerAtom> {
+        let mut out: Vec<_> = if self.annular_ring_radius > 0. {
+            vec![
+                Layer::BackCopper,
+                Layer::BackMask,
+                Layer::FrontCopper,
+                Layer::FrontMask,
+            ]
+            .into_iter()
+            .map(|layer| InnerAtom::Circle {
+                center: self.center,
+                radius: self.annular_ring_radius,
+                layer,
+            })
+            .collect()
+        } else {
+            vec![]
+        };
+
+        out.push(InnerAtom::Drill {
+            center: self.center,
+            radius: self.drill_radius,
+            plated: true,
+        });
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::InnerFeature;
+
+    #[test]
+    fn drill_only() {
+        let atoms = ViaHole::new([1., 1.].into(), 0.3).atoms();
+        assert_eq!(atoms.len(), 1);
+        assert!(matches!(atoms[0], InnerAtom::Drill { plated: true, .. }));
+    }
+
+    #[test]
+    fn ring() {
+        let atoms = ViaHole::new([1., 1.].into(), 0.3).with_ring(0.15).atoms();
+        assert_eq!(atoms.len(), 5);
+        assert!(matches!(atoms[4], InnerAtom::Drill { plated: true, .. }));
+        assert_eq!(
+            atoms
+                .iter()
+                .filter(|a| matches!(a, InnerAtom::Circle { radius, .. } if *radius > 0.29 && *radius < 0.31))
+                .count(),
+            4
+        );
+    }
+}
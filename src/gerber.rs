@@ -9,6 +9,53 @@ use std::collections::HashMap;
 
 const VERSION: &'static str = env!("CARGO_PKG_VERSION");
 
+/// Identifying information emitted at the head of generated gerber files.
+#[derive(Debug, Clone)]
+pub struct GerberMetadata {
+    pub tool_name: String,
+    pub tool_version: String,
+    #[cfg(feature = "timestamps")]
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl Default for GerberMetadata {
+    fn default() -> Self {
+        Self {
+            tool_name: "maker-panel".to_string(),
+            tool_version: VERSION.to_string(),
+            #[cfg(feature = "timestamps")]
+            created_at: Some(chrono::Utc::now()),
+        }
+    }
+}
+
+impl GerberMetadata {
+    fn comments(&self, layer: &str) -> Vec<Command> {
+        #[cfg_attr(not(feature = "timestamps"), allow(unused_mut))]
+        let mut out: Vec<Command> = vec![
+            FunctionCode::GCode(GCode::Comment(format!(
+                "Generated by {} v{}",
+                self.tool_name, self.tool_version
+            )))
+            .into(),
+            FunctionCode::GCode(GCode::Comment(format!("Layer: {}", layer))).into(),
+        ];
+
+        #[cfg(feature = "timestamps")]
+        if let Some(created_at) = self.created_at {
+            out.push(
+                FunctionCode::GCode(GCode::Comment(format!(
+                    "Created: {}",
+                    created_at.to_rfc3339()
+                )))
+                .into(),
+            );
+        }
+
+        out
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 enum ApertureType {
     Circle(f64),
@@ -16,28 +63,33 @@ enum ApertureType {
 }
 
 fn gerber_prelude<'a>(
+    meta: &GerberMetadata,
+    layer: &str,
     cf: CoordinateFormat,
     ff: Option<FileFunction>,
     apertures: impl Iterator<Item = &'a (i32, ApertureType)>,
 ) -> Vec<Command> {
-    let mut commands =
-        vec![
-            FunctionCode::GCode(GCode::Comment("Autogenerated Maker Panel".to_string())).into(),
-            ExtendedCode::CoordinateFormat(cf).into(),
-            ExtendedCode::Unit(Unit::Millimeters).into(),
-            ExtendedCode::FileAttribute(FileAttribute::GenerationSoftware(
-                GenerationSoftware::new("Maker Panel", "maker-panel", Some(VERSION)),
-            ))
-            .into(),
-            ExtendedCode::FileAttribute(FileAttribute::Part(Part::Single)).into(),
-            if let Some(ff) = ff {
-                ExtendedCode::FileAttribute(FileAttribute::FileFunction(ff)).into()
-            } else {
-                FunctionCode::GCode(GCode::Comment("".to_string())).into()
-            },
-            ExtendedCode::LoadPolarity(Polarity::Dark).into(),
-            FunctionCode::GCode(GCode::InterpolationMode(InterpolationMode::Linear)).into(),
-        ];
+    let mut commands = meta.comments(layer);
+    commands.extend(vec![
+        ExtendedCode::CoordinateFormat(cf).into(),
+        ExtendedCode::Unit(Unit::Millimeters).into(),
+        ExtendedCode::FileAttribute(FileAttribute::GenerationSoftware(GenerationSoftware::new(
+            "Maker Panel",
+            "maker-panel",
+            Some(VERSION),
+        )))
+        .into(),
+        ExtendedCode::FileAttribute(FileAttribute::Part(Part::Single)).into(),
+    ]);
+    // Layers without a supported X2 file function, such as courtyards, are
+    // identified only by the header comment.
+    if let Some(ff) = ff {
+        commands.push(ExtendedCode::FileAttribute(FileAttribute::FileFunction(ff)).into());
+    }
+    commands.extend(vec![
+        ExtendedCode::LoadPolarity(Polarity::Dark).into(),
+        FunctionCode::GCode(GCode::InterpolationMode(InterpolationMode::Linear)).into(),
+    ]);
 
     for (code, shape) in apertures {
         commands.push(
@@ -116,9 +168,11 @@ fn emit_poly<I: Iterator<Item = geo::Point<f64>>>(commands: &mut Vec<Command>, p
 }
 
 /// Serializes a representation of edge geometry in extender gerber format.
-pub fn serialize_edge(poly: Polygon<f64>) -> Result<Vec<Command>, ()> {
+pub fn serialize_edge(poly: Polygon<f64>, meta: &GerberMetadata) -> Result<Vec<Command>, ()> {
     let cf = CoordinateFormat::new(4, 6);
     let mut commands = gerber_prelude(
+        meta,
+        "Edge",
         cf,
         Some(FileFunction::Profile(Profile::NonPlated)),
         [(10, ApertureType::Circle(0.01))].iter(),
@@ -165,6 +219,7 @@ pub fn serialize_layer(
     out_layer: super::Layer,
     features: Vec<InnerAtom>,
     bounds: geo::Rect<f64>,
+    meta: &GerberMetadata,
 ) -> Result<Vec<Command>, ()> {
     let cf = CoordinateFormat::new(4, 6);
 
@@ -184,7 +239,12 @@ pub fn serialize_layer(
                     rects.insert((FloatBits(rect.width()), FloatBits(rect.height())), ());
                 }
             }
-            InnerAtom::Drill { .. } => (), // Drill hits are not on gerbers
+            InnerAtom::Line { width, layer, .. } => {
+                if out_layer == *layer {
+                    dias.insert(FloatBits(*width), ());
+                }
+            }
+            InnerAtom::Drill { .. } | InnerAtom::Slot { .. } => (), // Drill hits are not on gerbers
             InnerAtom::VScoreH(_) | InnerAtom::VScoreV(_) => {
                 if out_layer == super::Layer::FabricationInstructions {
                     dias.insert(FloatBits(0.18), ());
@@ -207,6 +267,8 @@ pub fn serialize_layer(
         .collect();
 
     let mut commands = gerber_prelude(
+        meta,
+        &out_layer.to_string(),
         cf,
         match out_layer {
             super::Layer::FrontCopper => Some(FileFunction::Copper {
@@ -235,7 +297,12 @@ pub fn serialize_layer(
                 pos: Position::Bottom,
                 index: None,
             }),
-            super::Layer::FabricationInstructions => None,
+            // gerber-types cannot serialize the paste file function.
+            super::Layer::PasteFront
+            | super::Layer::PasteBack
+            | super::Layer::CourtyardFront
+            | super::Layer::CourtyardBack
+            | super::Layer::FabricationInstructions => None,
         },
         apertures.iter(),
     );
@@ -284,7 +351,45 @@ pub fn serialize_layer(
                     ));
                 }
             }
-            InnerAtom::Drill { .. } => (), // Drill hits are not on gerbers
+            InnerAtom::Line {
+                start,
+                end,
+                width,
+                layer,
+            } => {
+                if out_layer == *layer {
+                    let code = apertures
+                        .iter()
+                        .find(|&(_, f)| matches!(f, ApertureType::Circle(f)  if *f == *width))
+                        .unwrap()
+                        .0;
+                    if last_aperture != Some(code) {
+                        commands.push(FunctionCode::DCode(DCode::SelectAperture(code)).into());
+                        last_aperture = Some(code);
+                    }
+
+                    commands.push(
+                        FunctionCode::DCode(DCode::Operation(Operation::Move(Coordinates::new(
+                            CoordinateNumber::try_from(start.x).unwrap(),
+                            CoordinateNumber::try_from(start.y).unwrap(),
+                            cf,
+                        ))))
+                        .into(),
+                    );
+                    commands.push(
+                        FunctionCode::DCode(DCode::Operation(Operation::Interpolate(
+                            Coordinates::new(
+                                CoordinateNumber::try_from(end.x).unwrap(),
+                                CoordinateNumber::try_from(end.y).unwrap(),
+                                cf,
+                            ),
+                            None,
+                        )))
+                        .into(),
+                    );
+                }
+            }
+            InnerAtom::Drill { .. } | InnerAtom::Slot { .. } => (), // Drill hits are not on gerbers
 
             InnerAtom::VScoreH(y) => {
                 if out_layer == super::Layer::FabricationInstructions {
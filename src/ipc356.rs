@@ -0,0 +1,181 @@
+//! Generates IPC-D-356A netlists, describing test points for bare-board
+//! electrical testing.
+
+use super::{InnerAtom, Layer};
+
+/// Converts millimeters to thousandths of an inch.
+fn thou(mm: f64) -> f64 {
+    mm * 1000. / 25.4
+}
+
+fn signed(v: f64) -> String {
+    format!(
+        "{}{:06}",
+        if v < 0. { '-' } else { '+' },
+        thou(v).abs().round() as u64
+    )
+}
+
+struct Record {
+    through_hole: bool,
+    designator: &'static str,
+    pin: usize,
+    drill: Option<(f64, bool)>,
+    access: u8,
+    center: geo::Coordinate<f64>,
+    size: (f64, f64),
+}
+
+impl Record {
+    fn format(&self) -> String {
+        format!(
+            "{}{:<14}   {:<6}-{:<4} {}A{:02}X{}Y{}X{:04}Y{:04}R000  S0\n",
+            if self.through_hole { "317" } else { "327" },
+            "N/C",
+            self.designator,
+            self.pin,
+            match self.drill {
+                Some((dia, plated)) => format!(
+                    "D{:04}{}",
+                    thou(dia).round() as u64,
+                    if plated { 'P' } else { 'U' }
+                ),
+                None => "      ".to_string(),
+            },
+            self.access,
+            signed(self.center.x),
+            signed(self.center.y),
+            thou(self.size.0).round() as u64,
+            thou(self.size.1).round() as u64,
+        )
+    }
+}
+
+pub fn serialize<W: std::io::Write>(atoms: &[InnerAtom], w: &mut W) -> Result<(), std::io::Error> {
+    w.write_all(b"C  IPC-D-356A netlist generated by maker-panel\n")?;
+    w.write_all(b"C  Coordinates are in thousandths of an inch\n")?;
+    w.write_all(b"P  JOB   maker-panel\n")?;
+    w.write_all(b"P  DIM   N\n")?;
+
+    let drills: Vec<_> = atoms
+        .iter()
+        .filter_map(|a| match a {
+            InnerAtom::Drill {
+                center,
+                radius,
+                plated,
+            } => Some((*center, *radius, *plated)),
+            _ => None,
+        })
+        .collect();
+    // Copper around a drill hit is the annular ring of that hole,
+    // so is tested as part of the through-hole record.
+    let on_drill = |c: geo::Coordinate<f64>| {
+        drills
+            .iter()
+            .any(|(d, _, _)| (d.x - c.x).abs() < 1e-6 && (d.y - c.y).abs() < 1e-6)
+    };
+
+    let mut records: Vec<Record> = drills
+        .iter()
+        .enumerate()
+        .map(|(i, (center, radius, plated))| Record {
+            through_hole: true,
+            designator: "DRILL",
+            pin: i + 1,
+            drill: Some((radius * 2., *plated)),
+            access: 0,
+            center: *center,
+            size: (radius * 2., radius * 2.),
+        })
+        .collect();
+
+    let pads = atoms
+        .iter()
+        .filter_map(|a| {
+            // Traces connect test points, rather than being one.
+            if let InnerAtom::Line { .. } = a {
+                return None;
+            }
+            let access = match a.layer() {
+                Some(Layer::FrontCopper) => 1,
+                Some(Layer::BackCopper) => 2,
+                _ => return None,
+            };
+            let bounds = a.bounds()?;
+            if on_drill(bounds.center()) {
+                return None;
+            }
+            Some((access, bounds))
+        })
+        .enumerate()
+        .map(|(i, (access, bounds))| Record {
+            through_hole: false,
+            designator: "PAD",
+            pin: i + 1,
+            drill: None,
+            access,
+            center: bounds.center(),
+            size: (bounds.width(), bounds.height()),
+        });
+    records.extend(pads);
+
+    for r in records {
+        w.write_all(r.format().as_bytes())?;
+    }
+    w.write_all(b"999\n")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_columns() {
+        let r = Record {
+            through_hole: true,
+            designator: "DRILL",
+            pin: 1,
+            drill: Some((1., true)),
+            access: 0,
+            center: [25.4, -2.54].into(),
+            size: (1., 1.),
+        }
+        .format();
+        assert_eq!(&r[0..3], "317");
+        assert_eq!(&r[20..31], "DRILL -1   ");
+        assert_eq!(&r[32..38], "D0039P");
+        assert_eq!(&r[38..41], "A00");
+        assert_eq!(&r[41..57], "X+001000Y-000100");
+        assert_eq!(&r[57..71], "X0039Y0039R000");
+    }
+
+    #[test]
+    fn annular_rings_skipped() {
+        let atoms = vec![
+            InnerAtom::Circle {
+                center: [1., 1.].into(),
+                radius: 1.,
+                layer: Layer::FrontCopper,
+            },
+            InnerAtom::Drill {
+                center: [1., 1.].into(),
+                radius: 0.5,
+                plated: true,
+            },
+            InnerAtom::Rect {
+                rect: geo::Rect::new([4., 4.], [5., 5.]),
+                layer: Layer::BackCopper,
+            },
+        ];
+        let mut out = Vec::new();
+        serialize(&atoms, &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let records: Vec<_> = out.lines().filter(|l| l.starts_with("3")).collect();
+        assert_eq!(records.len(), 2);
+        assert!(records[0].starts_with("317"));
+        assert!(records[1].starts_with("327"));
+        assert_eq!(&records[1][38..41], "A02");
+    }
+}
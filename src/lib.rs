@@ -9,19 +9,31 @@ use usvg::NodeExt;
 pub mod features;
 use features::{Feature, InnerAtom};
 
+mod bom;
+mod cost;
 mod drill;
+mod dxf;
 mod gerber;
+mod ipc356;
 mod parser;
+mod pnp;
+mod rules;
+mod slice;
 #[cfg(feature = "tessellate")]
 mod tessellate;
+mod wkt;
 #[cfg(feature = "tessellate")]
-pub use tessellate::normals_from_tessellation;
+pub use tessellate::{normals_from_tessellation, smooth_normals_from_tessellation};
 #[cfg(feature = "tessellate")]
-pub use tessellate::{Point as TPoint, TessellationError, VertexBuffers};
+pub use tessellate::{Point as TPoint, PointWithUV, TessellationError, VertexBuffers};
 #[cfg(feature = "text")]
 mod text;
 
+pub use cost::{CostEstimate, ManufacturingTariff};
+pub use drill::{DrillUnits, SlotFormat};
 pub use parser::Err as SpecErr;
+pub use parser::{ast, InnerAST, Span, TriangleKind, Value, WrapPosition, AST};
+pub use rules::{DesignRules, Violation};
 
 /// Alignment of multiple elements in an array.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -32,7 +44,7 @@ pub enum Align {
 }
 
 /// PCB layers.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Layer {
     FrontCopper,
     FrontMask,
@@ -40,19 +52,28 @@ pub enum Layer {
     BackCopper,
     BackMask,
     BackLegend,
+    PasteFront,
+    PasteBack,
+    CourtyardFront,
+    CourtyardBack,
     FabricationInstructions,
 }
 
 impl Layer {
-    fn color(&self) -> usvg::Color {
+    /// Returns the color the layer is rendered in.
+    pub fn rgb(&self) -> (u8, u8, u8) {
         match self {
-            Layer::FrontCopper => usvg::Color::new(0x84, 0, 0),
-            Layer::FrontMask => usvg::Color::new(0x84, 0, 0x84),
-            Layer::FrontLegend => usvg::Color::new(0, 0xce, 0xde),
-            Layer::BackCopper => usvg::Color::new(0, 0x84, 0),
-            Layer::BackMask => usvg::Color::new(0x84, 0, 0x84),
-            Layer::BackLegend => usvg::Color::new(0x4, 0, 0x84),
-            Layer::FabricationInstructions => usvg::Color::new(0x66, 0x66, 0x66),
+            Layer::FrontCopper => (0x84, 0, 0),
+            Layer::FrontMask => (0x84, 0, 0x84),
+            Layer::FrontLegend => (0, 0xce, 0xde),
+            Layer::BackCopper => (0, 0x84, 0),
+            Layer::BackMask => (0x84, 0, 0x84),
+            Layer::BackLegend => (0x4, 0, 0x84),
+            Layer::PasteFront => (0x84, 0x84, 0x84),
+            Layer::PasteBack => (0x84, 0x84, 0x84),
+            Layer::CourtyardFront => (0xff, 0x26, 0xe2),
+            Layer::CourtyardBack => (0x26, 0xe9, 0xff),
+            Layer::FabricationInstructions => (0x66, 0x66, 0x66),
         }
     }
 
@@ -64,9 +85,100 @@ impl Layer {
             Layer::BackCopper => String::from("BackCopper"),
             Layer::BackMask => String::from("BackMask"),
             Layer::BackLegend => String::from("BackLegend"),
+            Layer::PasteFront => String::from("PasteFront"),
+            Layer::PasteBack => String::from("PasteBack"),
+            Layer::CourtyardFront => String::from("CourtyardFront"),
+            Layer::CourtyardBack => String::from("CourtyardBack"),
             Layer::FabricationInstructions => String::from("FabricationInstructions"),
         }
     }
+
+    /// Returns the name of the file a gerber of the layer is written to.
+    pub fn file_suffix(&self) -> &'static str {
+        match self {
+            Layer::FrontCopper => "F.Cu.gtl",
+            Layer::FrontMask => "F.Mask.gts",
+            Layer::FrontLegend => "F.SilkS.gto",
+            Layer::BackCopper => "B.Cu.gbl",
+            Layer::BackMask => "B.Mask.gbs",
+            Layer::BackLegend => "B.SilkS.gto",
+            Layer::PasteFront => "F.Paste.gtp",
+            Layer::PasteBack => "B.Paste.gbp",
+            Layer::CourtyardFront => "F.CrtYd.fcrtyd",
+            Layer::CourtyardBack => "B.CrtYd.bcrtyd",
+            Layer::FabricationInstructions => "Cmts.User",
+        }
+    }
+
+    /// Returns the side of the board the layer is on, if any.
+    pub fn side(&self) -> Option<Side> {
+        match self {
+            Layer::FrontCopper
+            | Layer::FrontMask
+            | Layer::FrontLegend
+            | Layer::PasteFront
+            | Layer::CourtyardFront => Some(Side::Front),
+            Layer::BackCopper
+            | Layer::BackMask
+            | Layer::BackLegend
+            | Layer::PasteBack
+            | Layer::CourtyardBack => Some(Side::Back),
+            Layer::FabricationInstructions => None,
+        }
+    }
+}
+
+impl std::str::FromStr for Layer {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "frontcopper" => Ok(Layer::FrontCopper),
+            "frontmask" => Ok(Layer::FrontMask),
+            "frontlegend" => Ok(Layer::FrontLegend),
+            "backcopper" => Ok(Layer::BackCopper),
+            "backmask" => Ok(Layer::BackMask),
+            "backlegend" => Ok(Layer::BackLegend),
+            "pastefront" => Ok(Layer::PasteFront),
+            "pasteback" => Ok(Layer::PasteBack),
+            "courtyardfront" => Ok(Layer::CourtyardFront),
+            "courtyardback" => Ok(Layer::CourtyardBack),
+            "fabricationinstructions" => Ok(Layer::FabricationInstructions),
+            _ => Err(format!("no such layer: {}", s)),
+        }
+    }
+}
+
+/// A side of the board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Front,
+    Back,
+}
+
+impl std::fmt::Display for Side {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Side::Front => write!(f, "front"),
+            Side::Back => write!(f, "back"),
+        }
+    }
+}
+
+/// Describes a single drill hit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DrillInfo {
+    pub center: Coordinate<f64>,
+    pub diameter: f64,
+    pub plated: bool,
+}
+
+/// Describes the number of drill hits of a single size.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DrillSize {
+    pub diameter_mm: f64,
+    pub count: usize,
+    pub plated: bool,
 }
 
 /// The direction in which repetitions occur.
@@ -100,22 +212,96 @@ impl Direction {
     }
 }
 
+/// Name of the file the board outline gerber is written to.
+pub const EDGE_FILE_SUFFIX: &str = "Edge.Cuts.gm1";
+
+/// Returns the name of the file plated or non-plated drill hits are
+/// written to.
+pub fn drill_file_suffix(plated: bool) -> &'static str {
+    if plated {
+        "PTH.drl"
+    } else {
+        "NPTH.drl"
+    }
+}
+
+/// Gerber layers always included in a fabrication archive. Fabrication
+/// instructions are only included when the panel has any.
+const ZIP_GERBER_LAYERS: &[Layer] = &[
+    Layer::FrontCopper,
+    Layer::FrontMask,
+    Layer::FrontLegend,
+    Layer::BackCopper,
+    Layer::BackMask,
+    Layer::BackLegend,
+    Layer::PasteFront,
+    Layer::PasteBack,
+    Layer::CourtyardFront,
+    Layer::CourtyardBack,
+];
+
 /// Failure modes when constructing or serializing geometry.
 #[derive(Debug)]
 pub enum Err {
     NoFeatures,
     NoBounds,
     BadEdgeGeometry(String),
+    BadWKT(String),
+    BadSVGPath(String),
     InternalGerberFailure,
+    IO(std::io::Error),
+    Zip(zip::result::ZipError),
     #[cfg(feature = "tessellate")]
     TessellationError(TessellationError),
+    #[cfg(feature = "render")]
+    RenderFailure,
+    #[cfg(feature = "compress")]
+    CompressionFailure,
+}
+
+impl std::fmt::Display for Err {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Err::NoFeatures => write!(f, "no features were provided"),
+            Err::NoBounds => write!(f, "geometry has no bounds"),
+            Err::BadEdgeGeometry(msg) => write!(f, "bad edge geometry: {}", msg),
+            Err::BadWKT(msg) => write!(f, "invalid WKT: {}", msg),
+            Err::BadSVGPath(msg) => write!(f, "invalid SVG path: {}", msg),
+            Err::InternalGerberFailure => write!(f, "internal failure generating gerber"),
+            Err::IO(e) => write!(f, "i/o error: {}", e),
+            Err::Zip(e) => write!(f, "failed to build archive: {}", e),
+            #[cfg(feature = "tessellate")]
+            Err::TessellationError(e) => write!(f, "tessellation failed: {:?}", e),
+            #[cfg(feature = "render")]
+            Err::RenderFailure => write!(f, "failed to render image"),
+            #[cfg(feature = "compress")]
+            Err::CompressionFailure => write!(f, "failed to compress output"),
+        }
+    }
+}
+
+impl std::error::Error for Err {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        None
+    }
+}
+
+/// Rounds the value to the nearest multiple of grid.
+pub fn snap_coordinate(v: f64, grid: f64) -> f64 {
+    (v / grid).round() * grid
 }
 
+/// The thickness of the board in millimeters, unless otherwise set.
+const DEFAULT_BOARD_THICKNESS: f64 = 1.6;
+
 /// Combines features into single geometry.
 pub struct Panel<'a> {
     pub features: Vec<Box<dyn Feature + 'a>>,
+    custom_atoms: Vec<InnerAtom>,
     convex_hull: bool,
     grid_separation: Option<isize>,
+    default_layer: Option<Layer>,
+    board_thickness: f64,
 }
 
 impl<'a> Panel<'a> {
@@ -126,8 +312,11 @@ impl<'a> Panel<'a> {
         let grid_separation = None;
         Self {
             features,
+            custom_atoms: Vec::new(),
             convex_hull,
             grid_separation,
+            default_layer: None,
+            board_thickness: DEFAULT_BOARD_THICKNESS,
         }
     }
 
@@ -139,8 +328,11 @@ impl<'a> Panel<'a> {
         let grid_separation = None;
         Self {
             features,
+            custom_atoms: Vec::new(),
             convex_hull,
             grid_separation,
+            default_layer: None,
+            board_thickness: DEFAULT_BOARD_THICKNESS,
         }
     }
 
@@ -149,6 +341,17 @@ impl<'a> Panel<'a> {
         self.convex_hull = convex_hull;
     }
 
+    /// Sets the thickness of the board in millimeters, which is used
+    /// when extruding the panel in 3d.
+    pub fn set_board_thickness(&mut self, mm: f64) {
+        self.board_thickness = mm;
+    }
+
+    /// Returns the thickness of the board in millimeters.
+    pub fn board_thickness(&self) -> f64 {
+        self.board_thickness
+    }
+
     /// Sets the grid separation that should be rendered on the SVG.
     pub fn set_grid_separation(&mut self, grid_separation: Option<isize>) {
         self.grid_separation = grid_separation;
@@ -159,12 +362,252 @@ impl<'a> Panel<'a> {
         self.features.push(Box::new(f));
     }
 
+    /// Sets the layer that single-sided features added with
+    /// [`Panel::push_spec`] are placed on, when the spec does not
+    /// specify one.
+    pub fn set_default_layer(&mut self, layer: Layer) {
+        self.default_layer = Some(layer);
+    }
+
+    /// Returns the layer single-sided features are placed on by default.
+    pub fn default_layer(&self) -> Layer {
+        self.default_layer.clone().unwrap_or(Layer::FrontCopper)
+    }
+
+    /// Adds geometry to the interior of the panel which is not
+    /// associated with any feature.
+    pub fn add_interior_atom(&mut self, atom: InnerAtom) {
+        self.custom_atoms.push(atom);
+    }
+
+    /// Adds fiducial marks near three corners of the bounding box of the
+    /// panel, such that the panel orientation is unambiguous. Does nothing
+    /// if the panel has no edge geometry.
+    pub fn auto_fiducials(&mut self) {
+        use features::InnerFeature;
+        let bounds = match self.bounding_box() {
+            Some(b) => b,
+            None => return,
+        };
+        // Keep the mask opening within the bounds of the panel.
+        let inset = features::Fiducial::default().mask_opening_radius();
+        let (min, max) = (bounds.min(), bounds.max());
+
+        for &(x, y) in &[
+            (min.x + inset, min.y + inset),
+            (max.x - inset, min.y + inset),
+            (min.x + inset, max.y - inset),
+        ] {
+            for atom in features::Fiducial::new([x, y].into()).atoms() {
+                self.add_interior_atom(atom);
+            }
+        }
+    }
+
+    /// Adds a horizontal v-score across the panel at the given y coordinate.
+    pub fn add_h_v_score(&mut self, y: f64) {
+        self.add_interior_atom(InnerAtom::VScoreH(y));
+    }
+
+    /// Adds a vertical v-score across the panel at the given x coordinate.
+    pub fn add_v_v_score(&mut self, x: f64) {
+        self.add_interior_atom(InnerAtom::VScoreV(x));
+    }
+
+    /// Adds horizontal v-scores at each of the given y coordinates, and
+    /// vertical v-scores at each of the given x coordinates.
+    pub fn with_v_scores_at(&mut self, y_positions: &[f64], x_positions: &[f64]) -> &mut Self {
+        for y in y_positions {
+            self.add_h_v_score(*y);
+        }
+        for x in x_positions {
+            self.add_v_v_score(*x);
+        }
+        self
+    }
+
+    /// Adds v-scores along the boundaries between the copies of
+    /// each tiled feature in the panel.
+    pub fn generate_v_scores_from_tile_boundaries(&mut self) {
+        let scores: Vec<_> = self
+            .features
+            .iter()
+            .map(|f| f.tile_boundaries())
+            .flatten()
+            .collect();
+        for atom in scores {
+            self.add_interior_atom(atom);
+        }
+    }
+
+    /// Rotates the entire panel by the given number of degrees, by
+    /// wrapping all existing features in a [`features::Rotate`].
+    pub fn rotate_all(&mut self, degrees: f64) {
+        self.rotate_custom_atoms(degrees);
+        let features = std::mem::take(&mut self.features);
+        self.features
+            .push(Box::new(features::Rotate::new(degrees, features)));
+    }
+
+    /// Rotates the custom interior atoms by the given number of degrees,
+    /// about the centroid of the edge geometry.
+    fn rotate_custom_atoms(&mut self, degrees: f64) {
+        use geo::algorithm::centroid::Centroid;
+        let pivot = match self.edge_geometry().and_then(|e| e.centroid()) {
+            Some(p) => p.into(),
+            None => Coordinate { x: 0., y: 0. },
+        };
+        for a in self.custom_atoms.iter_mut() {
+            a.rotate_around(degrees, pivot);
+        }
+    }
+
+    /// Moves every feature and custom interior atom in the panel by
+    /// the given offset.
+    pub fn translate(&mut self, x: f64, y: f64) {
+        for f in self.features.iter_mut() {
+            f.translate(Coordinate { x, y });
+        }
+        for a in self.custom_atoms.iter_mut() {
+            a.translate(x, y);
+        }
+    }
+
+    /// Appends the features and custom interior atoms of another panel,
+    /// moved by the given offset. Returns an error if the other panel
+    /// has no features.
+    pub fn merge(&mut self, other: &Panel<'a>, offset: (f64, f64)) -> Result<(), Err> {
+        if other.features.is_empty() {
+            return Err(Err::NoFeatures);
+        }
+        let (x, y) = offset;
+
+        for f in other.features.iter() {
+            let mut f = f.clone();
+            f.translate(Coordinate { x, y });
+            self.features.push(f);
+        }
+        for a in other.custom_atoms.iter() {
+            let mut a = a.clone();
+            a.translate(x, y);
+            self.custom_atoms.push(a);
+        }
+        Ok(())
+    }
+
+    /// Rotates each top-level feature by the given number of degrees,
+    /// by wrapping it in its own [`features::Rotate`]. Unlike
+    /// [`Panel::rotate_all`], the panel keeps one entry per feature.
+    /// As each feature turns about its own centroid, custom interior
+    /// atoms belong to no single pivot and are left where they are.
+    pub fn rotate(&mut self, degrees: f64) {
+        self.features = std::mem::take(&mut self.features)
+            .into_iter()
+            .map(|f| {
+                Box::new(features::Rotate::new(degrees, vec![f])) as Box<dyn features::Feature>
+            })
+            .collect();
+    }
+
+    /// Moves each feature such that the center of its bounds lies
+    /// on the nearest multiple of grid_mm.
+    pub fn snap_to_grid(&mut self, grid_mm: f64) {
+        use geo::bounding_rect::BoundingRect;
+        for f in self.features.iter_mut() {
+            if let Some(b) = f.edge_union().and_then(|e| e.bounding_rect()) {
+                let c = b.center();
+                f.translate(Coordinate {
+                    x: snap_coordinate(c.x, grid_mm) - c.x,
+                    y: snap_coordinate(c.y, grid_mm) - c.y,
+                });
+            }
+        }
+    }
+
+    /// Pads the panel such that its bounds are at least min_width by
+    /// min_height, keeping the existing geometry centered. Padding is
+    /// added as small rectangles at the corners of the new bounds.
+    pub fn expand_to_minimum_size(&mut self, min_width: f64, min_height: f64) {
+        let bounds = match self.bounding_box() {
+            Some(b) => b,
+            None => return,
+        };
+        if bounds.width() >= min_width && bounds.height() >= min_height {
+            return;
+        }
+
+        let (w, h) = (
+            bounds.width().max(min_width),
+            bounds.height().max(min_height),
+        );
+        let c = bounds.center();
+        let (min, max) = (
+            Coordinate {
+                x: c.x - w / 2.,
+                y: c.y - h / 2.,
+            },
+            Coordinate {
+                x: c.x + w / 2.,
+                y: c.y + h / 2.,
+            },
+        );
+        let size = Coordinate {
+            x: 1f64.min(w),
+            y: 1f64.min(h),
+        };
+        self.push(features::Rect::new(min, min + size));
+        self.push(features::Rect::new(max - size, max));
+    }
+
+    /// Pads the panel such that the ratio of its width to its height
+    /// matches the given ratio.
+    pub fn expand_to_aspect_ratio(&mut self, ratio: f64) {
+        let bounds = match self.bounding_box() {
+            Some(b) => b,
+            None => return,
+        };
+
+        let (w, h) = (bounds.width(), bounds.height());
+        if w / h < ratio {
+            self.expand_to_minimum_size(h * ratio, h);
+        } else {
+            self.expand_to_minimum_size(w, w / ratio);
+        }
+    }
+
     /// Adds the feature described by the given spec to the panel.
     pub fn push_spec(&mut self, spec_str: &str) -> Result<(), SpecErr> {
-        self.features.append(&mut parser::build(spec_str)?);
+        self.features.append(&mut parser::build_with_layer(
+            spec_str,
+            self.default_layer.clone(),
+        )?);
+        Ok(())
+    }
+
+    /// Reads a spec from the given file and adds the features it describes.
+    /// Unlike [`Panel::push_spec`], the spec may import other specs.
+    pub fn push_spec_file(&mut self, path: &std::path::Path) -> Result<(), SpecErr> {
+        self.features
+            .append(&mut parser::build_from_file_with_layer(
+                path,
+                self.default_layer.clone(),
+            )?);
         Ok(())
     }
 
+    /// Returns a spec describing the features of the panel, which can be
+    /// passed to [`Panel::push_spec`] to reconstruct them. Geometry which
+    /// a spec cannot describe, such as outlines or separators in a column,
+    /// is written as a comment. Settings of the panel itself, such as the
+    /// convex hull transform, are not included.
+    pub fn to_spec_string(&self) -> String {
+        self.features
+            .iter()
+            .map(|f| f.to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
     /// Returns information about the named geometry in the panel.
     pub fn named_info(&self) -> Vec<features::NamedInfo> {
         self.features.iter().fold(vec![], |mut acc, f| {
@@ -175,6 +618,66 @@ impl<'a> Panel<'a> {
         })
     }
 
+    /// Returns the top-level feature which contains geometry with the
+    /// given name, so it can be modified after construction.
+    pub fn feature_named_mut(&mut self, name: &str) -> Option<&mut (dyn Feature + 'a)> {
+        self.features
+            .iter_mut()
+            .find(|f| f.named_info().iter().any(|info| info.name == name))
+            .map(|f| f.as_mut())
+    }
+
+    /// Moves the top-level feature which contains geometry with the given
+    /// name by the given offset, returning false if no such feature exists.
+    pub fn translate_named(&mut self, name: &str, delta: (f64, f64)) -> bool {
+        match self.feature_named_mut(name) {
+            Some(f) => {
+                f.translate(Coordinate {
+                    x: delta.0,
+                    y: delta.1,
+                });
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the names of each pair of named top-level features whose
+    /// edge geometry overlaps. Features which merely touch do not conflict.
+    pub fn interference_check(&self) -> Vec<(String, String)> {
+        use geo::algorithm::area::Area;
+        let named: Vec<_> = self
+            .features
+            .iter()
+            .filter_map(|f| match (f.named_info().first(), f.edge_union()) {
+                (Some(info), Some(edge)) => Some((info.name.clone(), edge)),
+                _ => None,
+            })
+            .collect();
+
+        let mut out = Vec::new();
+        for (i, (a, a_edge)) in named.iter().enumerate() {
+            for (b, b_edge) in named.iter().skip(i + 1) {
+                if a_edge.intersection(b_edge).unsigned_area() > 1e-9 {
+                    out.push((a.clone(), b.clone()));
+                }
+            }
+        }
+        out
+    }
+
+    /// Returns the number of times each type of feature appears in the
+    /// panel, including features contained within other features.
+    pub fn feature_type_counts(&self) -> std::collections::HashMap<&'static str, usize> {
+        self.features.iter().map(|f| f.all_names()).flatten().fold(
+            std::collections::HashMap::new(),
+            |mut acc, name| {
+                *acc.entry(name).or_insert(0) += 1;
+                acc
+            },
+        )
+    }
+
     /// Computes the outer geometry of the panel.
     pub fn edge_geometry(&self) -> Option<MultiPolygon<f64>> {
         let mut edge = self
@@ -229,6 +732,85 @@ impl<'a> Panel<'a> {
         edge
     }
 
+    /// Returns the outer geometry of the panel as Well-Known Text, for use
+    /// with GIS and other spatial analysis tools.
+    pub fn to_wkt(&self) -> Option<String> {
+        Some(wkt::format(&self.edge_geometry()?))
+    }
+
+    /// Constructs a [`Panel`] from a Well-Known Text `POLYGON` or
+    /// `MULTIPOLYGON`, as a single [`features::PolyShape`] feature.
+    pub fn from_wkt(wkt: &str) -> Result<Self, Err> {
+        let mut panel = Self::new();
+        panel.push(features::PolyShape::new(
+            wkt::parse(wkt).map_err(|e| Err::BadWKT(e))?,
+        ));
+        Ok(panel)
+    }
+
+    /// Computes the length of the outer edge of the panel, not including
+    /// the edges of any holes.
+    pub fn outline_length(&self) -> Option<f64> {
+        use geo::algorithm::euclidean_length::EuclideanLength;
+        Some(
+            self.edge_geometry()?
+                .iter()
+                .map(|p| p.exterior().euclidean_length())
+                .sum(),
+        )
+    }
+
+    /// Computes the length of all edges of the panel, including the
+    /// edges of any holes.
+    pub fn outline_with_holes_length(&self) -> Option<f64> {
+        use geo::algorithm::euclidean_length::EuclideanLength;
+        Some(
+            self.edge_geometry()?
+                .iter()
+                .map(|p| std::iter::once(p.exterior()).chain(p.interiors().iter()))
+                .flatten()
+                .map(|ls| ls.euclidean_length())
+                .sum(),
+        )
+    }
+
+    /// Computes the centroid of the outer geometry of the panel, ignoring
+    /// any holes. This differs from the center of the bounding box for
+    /// shapes which are not symmetric.
+    pub fn centroid(&self) -> Option<Coordinate<f64>> {
+        use geo::algorithm::centroid::Centroid;
+        let exteriors: MultiPolygon<f64> = self
+            .edge_geometry()?
+            .into_iter()
+            .map(|p| geo::Polygon::new(p.exterior().clone(), vec![]))
+            .collect();
+        exteriors.centroid().map(|p| p.into())
+    }
+
+    /// Computes the centroid of the outer geometry of the panel, with
+    /// the area of any holes removed.
+    pub fn centroid_with_holes(&self) -> Option<Coordinate<f64>> {
+        use geo::algorithm::centroid::Centroid;
+        self.edge_geometry()?.centroid().map(|p| p.into())
+    }
+
+    /// Returns the bounding box of the outer geometry of the panel, or
+    /// None if the panel has no edge geometry.
+    pub fn bounding_box(&self) -> Option<geo::Rect<f64>> {
+        use geo::bounding_rect::BoundingRect;
+        self.edge_geometry()?.bounding_rect()
+    }
+
+    /// Returns the width of the bounding box of the panel.
+    pub fn width(&self) -> Option<f64> {
+        self.bounding_box().map(|b| b.width())
+    }
+
+    /// Returns the height of the bounding box of the panel.
+    pub fn height(&self) -> Option<f64> {
+        self.bounding_box().map(|b| b.height())
+    }
+
     fn edge_poly(&self) -> Result<geo::Polygon<f64>, Err> {
         match self.edge_geometry() {
             Some(edges) => {
@@ -245,19 +827,391 @@ impl<'a> Panel<'a> {
         }
     }
 
+    /// Returns the indices of features whose outer geometry has a bounding
+    /// box overlapping the provided rectangle.
+    pub fn features_overlapping_rect_approx(&self, rect: geo::Rect<f64>) -> Vec<usize> {
+        use geo::bounding_rect::BoundingRect;
+        self.features
+            .iter()
+            .enumerate()
+            .filter(|(_, f)| match f.edge_union() {
+                Some(edge) => match edge.bounding_rect() {
+                    Some(b) => rects_overlap(b, rect),
+                    None => false,
+                },
+                None => false,
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Returns the indices of features whose outer geometry overlaps the
+    /// provided rectangle. This is more expensive than
+    /// [`Panel::features_overlapping_rect_approx`], as candidates are
+    /// checked with a precise intersection test.
+    pub fn features_overlapping_rect_exact(&self, rect: geo::Rect<f64>) -> Vec<usize> {
+        let area = rect.to_polygon();
+        self.features_overlapping_rect_approx(rect)
+            .into_iter()
+            .filter(|i| match self.features[*i].edge_union() {
+                Some(edge) => edge.intersection(&area).0.len() > 0,
+                None => false,
+            })
+            .collect()
+    }
+
+    /// Returns the indices of features whose outer geometry contains
+    /// the provided point.
+    pub fn feature_at_point(&self, pt: Coordinate<f64>) -> Vec<usize> {
+        use geo::prelude::Contains;
+        self.features_overlapping_rect_approx(geo::Rect::new(pt, pt))
+            .into_iter()
+            .filter(|i| match self.features[*i].edge_union() {
+                Some(edge) => edge.contains(&pt),
+                None => false,
+            })
+            .collect()
+    }
+
+    /// Returns the distance from each drill center to the nearest point
+    /// on the edge of the panel, paired with the center of that drill.
+    fn drill_edge_distances(&self) -> Vec<(Coordinate<f64>, f64)> {
+        use geo::algorithm::euclidean_distance::EuclideanDistance;
+        let edges = match self.edge_geometry() {
+            Some(edges) => edges,
+            None => return vec![],
+        };
+
+        self.interior_geometry()
+            .into_iter()
+            .filter_map(|a| match a {
+                InnerAtom::Drill { center, .. } => {
+                    let pt = geo::Point::from(center);
+                    let dist = edges
+                        .iter()
+                        .map(|p| std::iter::once(p.exterior()).chain(p.interiors().iter()))
+                        .flatten()
+                        .map(|ls| pt.euclidean_distance(ls))
+                        .fold(f64::INFINITY, f64::min);
+                    Some((center, dist))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns the minimum distance between any drill center and the
+    /// edge of the panel, or None if there are no drills.
+    pub fn drill_to_edge_min_distance(&self) -> Option<f64> {
+        self.drill_edge_distances()
+            .into_iter()
+            .map(|(_, d)| d)
+            .fold(None, |acc, d| match acc {
+                Some(min) if min <= d => Some(min),
+                _ => Some(d),
+            })
+    }
+
+    /// Returns the centers of drills which are closer to the edge of the
+    /// panel than the provided clearance.
+    pub fn drills_violating_edge_clearance(&self, min_clearance: f64) -> Vec<Coordinate<f64>> {
+        self.drill_edge_distances()
+            .into_iter()
+            .filter(|(_, d)| *d < min_clearance)
+            .map(|(c, _)| c)
+            .collect()
+    }
+
+    /// Returns the distances between the centers of every pair of
+    /// drills, such that element `[i][j]` is the distance between
+    /// drill i and drill j.
+    pub fn drill_clearance_matrix(&self) -> Vec<Vec<f64>> {
+        use geo::algorithm::euclidean_distance::EuclideanDistance;
+        let centers: Vec<geo::Point<f64>> = self.iter_drills().map(|d| d.center.into()).collect();
+
+        centers
+            .iter()
+            .map(|a| centers.iter().map(|b| a.euclidean_distance(b)).collect())
+            .collect()
+    }
+
+    /// Returns the minimum distance between the centers of any two
+    /// drills, or None if there are less than two drills.
+    pub fn minimum_drill_to_drill_clearance(&self) -> Option<f64> {
+        self.drill_clearance_matrix()
+            .into_iter()
+            .enumerate()
+            .map(|(i, row)| {
+                row.into_iter()
+                    .enumerate()
+                    .filter(move |(j, _)| *j != i)
+                    .map(|(_, d)| d)
+            })
+            .flatten()
+            .fold(None, |acc, d| match acc {
+                Some(min) if min <= d => Some(min),
+                _ => Some(d),
+            })
+    }
+
+    /// Checks the panel against the given design rules, returning every
+    /// violation found. Copper is only checked against copper on the same
+    /// layer, with the gap between them approximated using their bounds.
+    pub fn validate(&self, rules: &DesignRules) -> Vec<Violation> {
+        use geo::algorithm::euclidean_distance::EuclideanDistance;
+        let interior = self.interior_geometry();
+        let drills: Vec<_> = interior
+            .iter()
+            .filter_map(|a| match a {
+                InnerAtom::Drill { center, radius, .. } => Some((a, *center, *radius)),
+                _ => None,
+            })
+            .collect();
+        let mut out = Vec::new();
+
+        for (_, center, radius) in &drills {
+            if radius * 2. < rules.min_drill_diameter_mm {
+                out.push(Violation::TooSmallDrill {
+                    diameter: radius * 2.,
+                    center: *center,
+                });
+            }
+        }
+
+        // Distances are returned for each drill, in the same order.
+        for ((atom, _, radius), (_, dist)) in drills.iter().zip(self.drill_edge_distances()) {
+            let distance = dist - radius;
+            if distance < rules.min_edge_clearance_mm {
+                out.push(Violation::TooCloseToEdge {
+                    atom: (*atom).clone(),
+                    distance,
+                });
+            }
+        }
+
+        for (i, (_, a, ra)) in drills.iter().enumerate() {
+            for (_, b, rb) in drills.iter().skip(i + 1) {
+                let distance =
+                    geo::Point::from(*a).euclidean_distance(&geo::Point::from(*b)) - ra - rb;
+                if distance < rules.min_drill_spacing_mm {
+                    out.push(Violation::DrillsTooClose {
+                        a: *a,
+                        b: *b,
+                        distance,
+                    });
+                }
+            }
+        }
+
+        let copper: Vec<_> = interior
+            .iter()
+            .filter_map(|a| match (a.layer(), a.bounds()) {
+                (Some(layer @ Layer::FrontCopper), Some(b))
+                | (Some(layer @ Layer::BackCopper), Some(b)) => Some((a, layer, b)),
+                _ => None,
+            })
+            .collect();
+        for (i, (a, la, ba)) in copper.iter().enumerate() {
+            for (b, lb, bb) in copper.iter().skip(i + 1) {
+                if la != lb {
+                    continue;
+                }
+                let dx = (ba.min().x - bb.max().x).max(bb.min().x - ba.max().x);
+                let dy = (ba.min().y - bb.max().y).max(bb.min().y - ba.max().y);
+                let distance = dx.max(0.).hypot(dy.max(0.));
+                // Overlapping copper is part of the same conductor.
+                if distance > 0. && distance < rules.min_copper_clearance_mm {
+                    out.push(Violation::CopperTooClose {
+                        a: (*a).clone(),
+                        b: (*b).clone(),
+                        distance,
+                    });
+                }
+            }
+        }
+
+        out
+    }
+
     /// Computes the inner geometry of the panel.
     pub fn interior_geometry(&self) -> Vec<InnerAtom> {
         self.features
             .iter()
             .map(|f| f.interior())
             .flatten()
+            .chain(self.custom_atoms.iter().cloned())
+            .collect()
+    }
+
+    /// Returns the drill hits in the panel.
+    pub fn iter_drills(&self) -> impl Iterator<Item = DrillInfo> {
+        self.interior_geometry()
+            .into_iter()
+            .filter_map(|a| match a {
+                InnerAtom::Drill {
+                    center,
+                    radius,
+                    plated,
+                } => Some(DrillInfo {
+                    center,
+                    diameter: radius * 2.,
+                    plated,
+                }),
+                _ => None,
+            })
+    }
+
+    /// Returns the plated drill hits in the panel.
+    pub fn iter_drills_plated(&self) -> impl Iterator<Item = DrillInfo> {
+        self.iter_drills().filter(|d| d.plated)
+    }
+
+    /// Returns the number of drill hits of each size, ordered by increasing
+    /// diameter. Slots are counted by their width.
+    pub fn drill_summary(&self) -> Vec<DrillSize> {
+        let mut out: Vec<DrillSize> = Vec::new();
+        for a in self.interior_geometry() {
+            let (diameter, plated) = match a {
+                InnerAtom::Drill { radius, plated, .. } => (radius * 2., plated),
+                InnerAtom::Slot { width, plated, .. } => (width, plated),
+                _ => continue,
+            };
+            match out
+                .iter_mut()
+                .find(|s| s.plated == plated && (s.diameter_mm - diameter).abs() < 1e-6)
+            {
+                Some(s) => s.count += 1,
+                None => out.push(DrillSize {
+                    diameter_mm: diameter,
+                    count: 1,
+                    plated,
+                }),
+            }
+        }
+        out.sort_by(|a, b| a.diameter_mm.partial_cmp(&b.diameter_mm).unwrap());
+        out
+    }
+
+    /// Returns the non-plated drill hits in the panel.
+    pub fn iter_drills_npth(&self) -> impl Iterator<Item = DrillInfo> {
+        self.iter_drills().filter(|d| !d.plated)
+    }
+
+    /// Returns the center and radius of each circle drawn on the given layer.
+    pub fn iter_copper_circles(
+        &self,
+        layer: Layer,
+    ) -> impl Iterator<Item = (Coordinate<f64>, f64)> {
+        self.interior_geometry_layer(layer)
+            .into_iter()
+            .filter_map(|a| match a {
+                InnerAtom::Circle { center, radius, .. } => Some((center, radius)),
+                _ => None,
+            })
+    }
+
+    /// Returns each rectangle drawn on the given layer.
+    pub fn iter_copper_rects(&self, layer: Layer) -> impl Iterator<Item = geo::Rect<f64>> {
+        self.interior_geometry_layer(layer)
+            .into_iter()
+            .filter_map(|a| match a {
+                InnerAtom::Rect { rect, .. } => Some(rect),
+                _ => None,
+            })
+    }
+
+    /// Computes the inner geometry of the panel which is drawn on the
+    /// given layer. Drill hits and v-scores are not on any layer, so are
+    /// never returned.
+    pub fn interior_geometry_layer(&self, layer: Layer) -> Vec<InnerAtom> {
+        self.interior_geometry()
+            .into_iter()
+            .filter(|a| a.layer().as_ref() == Some(&layer))
+            .collect()
+    }
+
+    /// Returns the interior atoms drawn on the given layer.
+    #[deprecated(note = "use `interior_geometry_layer` instead")]
+    pub fn interior_for_layer(&self, layer: &Layer) -> Vec<InnerAtom> {
+        self.interior_geometry_layer(layer.clone())
+    }
+
+    /// Returns the drill and slot atoms which are either plated or
+    /// non-plated.
+    pub fn interior_drills(&self, plated: bool) -> Vec<InnerAtom> {
+        self.interior_geometry()
+            .into_iter()
+            .filter(|a| match a {
+                InnerAtom::Drill { plated: p, .. } | InnerAtom::Slot { plated: p, .. } => {
+                    *p == plated
+                }
+                _ => false,
+            })
             .collect()
     }
 
+    /// Computes the inner geometry of the panel which overlaps the given
+    /// rectangle, translated such that the minimum corner of the
+    /// rectangle is the origin.
+    pub fn interior_atoms_in_rect(&self, rect: geo::Rect<f64>) -> Vec<InnerAtom> {
+        let (min, max) = (rect.min(), rect.max());
+        self.interior_geometry()
+            .into_iter()
+            .filter(|a| match a {
+                InnerAtom::VScoreH(y) => *y >= min.y && *y <= max.y,
+                InnerAtom::VScoreV(x) => *x >= min.x && *x <= max.x,
+                _ => match a.bounds() {
+                    Some(b) => {
+                        b.min().x < max.x
+                            && b.max().x > min.x
+                            && b.min().y < max.y
+                            && b.max().y > min.y
+                    }
+                    None => false,
+                },
+            })
+            .map(|mut a| {
+                a.translate(-min.x, -min.y);
+                a
+            })
+            .collect()
+    }
+
+    /// Returns SVG path data strings describing the inner geometry
+    /// drawn on the given layer.
+    pub fn layer_svg_paths(&self, layer: Layer) -> Result<Vec<String>, Err> {
+        Ok(self
+            .interior_geometry_layer(layer)
+            .into_iter()
+            .filter_map(|a| match a {
+                InnerAtom::Circle { center, radius, .. } => Some(format!(
+                    "M {x1} {y} A {r} {r} 0 1 1 {x2} {y} A {r} {r} 0 1 1 {x1} {y} Z",
+                    x1 = center.x + radius,
+                    x2 = center.x - radius,
+                    y = center.y,
+                    r = radius,
+                )),
+                InnerAtom::Rect { rect, .. } => Some(format!(
+                    "M {} {} L {} {} L {} {} L {} {} Z",
+                    rect.min().x,
+                    rect.min().y,
+                    rect.max().x,
+                    rect.min().y,
+                    rect.max().x,
+                    rect.max().y,
+                    rect.min().x,
+                    rect.max().y,
+                )),
+                _ => None,
+            })
+            .collect())
+    }
+
     /// Serializes a gerber file describing the PCB profile to the provided writer.
     pub fn serialize_gerber_edges<W: std::io::Write>(&self, w: &mut W) -> Result<(), Err> {
         let edges = self.edge_poly()?;
-        let commands = gerber::serialize_edge(edges).map_err(|_| Err::InternalGerberFailure)?;
+        let commands = gerber::serialize_edge(edges, &gerber::GerberMetadata::default())
+            .map_err(|_| Err::InternalGerberFailure)?;
         use gerber_types::GerberCode;
         commands
             .serialize(w)
@@ -275,23 +1229,142 @@ impl<'a> Panel<'a> {
         let edges = self.edge_poly()?;
         let bounds = edges.bounding_rect().unwrap();
 
-        let commands = gerber::serialize_layer(layer, self.interior_geometry(), bounds)
-            .map_err(|_| Err::InternalGerberFailure)?;
+        let commands = gerber::serialize_layer(
+            layer,
+            self.interior_geometry(),
+            bounds,
+            &gerber::GerberMetadata::default(),
+        )
+        .map_err(|_| Err::InternalGerberFailure)?;
         use gerber_types::GerberCode;
         commands
             .serialize(w)
             .map_err(|_| Err::InternalGerberFailure)
     }
 
-    /// Serializes a drill file describing drill hits to the provided writer.
-    pub fn serialize_drill<W: std::io::Write>(
-        &self,
-        w: &mut W,
-        want_plated: bool,
-    ) -> Result<(), std::io::Error> {
-        drill::serialize(&self.interior_geometry(), w, want_plated)
-    }
-
+    /// Builds a zip archive containing the gerbers and drill files needed
+    /// to fabricate the panel, returning the bytes of the archive.
+    pub fn export_gerbers_zip(&self) -> Result<Vec<u8>, Err> {
+        use std::io::Write;
+        let mut cursor = std::io::Cursor::new(Vec::with_capacity(4 * 1024));
+        let mut zip = zip::ZipWriter::new(&mut cursor);
+        let options = zip::write::FileOptions::default()
+            .compression_method(zip::CompressionMethod::Stored)
+            .unix_permissions(0o755);
+
+        zip.start_file(EDGE_FILE_SUFFIX, options)
+            .map_err(|e| Err::Zip(e))?;
+        self.serialize_gerber_edges(&mut zip)?;
+        for layer in ZIP_GERBER_LAYERS {
+            zip.start_file(layer.file_suffix(), options)
+                .map_err(|e| Err::Zip(e))?;
+            self.serialize_gerber_layer(layer.clone(), &mut zip)?;
+        }
+        for plated in &[true, false] {
+            zip.start_file(drill_file_suffix(*plated), options)
+                .map_err(|e| Err::Zip(e))?;
+            self.serialize_drill(&mut zip, *plated, DrillUnits::Inches)
+                .map_err(|e| Err::IO(e))?;
+        }
+
+        if self.has_fab_markings() {
+            zip.start_file(Layer::FabricationInstructions.file_suffix(), options)
+                .map_err(|e| Err::Zip(e))?;
+            self.serialize_gerber_layer(Layer::FabricationInstructions, &mut zip)?;
+
+            zip.start_file("fab-notes.txt", options)
+                .map_err(|e| Err::Zip(e))?;
+            zip.write_all(b"V-SCORE: See Cmts.User gerber file.\n")
+                .map_err(|e| Err::IO(e))?;
+        }
+        zip.finish().map_err(|e| Err::Zip(e))?;
+
+        drop(zip);
+        Ok(cursor.into_inner())
+    }
+
+    /// Serializes a drill file describing drill hits to the provided writer,
+    /// in the given units. Slots are described using G85 commands.
+    pub fn serialize_drill<W: std::io::Write>(
+        &self,
+        w: &mut W,
+        want_plated: bool,
+        units: DrillUnits,
+    ) -> Result<(), std::io::Error> {
+        self.serialize_drill_with_slots(w, want_plated, SlotFormat::G85, units)
+    }
+
+    /// Serializes a drill file describing drill hits to the provided writer,
+    /// describing slots in the given format.
+    pub fn serialize_drill_with_slots<W: std::io::Write>(
+        &self,
+        w: &mut W,
+        want_plated: bool,
+        slot_format: SlotFormat,
+        units: DrillUnits,
+    ) -> Result<(), std::io::Error> {
+        drill::serialize(
+            &self.interior_geometry(),
+            w,
+            want_plated,
+            slot_format,
+            units,
+        )
+    }
+
+    /// Serializes an Excellon format 2 drill file, in metric units and
+    /// with a tool table, to the provided writer.
+    pub fn serialize_excellon_v2<W: std::io::Write>(
+        &self,
+        w: &mut W,
+        plated: bool,
+    ) -> Result<(), std::io::Error> {
+        drill::serialize_v2(&self.interior_geometry(), w, plated, SlotFormat::G85)
+    }
+
+    /// Writes a human-readable summary of the drill hits in the panel
+    /// to the provided writer.
+    pub fn serialize_drill_report<W: std::io::Write>(
+        &self,
+        w: &mut W,
+        plated: bool,
+    ) -> Result<(), std::io::Error> {
+        drill::serialize_report(&self.interior_geometry(), w, plated)
+    }
+
+    /// Serializes a DXF drawing of the panel outline, drill hits, and
+    /// v-score lines to the provided writer.
+    pub fn serialize_dxf<W: std::io::Write>(&self, w: &mut W) -> Result<(), Err> {
+        use geo::bounding_rect::BoundingRect;
+        let edges = self.edge_poly()?;
+        let bounds = edges.bounding_rect().ok_or(Err::NoBounds)?;
+        dxf::serialize(&edges, bounds, &self.interior_geometry(), w).map_err(|e| Err::IO(e))
+    }
+
+    /// Serializes a pick-and-place file describing the position of
+    /// components to the provided writer. Named features with names
+    /// like a reference designator (such as `U1`) are treated as components.
+    pub fn serialize_pick_and_place<W: std::io::Write>(
+        &self,
+        w: &mut W,
+    ) -> Result<(), std::io::Error> {
+        pnp::serialize(&self.named_info(), &self.interior_geometry(), w)
+    }
+
+    /// Serializes an IPC-D-356A netlist to the provided writer, listing
+    /// each drill hit and copper pad as a test point.
+    pub fn export_ipc_d356<W: std::io::Write>(&self, w: &mut W) -> Result<(), std::io::Error> {
+        ipc356::serialize(&self.interior_geometry(), w)
+    }
+
+    /// Serializes a bill of materials to the provided writer. Named
+    /// features are listed individually, followed by the count of each
+    /// type of feature which is not named.
+    pub fn serialize_bom<W: std::io::Write>(&self, w: &mut W) -> Result<(), std::io::Error> {
+        let parts: Vec<_> = self.features.iter().map(|f| f.parts()).flatten().collect();
+        bom::serialize(&self.named_info(), &parts, w)
+    }
+
     /// Computes the 2d tessellation of the panel.
     #[cfg(feature = "tessellate")]
     pub fn tessellate_2d(&self) -> Result<VertexBuffers<TPoint, u16>, Err> {
@@ -301,10 +1374,31 @@ impl<'a> Panel<'a> {
         )
     }
 
+    /// Computes the 2d tessellation of the panel, with texture coordinates
+    /// spanning the bounding box of the panel.
+    #[cfg(feature = "tessellate")]
+    pub fn tessellate_2d_with_uv(&self) -> Result<VertexBuffers<PointWithUV, u16>, Err> {
+        Ok(
+            tessellate::tessellate_2d_with_uv(self.edge_poly()?, self.interior_geometry())
+                .map_err(|e| Err::TessellationError(e))?,
+        )
+    }
+
     /// Computes the 3d tessellation of the panel.
     #[cfg(feature = "tessellate")]
     pub fn tessellate_3d(&self) -> Result<(Vec<[f64; 3]>, Vec<u16>), Err> {
-        Ok(tessellate::tessellate_3d(self.tessellate_2d()?))
+        Ok(tessellate::tessellate_3d(
+            self.tessellate_2d()?,
+            self.board_thickness,
+        ))
+    }
+
+    /// Serializes a binary STL file of the 3d tessellation to the
+    /// provided writer.
+    #[cfg(feature = "tessellate")]
+    pub fn serialize_stl<W: std::io::Write>(&self, w: &mut W) -> Result<(), Err> {
+        let (verts, inds) = self.tessellate_3d()?;
+        tessellate::serialize_stl(&verts, &inds, w).map_err(|e| Err::IO(e))
     }
 
     /// Expands the bounds of the drawing area to give space to any
@@ -314,7 +1408,7 @@ impl<'a> Panel<'a> {
         let has_h_scores = ig.iter().any(|g| matches!(g, InnerAtom::VScoreH(_)));
         let has_v_scores = ig.iter().any(|g| matches!(g, InnerAtom::VScoreV(_)));
 
-        match (has_h_scores, has_v_scores) {
+        let bounds = match (has_h_scores, has_v_scores) {
             (true, true) => geo::Rect::<f64>::new(
                 bounds.min() - [10., 15.].into(),
                 bounds.max() + [65., 65.].into(),
@@ -328,7 +1422,36 @@ impl<'a> Panel<'a> {
                 bounds.max() + [5., 65.].into(),
             ),
             _ => bounds,
+        };
+
+        // Grid labels are drawn from each grid line outwards, so give the
+        // longest label room to fit on every edge.
+        #[cfg(feature = "text")]
+        if let Some(sep) = self.grid_separation {
+            let pad = text::text_width(self.grid_label_len(bounds, sep)) + 1.;
+            return geo::Rect::<f64>::new(
+                bounds.min() - [pad, pad].into(),
+                bounds.max() + [pad, pad].into(),
+            );
         }
+
+        bounds
+    }
+
+    /// Returns the length of the longest grid label drawn within the
+    /// given bounds.
+    #[cfg(feature = "text")]
+    fn grid_label_len(&self, bounds: geo::Rect<f64>, sep: isize) -> usize {
+        [
+            ((bounds.min().x.floor() as isize) / sep) * sep,
+            ((bounds.max().x.ceil() as isize) / sep) * sep,
+            ((bounds.min().y.floor() as isize) / sep) * sep,
+            ((bounds.max().y.ceil() as isize) / sep) * sep,
+        ]
+        .iter()
+        .map(|l| l.to_string().len())
+        .max()
+        .unwrap_or(0)
     }
 
     /// Indicates if the panel has fabrication instructions, such as
@@ -341,6 +1464,66 @@ impl<'a> Panel<'a> {
         has_h_scores || has_v_scores
     }
 
+    /// Returns true if the given layer has any content.
+    pub fn has_layer(&self, layer: Layer) -> bool {
+        match layer {
+            Layer::FabricationInstructions => self.has_fab_markings(),
+            _ => self
+                .interior_geometry()
+                .iter()
+                .any(|a| a.layer().as_ref() == Some(&layer)),
+        }
+    }
+
+    /// Returns all layers which have content.
+    pub fn non_empty_layers(&self) -> Vec<Layer> {
+        vec![
+            Layer::FrontCopper,
+            Layer::FrontMask,
+            Layer::FrontLegend,
+            Layer::BackCopper,
+            Layer::BackMask,
+            Layer::BackLegend,
+            Layer::PasteFront,
+            Layer::PasteBack,
+            Layer::CourtyardFront,
+            Layer::CourtyardBack,
+            Layer::FabricationInstructions,
+        ]
+        .into_iter()
+        .filter(|l| self.has_layer(l.clone()))
+        .collect()
+    }
+
+    /// Estimates the cost of fabricating the panel, based on its area,
+    /// the number of drill hits, and the layers which have content.
+    /// This is only intended as a rough sanity check.
+    pub fn compute_manufacturing_cost_estimate(
+        &self,
+        tariff: &ManufacturingTariff,
+    ) -> Option<CostEstimate> {
+        use geo::algorithm::area::Area;
+        let area = match self.edge_geometry() {
+            Some(edges) => edges.unsigned_area(),
+            None => 0.,
+        };
+
+        let base = area * tariff.base_mm2;
+        let drills = self.iter_drills().count() as f64 * tariff.drill_per_hole;
+        let layers = self
+            .non_empty_layers()
+            .iter()
+            .map(|l| tariff.layer_surcharge.get(l).unwrap_or(&0.))
+            .sum();
+
+        Some(CostEstimate {
+            base,
+            drills,
+            layers,
+            total: base + drills + layers,
+        })
+    }
+
     /// Produces an SVG tree rendering the panel.
     pub fn make_svg(&self) -> Result<usvg::Tree, Err> {
         let edges = self.edge_poly()?;
@@ -357,55 +1540,32 @@ impl<'a> Panel<'a> {
         let rtree = usvg::Tree::create(usvg::Svg {
             size,
             view_box: usvg::ViewBox {
-                rect: size.to_rect(0.0, 0.0),
+                rect: size.to_rect(img_bounds.min().x, img_bounds.min().y),
                 aspect: usvg::AspectRatio::default(),
             },
         });
 
+        // Holes are drawn as sub-paths of the outline, so the even-odd
+        // fill rule leaves them unfilled.
         let mut path = usvg::PathData::new();
-        let mut has_moved = false;
-        for point in edges.exterior().points_iter() {
-            if !has_moved {
-                has_moved = true;
-                path.push_move_to(point.x(), point.y());
-            } else {
-                path.push_line_to(point.x(), point.y());
-            }
+        for ring in std::iter::once(edges.exterior()).chain(edges.interiors().iter()) {
+            ring_path(&mut path, ring);
         }
-        path.push_close_path();
         rtree.root().append_kind(usvg::NodeKind::Path(usvg::Path {
             stroke: Some(usvg::Stroke {
                 paint: usvg::Paint::Color(usvg::Color::new(0, 0, 0)),
                 width: usvg::StrokeWidth::new(0.1),
                 ..usvg::Stroke::default()
             }),
+            fill: Some(usvg::Fill {
+                paint: usvg::Paint::Color(BOARD_COLOR),
+                rule: usvg::FillRule::EvenOdd,
+                ..usvg::Fill::default()
+            }),
             data: std::rc::Rc::new(path),
             ..usvg::Path::default()
         }));
 
-        for inners in edges.interiors() {
-            let mut path = usvg::PathData::new();
-            let mut has_moved = false;
-            for point in inners.points_iter() {
-                if !has_moved {
-                    has_moved = true;
-                    path.push_move_to(point.x(), point.y());
-                } else {
-                    path.push_line_to(point.x(), point.y());
-                }
-            }
-            path.push_close_path();
-            rtree.root().append_kind(usvg::NodeKind::Path(usvg::Path {
-                stroke: Some(usvg::Stroke {
-                    paint: usvg::Paint::Color(usvg::Color::new(0, 0, 0)),
-                    width: usvg::StrokeWidth::new(0.1),
-                    ..usvg::Stroke::default()
-                }),
-                data: std::rc::Rc::new(path),
-                ..usvg::Path::default()
-            }));
-        }
-
         for inner in self.interior_geometry() {
             match inner {
                 InnerAtom::Circle { center, radius, .. } => {
@@ -438,6 +1598,17 @@ impl<'a> Panel<'a> {
                         ..usvg::Path::default()
                     }));
                 }
+                InnerAtom::Slot {
+                    start, end, width, ..
+                } => {
+                    let p = slot(start, end, width / 2.);
+                    rtree.root().append_kind(usvg::NodeKind::Path(usvg::Path {
+                        stroke: inner.stroke(),
+                        fill: inner.fill(),
+                        data: std::rc::Rc::new(p),
+                        ..usvg::Path::default()
+                    }));
+                }
 
                 InnerAtom::VScoreH(y) => {
                     let mut p = usvg::PathData::with_capacity(2);
@@ -459,6 +1630,17 @@ impl<'a> Panel<'a> {
                             "v-score".into(),
                         )));
                 }
+                InnerAtom::Line { start, end, .. } => {
+                    let mut p = usvg::PathData::with_capacity(2);
+                    p.push_move_to(start.x, start.y);
+                    p.push_line_to(end.x, end.y);
+                    rtree.root().append_kind(usvg::NodeKind::Path(usvg::Path {
+                        stroke: inner.stroke(),
+                        fill: inner.fill(),
+                        data: std::rc::Rc::new(p),
+                        ..usvg::Path::default()
+                    }));
+                }
                 InnerAtom::VScoreV(x) => {
                     let mut p = usvg::PathData::with_capacity(2);
                     p.push_move_to(x, bounds.min().y - 4.);
@@ -540,6 +1722,123 @@ impl<'a> Panel<'a> {
 
         Ok(rtree)
     }
+
+    fn slice(&self, a: slice::HalfPlane, b: slice::HalfPlane) -> (Panel<'a>, Panel<'a>) {
+        let half = |keep: slice::HalfPlane| Panel {
+            features: self
+                .features
+                .iter()
+                .map(|f| Box::new(slice::Slice::new(f.clone(), keep)) as Box<dyn Feature + 'a>)
+                .collect(),
+            custom_atoms: self
+                .custom_atoms
+                .iter()
+                .filter(|a| keep.contains_atom(a))
+                .cloned()
+                .collect(),
+            convex_hull: self.convex_hull,
+            grid_separation: self.grid_separation,
+            default_layer: self.default_layer.clone(),
+            board_thickness: self.board_thickness,
+        };
+        (half(a), half(b))
+    }
+
+    /// Splits the panel along the horizontal line at y, returning the
+    /// part above the line and the part below it. Interior geometry
+    /// is assigned to whichever side its center lies on, and v-scores
+    /// along the cut are dropped.
+    pub fn slice_horizontal(&self, y: f64) -> (Panel<'a>, Panel<'a>) {
+        self.slice(slice::HalfPlane::Above(y), slice::HalfPlane::Below(y))
+    }
+
+    /// Splits the panel along the vertical line at x, returning the
+    /// part left of the line and the part right of it. Interior geometry
+    /// is assigned to whichever side its center lies on, and v-scores
+    /// along the cut are dropped.
+    pub fn slice_vertical(&self, x: f64) -> (Panel<'a>, Panel<'a>) {
+        self.slice(slice::HalfPlane::Left(x), slice::HalfPlane::Right(x))
+    }
+
+    /// Renders the panel to a PNG image of the given width, returning
+    /// the encoded bytes.
+    #[cfg(feature = "render")]
+    pub fn make_png_bytes(&self, width_px: u32) -> Result<Vec<u8>, Err> {
+        let tree = self.make_svg()?;
+        let img = resvg::render_node(
+            &tree.root(),
+            usvg::FitTo::Width(width_px),
+            Some(usvg::Color::white()),
+        )
+        .ok_or(Err::RenderFailure)?;
+
+        let mut out: Vec<u8> = Vec::with_capacity(img.data().len() / 4);
+        let mut encoder = png::Encoder::new(&mut out, img.width(), img.height());
+        encoder.set_color(png::ColorType::RGBA);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(|_| Err::RenderFailure)?;
+        writer
+            .write_image_data(img.data())
+            .map_err(|_| Err::RenderFailure)?;
+        drop(writer);
+        Ok(out)
+    }
+
+    /// Renders the panel to an SVG document, returning the UTF-8 bytes.
+    pub fn to_svg_bytes(&self) -> Result<Vec<u8>, Err> {
+        Ok(self.to_svg_string()?.into_bytes())
+    }
+
+    /// Renders the panel to an SVG document.
+    pub fn to_svg_string(&self) -> Result<String, Err> {
+        let tree = self.make_svg()?;
+        Ok(tree.to_string(usvg::XmlOptions::default()))
+    }
+
+    /// Renders the panel to an SVG document, writing it to the
+    /// provided writer.
+    pub fn serialize_svg<W: std::io::Write>(&self, w: &mut W) -> Result<(), Err> {
+        w.write_all(self.to_svg_string()?.as_bytes())
+            .map_err(|e| Err::IO(e))
+    }
+
+    /// Renders the panel to a gzip-compressed SVG document (SVGZ).
+    #[cfg(feature = "compress")]
+    pub fn to_svgz_bytes(&self) -> Result<Vec<u8>, Err> {
+        use std::io::Write;
+        let svg = self.to_svg_bytes()?;
+        let mut enc = flate2::write::GzEncoder::new(
+            Vec::with_capacity(svg.len() / 4),
+            flate2::Compression::default(),
+        );
+        enc.write_all(&svg).map_err(|_| Err::CompressionFailure)?;
+        enc.finish().map_err(|_| Err::CompressionFailure)
+    }
+}
+
+/// Color of the substrate when rendering the panel.
+const BOARD_COLOR: usvg::Color = usvg::Color {
+    red: 0xf2,
+    green: 0xec,
+    blue: 0xd6,
+};
+
+fn ring_path(p: &mut usvg::PathData, ring: &geo::LineString<f64>) {
+    for (i, point) in ring.points_iter().enumerate() {
+        if i == 0 {
+            p.push_move_to(point.x(), point.y());
+        } else {
+            p.push_line_to(point.x(), point.y());
+        }
+    }
+    p.push_close_path();
+}
+
+fn rects_overlap(a: geo::Rect<f64>, b: geo::Rect<f64>) -> bool {
+    a.min().x <= b.max().x
+        && b.min().x <= a.max().x
+        && a.min().y <= b.max().y
+        && b.min().y <= a.max().y
 }
 
 fn circle(center: Coordinate<f64>, radius: f64) -> usvg::PathData {
@@ -585,6 +1884,34 @@ fn circle(center: Coordinate<f64>, radius: f64) -> usvg::PathData {
     p
 }
 
+/// Returns a stadium shape: a rectangle between the two points, capped
+/// with a semicircle at each end.
+fn slot(start: Coordinate<f64>, end: Coordinate<f64>, radius: f64) -> usvg::PathData {
+    let (dx, dy) = (end.x - start.x, end.y - start.y);
+    let len = (dx * dx + dy * dy).sqrt();
+    if len == 0. {
+        return circle(start, radius);
+    }
+    let (nx, ny) = (-dy / len * radius, dx / len * radius);
+
+    let mut p = usvg::PathData::with_capacity(6);
+    p.push_move_to(start.x + nx, start.y + ny);
+    p.push_line_to(end.x + nx, end.y + ny);
+    p.push_arc_to(radius, radius, 0.0, false, false, end.x - nx, end.y - ny);
+    p.push_line_to(start.x - nx, start.y - ny);
+    p.push_arc_to(
+        radius,
+        radius,
+        0.0,
+        false,
+        false,
+        start.x + nx,
+        start.y + ny,
+    );
+    p.push_close_path();
+    p
+}
+
 fn rect(rect: geo::Rect<f64>) -> usvg::PathData {
     let mut p = usvg::PathData::with_capacity(5);
     p.push_move_to(rect.min().x, rect.min().y);
@@ -641,20 +1968,33 @@ mod tests {
         let mut panel = Panel::new();
         panel.push_spec("R<@(2.5, -2.5), 5>(h3)").unwrap();
 
-        // eprintln!("{:?}", panel.interior_geometry());
-        for i in 0..5 {
-            assert!(panel.interior_geometry()[i].bounds().unwrap().center().x > 2.49);
-            assert!(panel.interior_geometry()[i].bounds().unwrap().center().x < 2.51);
-            assert!(panel.interior_geometry()[i].bounds().unwrap().center().y < -2.49);
-            assert!(panel.interior_geometry()[i].bounds().unwrap().center().y > -2.51);
-        }
+        use features::InnerFeature;
+        let mut hole = features::ScrewHole::with_diameter(3.);
+        hole.translate([2.5, -2.5].into());
+        assert_eq!(panel.interior_geometry(), hole.atoms());
+
+        // Atoms are hashable, so duplicates can be removed with a set.
+        let atoms: std::collections::HashSet<_> = panel.interior_geometry().into_iter().collect();
+        assert_eq!(atoms.len(), hole.atoms().len());
+        assert!(hole.atoms().iter().all(|a| atoms.contains(a)));
+
+        // NaN values compare equal, so equality stays reflexive.
+        let nan = InnerAtom::VScoreH(f64::NAN);
+        assert_eq!(nan, nan.clone());
+        assert_eq!(
+            vec![nan.clone(), InnerAtom::VScoreH(-f64::NAN)]
+                .into_iter()
+                .collect::<std::collections::HashSet<_>>()
+                .len(),
+            1
+        );
     }
 
     #[test]
     fn test_array_inner() {
         let mut panel = Panel::new();
         panel.push_spec("[5]R<5>(h3)").unwrap();
-        assert_eq!(panel.interior_geometry().len(), 25);
+        assert_eq!(panel.interior_geometry().len(), 35);
 
         use geo::bounding_rect::BoundingRect;
         let bounds = panel.edge_geometry().unwrap().bounding_rect().unwrap();
@@ -714,13 +2054,13 @@ mod tests {
             )),
         ));
 
-        for i in 0..5 {
+        for i in 0..7 {
             assert!(panel.interior_geometry()[i].bounds().unwrap().center().x < 3.01);
             assert!(panel.interior_geometry()[i].bounds().unwrap().center().x > 2.99);
             assert!(panel.interior_geometry()[i].bounds().unwrap().center().y < 2.01);
             assert!(panel.interior_geometry()[i].bounds().unwrap().center().y > -2.01);
         }
-        for i in 5..10 {
+        for i in 7..14 {
             assert!(panel.interior_geometry()[i].bounds().unwrap().center().x < 5.01);
             assert!(panel.interior_geometry()[i].bounds().unwrap().center().x > 4.99);
             assert!(panel.interior_geometry()[i].bounds().unwrap().center().y < 2.01);
@@ -728,6 +2068,37 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_atpos_optional() {
+        use geo::bounding_rect::BoundingRect;
+        let right = features::Positioning::Cardinal {
+            side: Direction::Right,
+            centerline_adjustment: 0.0,
+            align: Align::End,
+        };
+
+        let mut pos = features::AtPos::new_dyn(Box::new(features::Rect::with_center(
+            [0., 0.].into(),
+            2.,
+            2.,
+        )));
+        pos.with_optional_position(None, right.clone());
+        let bounds = pos.edge_union().unwrap().bounding_rect().unwrap();
+        assert!(bounds.width() > 1.99 && bounds.width() < 2.01);
+        assert_eq!(pos.interior().len(), 0);
+
+        pos.with_optional_position(
+            Some(Box::new(features::Rect::with_center(
+                [0., 0.].into(),
+                2.,
+                2.,
+            ))),
+            right,
+        );
+        let bounds = pos.edge_union().unwrap().bounding_rect().unwrap();
+        assert!(bounds.width() > 3.99 && bounds.width() < 4.01);
+    }
+
     #[test]
     fn test_atpos_angle() {
         let mut r = features::AtPos::<features::Rect, features::Rect>::new(
@@ -745,6 +2116,145 @@ mod tests {
         assert!(ig.contains(&geo::Coordinate::from([5.8, 3.8])));
     }
 
+    #[test]
+    fn test_atpos_absolute() {
+        use geo::bounding_rect::BoundingRect;
+        let mut r = features::AtPos::<features::Rect, features::Circle>::new(
+            features::Rect::with_center([20., 20.].into(), 4., 4.),
+        );
+        r.push(
+            features::Circle::wrap_with_radius(features::Unit, 1.),
+            features::Positioning::AbsoluteXY { x: 5., y: 5. },
+        );
+        let circle = r
+            .edge_union()
+            .unwrap()
+            .into_iter()
+            .map(|p| p.bounding_rect().unwrap())
+            .find(|b| b.width() < 2.01)
+            .unwrap();
+        assert!(circle.center().x > 4.99 && circle.center().x < 5.01);
+        assert!(circle.center().y > 4.99 && circle.center().y < 5.01);
+    }
+
+    #[test]
+    fn test_atpos_radial() {
+        use geo::bounding_rect::BoundingRect;
+        let mut r = features::AtPos::<features::Rect, features::Circle>::new(
+            features::Rect::with_center([0., 0.].into(), 2., 2.),
+        );
+        r.push_radial(
+            4,
+            5.,
+            features::Circle::wrap_with_radius(features::Unit, 1.),
+        );
+
+        let centers: Vec<_> = r
+            .edge_union()
+            .unwrap()
+            .into_iter()
+            .map(|p| p.bounding_rect().unwrap().center())
+            .filter(|c| c.x.abs() > 0.01 || c.y.abs() > 0.01)
+            .collect();
+        assert_eq!(centers.len(), 4);
+        for want in [(5., 0.), (0., 5.), (-5., 0.), (0., -5.)].iter() {
+            assert!(centers
+                .iter()
+                .any(|c| (c.x - want.0).abs() < 0.01 && (c.y - want.1).abs() < 0.01));
+        }
+    }
+
+    #[test]
+    fn test_atpos_stack() {
+        use geo::bounding_rect::BoundingRect;
+        let mut r = features::AtPos::<features::Rect, features::Circle>::new(
+            features::Rect::with_center([0., 0.].into(), 10., 10.),
+        );
+        for _ in 0..3 {
+            r.push(
+                features::Circle::wrap_with_radius(features::Unit, 2.5),
+                features::Positioning::Stack {
+                    side: Direction::Right,
+                    gap: 0.,
+                },
+            );
+        }
+        let bounds = r.edge_union().unwrap().bounding_rect().unwrap();
+        assert!(bounds.width() > 24.99 && bounds.width() < 25.01);
+        assert!(bounds.height() > 9.99 && bounds.height() < 10.01);
+
+        // A gap is left before each stacked feature, including the first.
+        let mut r = features::AtPos::<features::Rect, features::Circle<features::ScrewHole>>::new(
+            features::Rect::with_center([0., 0.].into(), 10., 10.),
+        );
+        for _ in 0..2 {
+            r.push(
+                features::Circle::wrap_with_radius(features::ScrewHole::default(), 2.5),
+                features::Positioning::Stack {
+                    side: Direction::Left,
+                    gap: 1.,
+                },
+            );
+        }
+        let drills: Vec<_> = r
+            .interior()
+            .into_iter()
+            .filter_map(|a| match a {
+                InnerAtom::Drill { center, .. } => Some(center.x),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(drills.len(), 2);
+        assert!((drills[0] + 8.5).abs() < 1e-9 && (drills[1] + 14.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_atpos_negative_primary() {
+        use geo::prelude::Contains;
+        let mut r = features::AtPos::new_dyn(Box::new(features::Negative::new(vec![
+            features::Rect::new([0., 0.].into(), [4., 4.].into()),
+        ])));
+        r.push(
+            Box::new(features::Negative::new(vec![features::Rect::with_center(
+                [0., 0.].into(),
+                1.,
+                1.,
+            )])),
+            features::Positioning::Cardinal {
+                side: Direction::Right,
+                centerline_adjustment: 0.0,
+                align: Align::Center,
+            },
+        );
+        r.push(
+            Box::new(
+                features::Rect::with_inner(features::ScrewHole::default())
+                    .bounds([0., 0.].into(), [1., 1.].into()),
+            ),
+            features::Positioning::Cardinal {
+                side: Direction::Left,
+                centerline_adjustment: 0.0,
+                align: Align::Center,
+            },
+        );
+
+        let sub = r.edge_subtract().unwrap();
+        assert!(sub.contains(&geo::Coordinate::from([4.4, 2.])));
+        assert!(!sub.contains(&geo::Coordinate::from([4.6, 2.])));
+
+        let drills: Vec<_> = r
+            .interior()
+            .into_iter()
+            .filter_map(|a| match a {
+                InnerAtom::Drill { center, .. } => Some(center),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(drills.len(), 1);
+        assert!(drills[0].x > -0.01 && drills[0].x < 0.01);
+        assert!(drills[0].y > 1.99 && drills[0].y < 2.01);
+    }
+
     #[test]
     fn test_atpos_corner() {
         let mut r = features::AtPos::<features::Rect, features::Rect>::new(
@@ -852,4 +2362,1603 @@ mod tests {
         assert!(infos.len() == 3 && infos[1].name == "inner1" && infos[1].bounds.min().x < 1.5001);
         assert!(infos.len() == 3 && infos[2].name == "rect" && infos[2].bounds.min().x < -3.4999);
     }
+
+    #[test]
+    #[cfg(feature = "text")]
+    fn test_grid_labels_within_viewbox() {
+        let mut panel = Panel::new();
+        panel.push_spec("R<@(45, 45), 110, 110>").unwrap();
+        panel.set_grid_separation(Some(10));
+
+        let tree = panel.make_svg().unwrap();
+        let vb = tree.svg_node().view_box.rect;
+        let mut labels = 0;
+        for node in tree.root().descendants() {
+            if let usvg::NodeKind::Image(ref img) = *node.borrow() {
+                // The image is scaled to fit its view box, so the glyphs
+                // fill it exactly when their aspect ratios match.
+                let (w, h) = match img.kind {
+                    usvg::ImageKind::PNG(ref data) => (
+                        u32::from_be_bytes([data[16], data[17], data[18], data[19]]) as f64,
+                        u32::from_be_bytes([data[20], data[21], data[22], data[23]]) as f64,
+                    ),
+                    _ => unreachable!(),
+                };
+                let r = img.view_box.rect;
+                assert!((r.width() / r.height() - w / h).abs() < 1e-9);
+
+                let (x, y) = (img.transform.e + r.x(), img.transform.f + r.y());
+                assert!(x >= vb.x() && x + r.width() <= vb.right());
+                assert!(y >= vb.y() && y + r.height() <= vb.bottom());
+                labels += 1;
+            }
+        }
+        assert!(labels > 0);
+    }
+
+    #[test]
+    fn test_err_display() {
+        let errs = vec![
+            Err::NoFeatures,
+            Err::NoBounds,
+            Err::BadEdgeGeometry("multiple polygons".to_string()),
+            Err::InternalGerberFailure,
+        ];
+        for e in errs {
+            assert!(!format!("{}", e).is_empty());
+        }
+
+        assert_eq!(format!("{}", Err::NoFeatures), "no features were provided");
+        assert_eq!(
+            format!("{}", Err::BadEdgeGeometry("multiple polygons".to_string())),
+            "bad edge geometry: multiple polygons"
+        );
+
+        let boxed: Box<dyn std::error::Error> = Box::new(Err::NoBounds);
+        assert!(boxed.source().is_none());
+    }
+
+    #[test]
+    fn test_feature_queries() {
+        let mut panel = Panel::new();
+        panel.push(features::Rect::new([0., 0.].into(), [5., 5.].into()));
+        panel.push(features::Rect::new([10., 0.].into(), [15., 5.].into()));
+
+        assert_eq!(panel.feature_at_point([2., 2.].into()), vec![0]);
+        assert_eq!(panel.feature_at_point([12., 2.].into()), vec![1]);
+        assert_eq!(panel.feature_at_point([7., 2.].into()), Vec::<usize>::new());
+
+        let sel = geo::Rect::new::<Coordinate<f64>>([4., 1.].into(), [11., 2.].into());
+        assert_eq!(panel.features_overlapping_rect_approx(sel), vec![0, 1]);
+        assert_eq!(panel.features_overlapping_rect_exact(sel), vec![0, 1]);
+        let sel = geo::Rect::new::<Coordinate<f64>>([6., 1.].into(), [9., 2.].into());
+        assert_eq!(
+            panel.features_overlapping_rect_exact(sel),
+            Vec::<usize>::new()
+        );
+    }
+
+    #[test]
+    fn test_gerber_header() {
+        let mut panel = Panel::new();
+        panel.push_spec("R<5>(h)").unwrap();
+
+        let mut edges = Vec::new();
+        panel.serialize_gerber_edges(&mut edges).unwrap();
+        let edges = String::from_utf8(edges).unwrap();
+        assert!(edges
+            .lines()
+            .next()
+            .unwrap()
+            .starts_with("G04 Generated by"));
+        assert!(edges.contains("G04 Layer: Edge"));
+
+        let mut copper = Vec::new();
+        panel
+            .serialize_gerber_layer(Layer::FrontCopper, &mut copper)
+            .unwrap();
+        let copper = String::from_utf8(copper).unwrap();
+        assert!(copper
+            .lines()
+            .next()
+            .unwrap()
+            .starts_with("G04 Generated by"));
+        assert!(copper.contains("G04 Layer: FrontCopper"));
+    }
+
+    #[test]
+    fn test_rotate_all() {
+        let mut panel = Panel::new();
+        panel.push(features::Rect::with_center([0., 0.].into(), 5., 10.));
+        panel.add_interior_atom(InnerAtom::Drill {
+            center: [0., 3.].into(),
+            radius: 0.5,
+            plated: false,
+        });
+        panel.rotate_all(90.);
+        assert_eq!(panel.features.len(), 1);
+
+        use geo::bounding_rect::BoundingRect;
+        let bounds = panel.edge_geometry().unwrap().bounding_rect().unwrap();
+        assert!(bounds.width() > 9.99 && bounds.width() < 10.01);
+        assert!(bounds.height() > 4.99 && bounds.height() < 5.01);
+        assert!(panel.interior_geometry().iter().any(|a| matches!(
+            a,
+            InnerAtom::Drill { center, .. } if (center.x.abs() - 3.).abs() < 1e-9 && center.y.abs() < 1e-9
+        )));
+    }
+
+    #[test]
+    fn test_drill_edge_distance() {
+        let mut panel = Panel::new();
+        assert_eq!(panel.drill_to_edge_min_distance(), None);
+
+        panel.push(features::Rect::new([0., -5.].into(), [10., 5.].into()));
+        assert_eq!(panel.drill_to_edge_min_distance(), None);
+
+        panel.push(
+            features::Rect::with_inner(features::ScrewHole::with_diameter(0.1))
+                .bounds([0., -0.1].into(), [0.2, 0.1].into()),
+        );
+        let dist = panel.drill_to_edge_min_distance().unwrap();
+        assert!(dist > 0.099 && dist < 0.101);
+
+        assert_eq!(panel.drills_violating_edge_clearance(0.05).len(), 0);
+        let violations = panel.drills_violating_edge_clearance(0.25);
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].x > 0.099 && violations[0].x < 0.101);
+    }
+
+    #[test]
+    fn test_pick_and_place() {
+        let mut panel = Panel::new();
+        panel
+            .push_spec("R<@(5, 5), 10, 10>\nR<@(2, 3), 2> % U1\nR<@(8, 8), 1> % edge")
+            .unwrap();
+
+        let mut out = Vec::new();
+        panel.serialize_pick_and_place(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let lines: Vec<_> = out.lines().collect();
+        assert_eq!(lines[0], "Reference,X,Y,Rotation,Side");
+        assert_eq!(lines[1], "U1,2.0000,3.0000,0.0000,front");
+        assert_eq!(lines.len(), 2);
+    }
+
+    #[test]
+    fn test_layer_svg_paths() {
+        let mut panel = Panel::new();
+        panel.push_spec("[2]R<10>(h)").unwrap();
+
+        let paths = panel.layer_svg_paths(Layer::FrontCopper).unwrap();
+        assert_eq!(paths.len(), 2);
+        assert_eq!(panel.layer_svg_paths(Layer::FrontLegend).unwrap().len(), 0);
+
+        let svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"-5 -5 20 10\">{}</svg>",
+            paths
+                .iter()
+                .map(|d| format!("<path d=\"{}\"/>", d))
+                .collect::<String>()
+        );
+        let tree = usvg::Tree::from_str(&svg, &usvg::Options::default()).unwrap();
+        let rendered = tree
+            .root()
+            .descendants()
+            .filter(|n| match &*n.borrow() {
+                usvg::NodeKind::Path(p) => p.data.len() > 1,
+                _ => false,
+            })
+            .count();
+        assert_eq!(rendered, paths.len());
+    }
+
+    #[test]
+    fn test_has_layer() {
+        let mut panel = Panel::new();
+        panel.push(features::Rect::with_inner(features::ScrewHole::default()));
+
+        assert!(panel.has_layer(Layer::FrontCopper));
+        assert!(panel.has_layer(Layer::BackMask));
+        assert!(!panel.has_layer(Layer::FrontLegend));
+        assert!(!panel.has_layer(Layer::FabricationInstructions));
+        assert_eq!(
+            panel.non_empty_layers(),
+            vec![
+                Layer::FrontCopper,
+                Layer::FrontMask,
+                Layer::BackCopper,
+                Layer::BackMask,
+                Layer::CourtyardFront,
+                Layer::CourtyardBack
+            ]
+        );
+
+        let mut panel = Panel::new();
+        panel.push_spec("[2; R; v-score]R<5>").unwrap();
+        assert!(panel.has_layer(Layer::FabricationInstructions));
+    }
+
+    #[test]
+    fn test_bom() {
+        let mut panel = Panel::new();
+        panel.push_spec("[5]R<5>(h)\nR<@(20, 2), 2> % U1").unwrap();
+
+        let mut out = Vec::new();
+        panel.serialize_bom(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let lines: Vec<_> = out.lines().collect();
+        assert_eq!(lines[0], "Reference,Type,X,Y,Quantity");
+        assert_eq!(lines[1], "U1,rect,20.0000,2.0000,1");
+        assert!(lines.contains(&",screw_hole,,,5"));
+        assert!(lines.contains(&",rect,,,5"));
+    }
+
+    #[test]
+    #[cfg(feature = "render")]
+    fn test_make_png_bytes() {
+        let mut panel = Panel::new();
+        panel.push_spec("R<10>(h)").unwrap();
+
+        let png = panel.make_png_bytes(64).unwrap();
+        assert!(png.starts_with(b"\x89PNG"));
+    }
+
+    #[test]
+    #[cfg(feature = "render")]
+    fn test_svg_holes_unfilled() {
+        let mut panel = Panel::new();
+        panel.push(features::Circle::new([0., 0.].into(), 5.));
+        panel.push(features::Negative::new(vec![features::Circle::new(
+            [0., 0.].into(),
+            2.,
+        )]));
+
+        let tree = panel.make_svg().unwrap();
+        let img = resvg::render_node(
+            &tree.root(),
+            usvg::FitTo::Width(100),
+            Some(usvg::Color::white()),
+        )
+        .unwrap();
+        let pixel = |x: usize, y: usize| {
+            let idx = (y * img.width() as usize + x) * 4;
+            (img.data()[idx], img.data()[idx + 1], img.data()[idx + 2])
+        };
+        assert_eq!(pixel(50, 50), (0xff, 0xff, 0xff));
+        assert_eq!(pixel(15, 50), (0xf2, 0xec, 0xd6));
+    }
+
+    #[test]
+    fn test_feature_type_counts() {
+        let mut panel = Panel::new();
+        panel
+            .push_spec("R<5>\nR<@(5, 0), 5>\nC<@(10, 0), 3>")
+            .unwrap();
+
+        let counts = panel.feature_type_counts();
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts["rect"], 2);
+        assert_eq!(counts["circle"], 1);
+
+        let mut panel = Panel::new();
+        panel.push_spec("[3]R<5>").unwrap();
+        let counts = panel.feature_type_counts();
+        assert_eq!(counts["repeating::Tile"], 1);
+        assert_eq!(counts["rect"], 3);
+
+        let mut panel = Panel::new();
+        panel.push_spec("column center { R<5> C<2> }").unwrap();
+        let counts = panel.feature_type_counts();
+        assert_eq!(counts.len(), 3);
+        assert_eq!(counts["Column"], 1);
+        assert_eq!(counts["rect"], 1);
+        assert_eq!(counts["circle"], 1);
+    }
+
+    #[test]
+    fn test_centroid() {
+        use geo::bounding_rect::BoundingRect;
+        let mut panel = Panel::new();
+        panel.push(features::Rect::new([0., 0.].into(), [10., 2.].into()));
+        panel.push(features::Rect::new([0., 0.].into(), [2., 10.].into()));
+        let centroid = panel.centroid().unwrap();
+        let center = panel
+            .edge_geometry()
+            .unwrap()
+            .bounding_rect()
+            .unwrap()
+            .center();
+        assert!(centroid.x < center.x - 1. && centroid.y < center.y - 1.);
+
+        let mut panel = Panel::new();
+        panel.push(features::Rect::new([0., 0.].into(), [10., 4.].into()));
+        let centroid = panel.centroid().unwrap();
+        assert!(centroid.x > 4.99 && centroid.x < 5.01);
+        assert!(centroid.y > 1.99 && centroid.y < 2.01);
+
+        panel.push(features::Negative::new(vec![features::Rect::new(
+            [6., 1.].into(),
+            [8., 3.].into(),
+        )]));
+        let centroid = panel.centroid().unwrap();
+        assert!(centroid.x > 4.99 && centroid.x < 5.01);
+        let centroid = panel.centroid_with_holes().unwrap();
+        assert!(centroid.x < 4.9);
+        assert!(centroid.y > 1.99 && centroid.y < 2.01);
+    }
+
+    #[test]
+    fn test_interior_atoms_in_rect() {
+        let mut panel = Panel::new();
+        for i in 0..4 {
+            let x = i as f64 * 5.;
+            panel.push(
+                features::Rect::with_inner(features::ScrewHole::with_diameter(1.))
+                    .bounds([x, 0.].into(), [x + 5., 5.].into()),
+            );
+        }
+
+        let atoms = panel.interior_atoms_in_rect(geo::Rect::new([0., -5.], [10., 5.]));
+        let drills: Vec<_> = atoms
+            .iter()
+            .filter_map(|a| match a {
+                InnerAtom::Drill { center, .. } => Some(center),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(drills.len(), 2);
+        assert!(drills.iter().all(|c| c.x < 10.));
+        assert!(drills.iter().all(|c| c.y > 7.49 && c.y < 7.51));
+    }
+
+    #[test]
+    fn test_slice_horizontal() {
+        use geo::bounding_rect::BoundingRect;
+        let mut panel = Panel::new();
+        panel.push_spec("[2; D; v-score]R<10, 5>(h)").unwrap();
+        let bounds = panel.edge_geometry().unwrap().bounding_rect().unwrap();
+        assert!(bounds.height() > 9.99 && bounds.height() < 10.01);
+        let cut = bounds.center().y;
+
+        let (top, bottom) = panel.slice_horizontal(cut);
+        for (half, above) in vec![(top, true), (bottom, false)] {
+            let b = half.edge_geometry().unwrap().bounding_rect().unwrap();
+            assert!(b.height() > 4.99 && b.height() < 5.01);
+
+            let drills: Vec<_> = half
+                .interior_geometry()
+                .into_iter()
+                .filter_map(|a| match a {
+                    InnerAtom::Drill { center, .. } => Some(center),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(drills.len(), 1);
+            assert_eq!(drills[0].y < cut, above);
+            assert!(!half
+                .interior_geometry()
+                .iter()
+                .any(|a| matches!(a, InnerAtom::VScoreH(_))));
+        }
+    }
+
+    #[test]
+    fn test_generate_v_scores() {
+        let mut panel = Panel::new();
+        panel.push(features::repeating::Tile::new(
+            features::Rect::new([0., 0.].into(), [5., 5.].into()),
+            Direction::Right,
+            3,
+        ));
+        assert_eq!(panel.interior_geometry().len(), 0);
+
+        panel.generate_v_scores_from_tile_boundaries();
+        let scores: Vec<_> = panel
+            .interior_geometry()
+            .into_iter()
+            .filter_map(|a| match a {
+                InnerAtom::VScoreV(x) => Some(x),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(scores.len(), 2);
+        assert!(scores[0] > 4.99 && scores[0] < 5.01);
+        assert!(scores[1] > 9.99 && scores[1] < 10.01);
+    }
+
+    #[test]
+    fn test_ipc_d356() {
+        let mut panel = Panel::new();
+        panel.push_spec("[2]R<5>(h)").unwrap();
+        let mut buf = Vec::new();
+        panel.export_ipc_d356(&mut buf).unwrap();
+        let out = String::from_utf8(buf).unwrap();
+
+        assert!(out.starts_with("C  IPC-D-356"));
+        assert_eq!(out.lines().last(), Some("999"));
+        let drills: Vec<_> = out.lines().filter(|l| l.starts_with("317")).collect();
+        assert_eq!(drills.len(), 2);
+        for d in drills {
+            // 3.1mm plated hole, accessible from both sides.
+            assert_eq!(&d[32..41], "D0122PA00");
+        }
+    }
+
+    #[test]
+    fn test_excellon_v2() {
+        let mut panel = Panel::new();
+        panel.push_spec("[2]R<5>(h)").unwrap();
+        panel.push(
+            features::Rect::with_inner(features::ScrewHole::with_diameter(1.))
+                .bounds([0., 5.].into(), [5., 10.].into()),
+        );
+        let mut buf = Vec::new();
+        panel.serialize_excellon_v2(&mut buf, true).unwrap();
+        let out = String::from_utf8(buf).unwrap();
+        let lines: Vec<_> = out.lines().collect();
+
+        assert_eq!(lines[0], "M48");
+        assert_eq!(lines[2], "FMAT,2");
+        assert_eq!(lines[3], "METRIC,TZ");
+        assert!(lines.contains(&"T1C1.000"));
+        assert!(lines.contains(&"T2C3.100"));
+        assert!(out.contains("Holesize 2 = 3.100 Tolerance = +0.050/-0.050 PLATED MM Quantity = 2"));
+
+        let t1 = lines.iter().position(|l| *l == "T1").unwrap();
+        let t2 = lines.iter().position(|l| *l == "T2").unwrap();
+        assert_eq!(t2 - t1, 2);
+        assert_eq!(lines[t1 + 1], "X2.500Y7.500");
+        assert_eq!(lines.last(), Some(&"M30"));
+
+        let mut buf = Vec::new();
+        panel.serialize_excellon_v2(&mut buf, false).unwrap();
+        assert!(!String::from_utf8(buf).unwrap().contains("T1C"));
+    }
+
+    #[test]
+    fn test_feature_named_mut() {
+        let mut panel = Panel::new();
+        panel.push(features::Rect::new([0., 0.].into(), [10., 10.].into()));
+        panel.push_spec("R<@(5, 5), 2> % mount_hole").unwrap();
+        assert!(panel.feature_named_mut("nope").is_none());
+
+        panel
+            .feature_named_mut("mount_hole")
+            .unwrap()
+            .translate([2., 1.].into());
+        let info = panel.named_info();
+        assert_eq!(info.len(), 1);
+        assert!(info[0].bounds.center().x > 6.99 && info[0].bounds.center().x < 7.01);
+        assert!(info[0].bounds.center().y > 5.99 && info[0].bounds.center().y < 6.01);
+    }
+
+    #[test]
+    fn test_wkt() {
+        let mut panel = Panel::new();
+        assert_eq!(panel.to_wkt(), None);
+        panel.push(features::Rect::new([0., 0.].into(), [2., 2.].into()));
+        let wkt = panel.to_wkt().unwrap();
+        assert!(wkt.starts_with("MULTIPOLYGON (((") && wkt.ends_with(")))"));
+        for pt in &["0 0", "2 0", "2 2", "0 2"] {
+            assert!(wkt.contains(pt));
+        }
+
+        let panel = Panel::from_wkt("MULTIPOLYGON (((0 0, 2 0, 2 2, 0 2, 0 0)))").unwrap();
+        assert_eq!(
+            panel.to_wkt().unwrap(),
+            "MULTIPOLYGON (((0 0, 2 0, 2 2, 0 2, 0 0)))"
+        );
+        assert!(matches!(
+            Panel::from_wkt("LINESTRING (0 0, 1 1)"),
+            Err(Err::BadWKT(_))
+        ));
+    }
+
+    #[test]
+    fn test_default_layer() {
+        let mut panel = Panel::new();
+        assert_eq!(panel.default_layer(), Layer::FrontCopper);
+        panel.push_spec("C<3>(msp)").unwrap();
+        assert!(panel.has_layer(Layer::FrontCopper) && panel.has_layer(Layer::BackCopper));
+
+        let mut panel = Panel::new();
+        panel.set_default_layer(Layer::BackCopper);
+        assert_eq!(panel.default_layer(), Layer::BackCopper);
+        panel.push_spec("C<3>(msp)").unwrap();
+        assert!(panel.has_layer(Layer::BackCopper));
+        assert!(!panel.has_layer(Layer::FrontCopper));
+    }
+
+    #[test]
+    fn test_iter_drills() {
+        let mut panel = Panel::new();
+        panel.push_spec("[3]R<5>(h)").unwrap();
+        panel.push_spec("R<@(0, 10), 2>(msp<1, 1, npth>)").unwrap();
+        assert_eq!(panel.iter_drills().count(), 4);
+        assert_eq!(panel.iter_drills_plated().count(), 3);
+        assert_eq!(panel.iter_drills_npth().count(), 1);
+        assert!(panel
+            .iter_drills_plated()
+            .all(|d| d.diameter > 3.09 && d.diameter < 3.11));
+
+        assert_eq!(panel.iter_copper_circles(Layer::FrontCopper).count(), 3);
+        assert_eq!(panel.iter_copper_rects(Layer::FrontCopper).count(), 0);
+    }
+
+    #[test]
+    fn test_outline_length() {
+        let mut panel = Panel::new();
+        assert_eq!(panel.outline_length(), None);
+        panel.push(features::Rect::new([0., 0.].into(), [10., 10.].into()));
+        assert_eq!(panel.outline_length(), Some(40.));
+        assert_eq!(panel.outline_with_holes_length(), Some(40.));
+
+        panel.push(features::Negative::new(vec![features::Rect::new(
+            [2., 2.].into(),
+            [4., 4.].into(),
+        )]));
+        assert_eq!(panel.outline_length(), Some(40.));
+        let l = panel.outline_with_holes_length().unwrap();
+        assert!(l > 47.99 && l < 48.01);
+
+        let mut panel = Panel::new();
+        panel.push(features::Circle::new([0., 0.].into(), 5.));
+        let want = 2. * std::f64::consts::PI * 5.;
+        let l = panel.outline_length().unwrap();
+        assert!((l - want).abs() / want < 0.001);
+    }
+
+    #[test]
+    fn test_colors_rgb() {
+        assert_eq!(Layer::FrontCopper.rgb(), (0x84, 0x00, 0x00));
+        let atom = InnerAtom::Rect {
+            rect: geo::Rect::new([0., 0.], [1., 1.]),
+            layer: Layer::BackCopper,
+        };
+        assert_eq!(atom.fill_color_rgb(), Some(Layer::BackCopper.rgb()));
+        assert_eq!(atom.stroke_color_rgb(), None);
+        assert_eq!(InnerAtom::VScoreH(1.).fill_color_rgb(), None);
+        assert_eq!(InnerAtom::VScoreH(1.).stroke_color_rgb(), Some((6, 6, 6)));
+    }
+
+    #[test]
+    fn test_drill_report() {
+        let mut panel = Panel::new();
+        panel.push_spec("[5]R<5>(h)").unwrap();
+        panel.push_spec("R<@(0, 10), 2>(drill<1.5>)").unwrap();
+        panel.push_spec("R<@(5, 10), 2>(drill<1.5>)").unwrap();
+
+        let mut buf = Vec::new();
+        panel.serialize_drill_report(&mut buf, true).unwrap();
+        let out = String::from_utf8(buf).unwrap();
+        assert_eq!(
+            out,
+            "Diameter: 1.500mm, Count: 2, Type: PTH, Length: n/a\n\
+             Diameter: 3.100mm, Count: 5, Type: PTH, Length: n/a\n\
+             Total: 7 drills in 2 sizes\n"
+        );
+
+        // Slots are counted by width, along with the length they route.
+        let mut panel = Panel::new();
+        panel.push_spec("R<@(0, 0), 2>(drill<1.5>)").unwrap();
+        panel.add_interior_atom(InnerAtom::Slot {
+            start: [0., 0.].into(),
+            end: [3., 4.].into(),
+            width: 1.,
+            plated: true,
+        });
+        let mut buf = Vec::new();
+        panel.serialize_drill_report(&mut buf, true).unwrap();
+        let out = String::from_utf8(buf).unwrap();
+        assert_eq!(
+            out,
+            "Diameter: 1.000mm, Count: 1, Type: PTH, Length: 5.000mm\n\
+             Diameter: 1.500mm, Count: 1, Type: PTH, Length: n/a\n\
+             Total: 2 drills in 2 sizes\n"
+        );
+        assert_eq!(panel.drill_summary().len(), 2);
+    }
+
+    #[test]
+    fn test_manual_v_scores() {
+        let mut panel = Panel::new();
+        panel
+            .push_spec("column center { R<15, 5> R<15, 5> R<15, 5> }")
+            .unwrap();
+        assert!(!panel.has_fab_markings());
+
+        panel.with_v_scores_at(&[5., 10.], &[]);
+        let scores: Vec<_> = panel
+            .interior_geometry()
+            .into_iter()
+            .filter_map(|a| match a {
+                InnerAtom::VScoreH(y) => Some(y),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(scores, vec![5., 10.]);
+        assert!(panel.has_fab_markings());
+    }
+
+    #[test]
+    fn test_snap_to_grid() {
+        assert_eq!(snap_coordinate(1.3, 0.5), 1.5);
+        assert_eq!(snap_coordinate(-0.2, 0.5), 0.);
+
+        let mut panel = Panel::new();
+        panel.push(features::Rect::with_center([1.3, 2.7].into(), 2., 2.));
+        panel.snap_to_grid(0.5);
+
+        let c = panel.centroid().unwrap();
+        assert!(c.x > 1.49 && c.x < 1.51);
+        assert!(c.y > 2.49 && c.y < 2.51);
+    }
+
+    #[test]
+    fn test_cost_estimate() {
+        let tariff = ManufacturingTariff::jlcpcb_2024();
+        let panel = Panel::new();
+        let cost = panel.compute_manufacturing_cost_estimate(&tariff).unwrap();
+        assert_eq!(cost.total, 0.);
+
+        let mut panel = Panel::new();
+        panel.push(
+            features::Rect::with_inner(features::ScrewHole::default())
+                .bounds([0., 0.].into(), [5., 5.].into()),
+        );
+        let cost = panel.compute_manufacturing_cost_estimate(&tariff).unwrap();
+        assert!(cost.drills > 0.);
+        assert!(cost.total > 0.);
+    }
+
+    #[test]
+    fn test_expand_to_minimum_size() {
+        use geo::bounding_rect::BoundingRect;
+        let mut panel = Panel::new();
+        panel.push(features::Rect::new([0., 0.].into(), [10., 5.].into()));
+        panel.expand_to_minimum_size(20., 20.);
+
+        let b = panel.edge_geometry().unwrap().bounding_rect().unwrap();
+        assert!(b.width() > 19.99 && b.width() < 20.01);
+        assert!(b.height() > 19.99 && b.height() < 20.01);
+        assert!(b.center().x > 4.99 && b.center().x < 5.01);
+        assert!(b.center().y > 2.49 && b.center().y < 2.51);
+        assert_eq!(panel.interior_geometry().len(), 0);
+    }
+
+    #[test]
+    fn test_expand_to_aspect_ratio() {
+        use geo::bounding_rect::BoundingRect;
+        let mut panel = Panel::new();
+        panel.push(features::Rect::new([0., 0.].into(), [10., 5.].into()));
+        panel.expand_to_aspect_ratio(1.);
+
+        let b = panel.edge_geometry().unwrap().bounding_rect().unwrap();
+        assert!(b.width() > 9.99 && b.width() < 10.01);
+        assert!(b.height() > 9.99 && b.height() < 10.01);
+    }
+
+    #[test]
+    fn test_inner_atom_scale() {
+        let mut a = InnerAtom::Drill {
+            center: [2., 3.].into(),
+            radius: 1.,
+            plated: false,
+        };
+        a.scale_uniform(2.);
+        assert!(matches!(a, InnerAtom::Drill { center, radius, .. }
+            if center == Coordinate { x: 4., y: 6. } && radius == 2.));
+
+        let mut a = InnerAtom::Rect {
+            rect: geo::Rect::new([1., 1.], [2., 3.]),
+            layer: Layer::FrontCopper,
+        };
+        a.scale(2., -1.);
+        assert_eq!(a.bounds(), Some(geo::Rect::new([2., -3.], [4., -1.])));
+    }
+
+    #[test]
+    #[cfg(feature = "compress")]
+    fn test_svgz_bytes() {
+        use std::io::Read;
+        let mut panel = Panel::new();
+        panel.push(features::Rect::new([0., 0.].into(), [5., 5.].into()));
+
+        let svg = panel.to_svg_bytes().unwrap();
+        assert!(String::from_utf8_lossy(&svg).contains("<svg"));
+
+        let svgz = panel.to_svgz_bytes().unwrap();
+        assert_eq!(&svgz[0..4], b"\x1f\x8b\x08\x00");
+
+        let mut out = Vec::new();
+        flate2::read::GzDecoder::new(&svgz[..])
+            .read_to_end(&mut out)
+            .unwrap();
+        assert_eq!(out, svg);
+    }
+
+    #[test]
+    fn test_drill_clearance_matrix() {
+        let mut panel = Panel::new();
+        for (x, y) in &[(0., 0.), (3., 4.), (6., 0.)] {
+            panel.add_interior_atom(InnerAtom::Drill {
+                center: [*x, *y].into(),
+                radius: 0.5,
+                plated: false,
+            });
+        }
+
+        let m = panel.drill_clearance_matrix();
+        assert_eq!(m.len(), 3);
+        assert_eq!(m[0][0], 0.);
+        assert_eq!(m[0][1], 5.);
+        assert_eq!(m[1][2], 5.);
+        assert_eq!(m[2][0], 6.);
+        assert_eq!(panel.minimum_drill_to_drill_clearance(), Some(5.));
+        assert_eq!(Panel::new().minimum_drill_to_drill_clearance(), None);
+    }
+
+    #[test]
+    fn test_rounded_rect_spec() {
+        let mut panel = Panel::new();
+        panel.push_spec("R<5, round = 1>").unwrap();
+        let edges = panel.edge_geometry().unwrap();
+        assert!(edges.0[0].exterior().0.len() > 5);
+    }
+
+    #[test]
+    fn test_row_layout() {
+        use geo::bounding_rect::BoundingRect;
+        let mut panel = Panel::new();
+        panel.push_spec("row center { R<5> R<3> }").unwrap();
+
+        let b = panel.edge_geometry().unwrap().bounding_rect().unwrap();
+        assert!(b.width() > 7.99 && b.width() < 8.01);
+        assert!(b.height() > 4.99 && b.height() < 5.01);
+    }
+
+    #[test]
+    fn test_grid_spec() {
+        use geo::bounding_rect::BoundingRect;
+        let mut panel = Panel::new();
+        panel.push_spec("[3x4; gap=1.0]R<5>(h)").unwrap();
+
+        let b = panel.edge_geometry().unwrap().bounding_rect().unwrap();
+        assert!(b.width() > 22.99 && b.width() < 23.01);
+        assert!(b.height() > 16.99 && b.height() < 17.01);
+        assert_eq!(panel.iter_drills().count(), 12);
+    }
+
+    #[test]
+    fn test_rotate_interior() {
+        let mut before = Panel::new();
+        before.push_spec("[5]R<5>(h3)").unwrap();
+        let mut after = Panel::new();
+        after.push_spec("rotate(90) { [5]R<5>(h3) }").unwrap();
+
+        let pivot = before.centroid().unwrap();
+        let (before, after): (Vec<_>, Vec<_>) = (
+            before.iter_drills().collect(),
+            after.iter_drills().collect(),
+        );
+        assert_eq!(after.len(), 5);
+
+        for (b, a) in before.iter().zip(after.iter()) {
+            let (bx, by) = (b.center.x - pivot.x, b.center.y - pivot.y);
+            let (ax, ay) = (a.center.x - pivot.x, a.center.y - pivot.y);
+            // Perpendicular, and the same distance from the pivot.
+            assert!((bx * ax + by * ay).abs() < 1e-6);
+            assert!(((bx * bx + by * by) - (ax * ax + ay * ay)).abs() < 1e-6);
+        }
+
+        // Rectangles become the bounds of all four rotated corners.
+        let mut panel = Panel::new();
+        panel
+            .push_spec("rotate(45) { R<10>(pad<@(0,0), 2, 2>) }")
+            .unwrap();
+        let rect = panel
+            .interior_geometry()
+            .into_iter()
+            .find_map(|a| match a {
+                InnerAtom::Rect {
+                    rect,
+                    layer: Layer::FrontCopper,
+                } => Some(rect),
+                _ => None,
+            })
+            .unwrap();
+        let diag = 2. * std::f64::consts::SQRT_2;
+        assert!((rect.width() - diag).abs() < 1e-6);
+        assert!((rect.height() - diag).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_line_gerber() {
+        let mut panel = Panel::new();
+        panel
+            .push_spec("R<10>(line<@(-2,0), @(2,0), width=0.2>)")
+            .unwrap();
+
+        let atoms = panel.interior_geometry_layer(Layer::FrontLegend);
+        assert_eq!(atoms.len(), 1);
+        let b = atoms[0].bounds().unwrap();
+        assert!(b.width() > 4.19 && b.width() < 4.21);
+        assert!(b.height() > 0.19 && b.height() < 0.21);
+
+        let mut legend = Vec::new();
+        panel
+            .serialize_gerber_layer(Layer::FrontLegend, &mut legend)
+            .unwrap();
+        let legend = String::from_utf8(legend).unwrap();
+        assert!(legend.contains("D02*"));
+        assert!(legend.contains("D01*"));
+    }
+
+    #[test]
+    fn test_slot_drill() {
+        let mut panel = Panel::new();
+        panel.push(
+            features::Rect::with_inner(features::PlatedSlot::new(
+                [-1., 0.].into(),
+                [1., 0.].into(),
+                0.8,
+            ))
+            .bounds([0., 0.].into(), [10., 10.].into()),
+        );
+
+        let mut buf = Vec::new();
+        panel
+            .serialize_drill(&mut buf, true, DrillUnits::Inches)
+            .unwrap();
+        let out = String::from_utf8(buf).unwrap();
+        assert!(out.lines().any(|l| l == "X0.1575Y0.1969G85X0.2362Y0.1969"));
+
+        let mut buf = Vec::new();
+        panel
+            .serialize_drill_with_slots(&mut buf, true, SlotFormat::Routed, DrillUnits::Inches)
+            .unwrap();
+        let out = String::from_utf8(buf).unwrap();
+        assert!(!out.contains("G85"));
+        assert!(out.contains("G00X0.1575Y0.1969\nM15\nG01X0.2362Y0.1969\nM16\n"));
+
+        let mut buf = Vec::new();
+        panel.serialize_excellon_v2(&mut buf, true).unwrap();
+        let out = String::from_utf8(buf).unwrap();
+        assert!(out.contains("T1C0.800"));
+        assert!(out.lines().any(|l| l == "X4.000Y5.000G85X6.000Y5.000"));
+
+        let mut buf = Vec::new();
+        panel
+            .serialize_drill(&mut buf, false, DrillUnits::Inches)
+            .unwrap();
+        assert!(!String::from_utf8(buf).unwrap().contains("G85"));
+    }
+
+    #[test]
+    fn test_slot_spec() {
+        let mut panel = Panel::new();
+        panel
+            .push_spec("R<10>(slot<@(-1,0), @(1,0), 0.8>)")
+            .unwrap();
+        let atoms = panel.interior_geometry();
+        assert_eq!(atoms.len(), 1);
+        assert!(
+            matches!(atoms[0], InnerAtom::Slot { width, plated: true, .. } if width > 0.79 && width < 0.81)
+        );
+        let b = atoms[0].bounds().unwrap();
+        assert!(b.width() > 2.79 && b.width() < 2.81);
+        assert!(b.height() > 0.79 && b.height() < 0.81);
+    }
+
+    #[test]
+    fn test_bounding_box() {
+        let mut panel = Panel::new();
+        assert!(panel.bounding_box().is_none());
+        assert!(panel.width().is_none());
+
+        panel.push_spec("[3]R<@(1, 2), 5, 4>").unwrap();
+        let b = panel.bounding_box().unwrap();
+        assert!(b.min().x > -1.51 && b.min().x < -1.49);
+        assert!(b.min().y > -0.01 && b.min().y < 0.01);
+        assert!(panel.width().unwrap() > 14.99 && panel.width().unwrap() < 15.01);
+        assert!(panel.height().unwrap() > 3.99 && panel.height().unwrap() < 4.01);
+    }
+
+    #[test]
+    fn test_drill_summary() {
+        let mut panel = Panel::new();
+        assert_eq!(panel.drill_summary(), vec![]);
+
+        panel
+            .push_spec("[3]R<5>(h)\nR<@(20, 0), 5>(drill<1, npth>)\nR<@(30, 0), 5>(h1)")
+            .unwrap();
+        assert_eq!(
+            panel.drill_summary(),
+            vec![
+                DrillSize {
+                    diameter_mm: 1.,
+                    count: 1,
+                    plated: false,
+                },
+                DrillSize {
+                    diameter_mm: 1.,
+                    count: 1,
+                    plated: true,
+                },
+                DrillSize {
+                    diameter_mm: 3.1,
+                    count: 3,
+                    plated: true,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let rules = DesignRules::default();
+        let panel_with = |atoms: Vec<InnerAtom>| {
+            let mut panel = Panel::new();
+            panel.push(features::Rect::new([0., 0.].into(), [5., 5.].into()));
+            for a in atoms {
+                panel.add_interior_atom(a);
+            }
+            panel.validate(&rules)
+        };
+        let drill = |x: f64, y: f64, radius: f64| InnerAtom::Drill {
+            center: [x, y].into(),
+            radius,
+            plated: true,
+        };
+        let copper = |min: [f64; 2], max: [f64; 2]| InnerAtom::Rect {
+            rect: geo::Rect::new(min, max),
+            layer: Layer::FrontCopper,
+        };
+
+        assert_eq!(panel_with(vec![drill(2.5, 2.5, 0.5)]).len(), 0);
+
+        let v = panel_with(vec![drill(2.5, 2.5, 0.1)]);
+        assert_eq!(v.len(), 1);
+        assert!(
+            matches!(v[0], Violation::TooSmallDrill { diameter, .. } if diameter > 0.19 && diameter < 0.21)
+        );
+
+        let v = panel_with(vec![drill(0.6, 2.5, 0.5)]);
+        assert_eq!(v.len(), 1);
+        assert!(
+            matches!(v[0], Violation::TooCloseToEdge { distance, .. } if distance > 0.09 && distance < 0.11)
+        );
+
+        let v = panel_with(vec![drill(2., 2.5, 0.5), drill(3.2, 2.5, 0.5)]);
+        assert_eq!(v.len(), 1);
+        assert!(
+            matches!(v[0], Violation::DrillsTooClose { distance, .. } if distance > 0.19 && distance < 0.21)
+        );
+
+        let v = panel_with(vec![
+            copper([1., 1.], [2., 2.]),
+            copper([2.1, 1.], [3., 2.]),
+            copper([1.5, 1.5], [2.5, 2.5]),
+            copper([3.5, 3.5], [4., 4.]),
+        ]);
+        assert_eq!(v.len(), 1);
+        assert!(
+            matches!(v[0], Violation::CopperTooClose { distance, .. } if distance > 0.09 && distance < 0.11)
+        );
+    }
+
+    #[test]
+    fn test_auto_fiducials() {
+        let mut panel = Panel::new();
+        panel.auto_fiducials();
+        assert_eq!(panel.interior_geometry().len(), 0);
+
+        panel.push(features::Rect::new([0., 0.].into(), [20., 10.].into()));
+        panel.auto_fiducials();
+
+        let copper = panel.interior_geometry_layer(Layer::FrontCopper);
+        let mask = panel.interior_geometry_layer(Layer::FrontMask);
+        assert_eq!(copper.len(), 3);
+        assert_eq!(mask.len(), 3);
+        assert_eq!(panel.interior_geometry_layer(Layer::BackMask).len(), 3);
+
+        for (c, m) in copper.iter().zip(mask.iter()) {
+            let (c, m) = (c.bounds().unwrap(), m.bounds().unwrap());
+            assert!(m.width() > c.width());
+            assert_eq!(m.center(), c.center());
+            assert!(m.min().x >= 0. && m.max().x <= 20.);
+            assert!(m.min().y >= 0. && m.max().y <= 10.);
+        }
+    }
+
+    #[test]
+    fn test_castellation_notches() {
+        use geo::algorithm::contains::Contains;
+        let mut panel = Panel::new();
+        panel
+            .push_spec("wrap(R<20>) with { left => castellation<8, 1.0> }")
+            .unwrap();
+
+        let edges = panel.edge_geometry().unwrap();
+        let b = panel.bounding_box().unwrap();
+        assert!(b.height() > 19.99 && b.height() < 20.01);
+
+        let x = b.min().x + 0.25;
+        for i in 0..8 {
+            let y = b.center().y + (i as f64 - 3.5) * 2.54;
+            // Inside a notch, but not between notches.
+            assert!(!edges.contains(&geo::Point::new(x, y)));
+            if i < 7 {
+                assert!(edges.contains(&geo::Point::new(x, y + 1.27)));
+            }
+            // The notches do not reach across the board.
+            assert!(edges.contains(&geo::Point::new(x + 1., y)));
+        }
+
+        let drills = panel.iter_drills().filter(|d| d.plated).count();
+        assert_eq!(drills, 8);
+    }
+
+    #[test]
+    fn test_panel_translate() {
+        let mut panel = Panel::new();
+        panel.push(features::Rect::with_center([0., 0.].into(), 4., 4.));
+        panel.add_interior_atom(InnerAtom::Drill {
+            center: [0., 0.].into(),
+            radius: 0.5,
+            plated: false,
+        });
+        panel.translate(10., 5.);
+
+        use geo::bounding_rect::BoundingRect;
+        let bounds = panel.edge_geometry().unwrap().bounding_rect().unwrap();
+        assert_eq!(bounds.min(), Coordinate { x: 8., y: 3. });
+        assert_eq!(bounds.max(), Coordinate { x: 12., y: 7. });
+        assert!(panel.interior_geometry().iter().any(|a| matches!(
+            a,
+            InnerAtom::Drill { center, .. } if *center == Coordinate { x: 10., y: 5. }
+        )));
+    }
+
+    #[test]
+    fn test_panel_rotate() {
+        let mut panel = Panel::new();
+        panel.push(features::Rect::with_center([0., 0.].into(), 6., 2.));
+        panel.push(features::Rect::with_center([10., 0.].into(), 6., 2.));
+        panel.add_interior_atom(InnerAtom::Drill {
+            center: [5., 1.].into(),
+            radius: 0.5,
+            plated: false,
+        });
+        panel.rotate(90.);
+        assert_eq!(panel.features.len(), 2);
+
+        let bounds = panel.bounding_box().unwrap();
+        assert!(bounds.width() > 11.99 && bounds.width() < 12.01);
+        assert!(bounds.height() > 5.99 && bounds.height() < 6.01);
+        // Custom atoms are not attached to a feature, so stay in place.
+        assert!(panel.interior_geometry().iter().any(|a| matches!(
+            a,
+            InnerAtom::Drill { center, .. } if *center == Coordinate { x: 5., y: 1. }
+        )));
+    }
+
+    #[test]
+    fn test_paste_and_courtyard_layers() {
+        let mut panel = Panel::new();
+        panel.push_spec("R<@(0, 0), 5>(msp)").unwrap();
+        let paste = panel.interior_geometry_layer(Layer::PasteFront);
+        assert_eq!(paste.len(), 1);
+        assert!(panel.has_layer(Layer::PasteBack));
+        assert!(!panel.has_layer(Layer::CourtyardFront));
+
+        let mut panel = Panel::new();
+        panel.push_spec("R<@(0, 0), 10>(h3)").unwrap();
+        assert!(panel.has_layer(Layer::CourtyardFront));
+        assert!(panel.has_layer(Layer::CourtyardBack));
+        assert!(!panel.has_layer(Layer::PasteFront));
+
+        let mut gerber = Vec::new();
+        panel
+            .serialize_gerber_layer(Layer::CourtyardFront, &mut gerber)
+            .unwrap();
+        assert!(!gerber.is_empty());
+    }
+
+    #[test]
+    fn test_serialize_svg() {
+        let mut panel = Panel::new();
+        panel.push(features::Rect::new([0., 0.].into(), [5., 5.].into()));
+
+        let mut out = Vec::new();
+        panel.serialize_svg(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with("<svg"));
+        assert!(out.contains("<path"));
+        assert_eq!(out, panel.to_svg_string().unwrap());
+
+        assert!(matches!(
+            Panel::new().serialize_svg(&mut Vec::new()),
+            Err(Err::NoFeatures) | Err(Err::NoBounds)
+        ));
+    }
+
+    #[test]
+    fn test_export_gerbers_zip() {
+        let mut panel = Panel::new();
+        panel.push_spec("[2; R; v-score]R<5>(h)").unwrap();
+        let bytes = panel.export_gerbers_zip().unwrap();
+
+        let mut archive = zip::ZipArchive::new(std::io::Cursor::new(bytes)).unwrap();
+        let names: Vec<String> = (0..archive.len())
+            .map(|i| archive.by_index(i).unwrap().name().to_string())
+            .collect();
+        for want in &[
+            "Edge.Cuts.gm1",
+            "F.Cu.gtl",
+            "F.Mask.gts",
+            "F.SilkS.gto",
+            "B.Cu.gbl",
+            "B.Mask.gbs",
+            "B.SilkS.gto",
+            "F.Paste.gtp",
+            "B.Paste.gbp",
+            "F.CrtYd.fcrtyd",
+            "B.CrtYd.bcrtyd",
+            "PTH.drl",
+            "NPTH.drl",
+            "Cmts.User",
+            "fab-notes.txt",
+        ] {
+            assert!(names.iter().any(|n| n == want), "missing {}", want);
+        }
+    }
+
+    #[test]
+    fn test_interior_geometry_layer() {
+        let mut panel = Panel::new();
+        panel.push_spec("R<@(0, 0), 10>(h3)").unwrap();
+
+        let copper = panel.interior_geometry_layer(Layer::FrontCopper);
+        assert_eq!(copper.len(), 1);
+        assert!(matches!(copper[0], InnerAtom::Circle { .. }));
+        assert_eq!(panel.interior_geometry_layer(Layer::FrontLegend).len(), 0);
+        #[allow(deprecated)]
+        let legacy = panel.interior_for_layer(&Layer::FrontCopper);
+        assert_eq!(legacy, copper);
+
+        assert_eq!(panel.interior_drills(true).len(), 1);
+        assert_eq!(panel.interior_drills(false).len(), 0);
+        panel.push_spec("R<@(20, 0), 10>(drill<1, npth>)").unwrap();
+        assert_eq!(panel.interior_drills(false).len(), 1);
+    }
+
+    #[test]
+    fn test_atpos_distribute() {
+        let mut panel = Panel::new();
+        panel
+            .push_spec("wrap(R<20>) with { [5] left => C<2>(h) }")
+            .unwrap();
+
+        let mut drills: Vec<_> = panel.iter_drills().map(|d| d.center).collect();
+        assert_eq!(drills.len(), 5);
+        drills.sort_by(|a, b| a.y.partial_cmp(&b.y).unwrap());
+
+        // The circles are centered on the left edge, from one corner to
+        // the other.
+        let b = panel.bounding_box().unwrap();
+        use geo::algorithm::contains::Contains;
+        let edges = panel.edge_geometry().unwrap();
+        for (i, d) in drills.iter().enumerate() {
+            assert!(d.x > b.min().x + 1.99 && d.x < b.min().x + 2.01);
+            let want = drills[0].y + 5. * i as f64;
+            assert!(d.y > want - 0.01 && d.y < want + 0.01);
+            assert!(edges.contains(&geo::Coordinate::from([d.x - 1.8, d.y])));
+        }
+    }
+
+    #[test]
+    fn test_mirror() {
+        let mut panel = Panel::new();
+        // Rectangles are centered on their coordinates, so this one
+        // touches the X axis.
+        panel.push_spec("mirror(y) { R<@(0, 0.5), 3, 1> }").unwrap();
+        let b = panel.bounding_box().unwrap();
+        assert!(b.width() > 2.99 && b.width() < 3.01);
+        assert!(b.height() > 1.99 && b.height() < 2.01);
+
+        let mut panel = Panel::new();
+        panel.push_spec("mirror(y) { R<@(0, 5), 2>(h1) }").unwrap();
+        let drills: Vec<_> = panel.iter_drills().map(|d| d.center).collect();
+        assert_eq!(drills.len(), 2);
+        assert!((drills[0].y + drills[1].y).abs() < 1e-9);
+        assert!(drills[0].y.abs() > 1.);
+        assert_eq!(panel.interior_geometry().len() % 2, 0);
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut panel = Panel::new();
+        panel.push_spec("R<@(5, 5), 10>(h3)").unwrap();
+
+        let mut other = Panel::new();
+        other.push_spec("R<@(5, 5), 10>(h3) % board_b").unwrap();
+        assert!(matches!(
+            panel.merge(&Panel::new(), (0., 0.)),
+            Err(Err::NoFeatures)
+        ));
+        panel.merge(&other, (100., 0.)).unwrap();
+
+        let b = panel.bounding_box().unwrap();
+        assert!(b.width() > 109.99 && b.width() < 110.01);
+        assert!(b.height() > 9.99 && b.height() < 10.01);
+
+        let drills: Vec<_> = panel.iter_drills().map(|d| d.center.x).collect();
+        assert_eq!(drills.len(), 2);
+        assert!(drills.iter().any(|x| *x > 4.99 && *x < 5.01));
+        assert!(drills.iter().any(|x| *x > 104.99 && *x < 105.01));
+
+        let named = panel.named_info();
+        assert_eq!(named.len(), 1);
+        assert!(named[0].bounds.min().x > 99.99 && named[0].bounds.min().x < 100.01);
+    }
+
+    #[test]
+    fn test_spec_functions() {
+        let mut called = Panel::new();
+        called.push_spec("fn f(r) = C<$r>\nf(5)").unwrap();
+        let mut direct = Panel::new();
+        direct.push_spec("C<5>").unwrap();
+
+        let (a, b) = (
+            called.bounding_box().unwrap(),
+            direct.bounding_box().unwrap(),
+        );
+        assert!((a.width() - b.width()).abs() < 1e-9);
+        assert!((a.height() - b.height()).abs() < 1e-9);
+        assert!((a.min().x - b.min().x).abs() < 1e-9);
+        assert!((a.min().y - b.min().y).abs() < 1e-9);
+
+        let mut panel = Panel::new();
+        panel
+            .push_spec("fn board(w, h) = R<$w, $h>(h3)\nboard(10, 5)")
+            .unwrap();
+        let b = panel.bounding_box().unwrap();
+        assert!(b.width() > 9.99 && b.width() < 10.01);
+        assert!(b.height() > 4.99 && b.height() < 5.01);
+        assert_eq!(panel.iter_drills().count(), 1);
+    }
+
+    #[test]
+    fn test_spec_units() {
+        let mut inches = Panel::new();
+        inches.push_spec("R<0.197in>").unwrap();
+        let mut mm = Panel::new();
+        mm.push_spec("R<5mm>").unwrap();
+        let (a, b) = (inches.bounding_box().unwrap(), mm.bounding_box().unwrap());
+        assert!((a.width() - b.width()).abs() < 0.01);
+        assert!((a.height() - b.height()).abs() < 0.01);
+
+        let mut panel = Panel::new();
+        panel.push_spec("R<1in>").unwrap();
+        let b = panel.bounding_box().unwrap();
+        assert!(b.width() > 25.39 && b.width() < 25.41);
+        assert!(panel.to_svg_string().is_ok());
+    }
+
+    #[test]
+    fn test_to_spec_string_round_trip() {
+        let mut panel = Panel::new();
+        panel
+            .push_spec(
+                "wrap ([2; R; v-score] R<@(0, 0), 20, 10, round = 1>(h3)) with {
+                    left align exterior => tab<3, 2, dir = left>,
+                    angle(45)+6 => C<1>,
+                    min-top align interior => R<4, 2>,
+                }
+                column center @(0, 30) { R<5, 5>, T<6, 4, iso>(h2), mount_cut_left<4> }
+                negative { C<@(10, 5), 2> }
+                rotate(30) { E<@(50, 0), 4, 2> } % ellipse
+                mirror(x) { P<@(-60, 0), 6, 3>(drill<1, npth>) }
+                circle_array(4, 8, 45) { C<@(0, -40), 1.5> }
+                [2x2; gap = 1] R<@(80, 0), 5, 5>(fiducial<@(1, 1)>)
+                translate(0, 60) { castellation<3, 1, side = right> }
+                sector<@(0, -60), 5, 0, 90>",
+            )
+            .unwrap();
+        // Columns with separators are only constructed programmatically.
+        let mut column = features::Column::align_center(vec![
+            features::Rect::new([0., 0.].into(), [10., 5.].into()),
+            features::Rect::new([0., 0.].into(), [6., 5.].into()),
+        ])
+        .with_separator(features::Rect::new([0., 0.].into(), [10., 0.5].into()), 0.5);
+        column.translate([0., 80.].into());
+        panel.push(column);
+
+        let spec = panel.to_spec_string();
+        let mut rebuilt = Panel::new();
+        rebuilt
+            .push_spec(&spec)
+            .unwrap_or_else(|e| panic!("{}\n{}", e, spec));
+        assert_eq!(rebuilt.features.len(), panel.features.len());
+        assert_eq!(rebuilt.named_info().len(), 1);
+
+        use geo::area::Area;
+        let (a, b) = (
+            panel.edge_geometry().unwrap(),
+            rebuilt.edge_geometry().unwrap(),
+        );
+        assert!((a.unsigned_area() - b.unsigned_area()).abs() < 1e-6);
+        let (ab, bb) = (
+            panel.bounding_box().unwrap(),
+            rebuilt.bounding_box().unwrap(),
+        );
+        assert!((ab.min().x - bb.min().x).abs() < 1e-6 && (ab.min().y - bb.min().y).abs() < 1e-6);
+        assert!((ab.max().x - bb.max().x).abs() < 1e-6 && (ab.max().y - bb.max().y).abs() < 1e-6);
+        assert_eq!(
+            panel.interior_geometry().len(),
+            rebuilt.interior_geometry().len()
+        );
+    }
+
+    #[test]
+    fn test_gerber_x2_attributes() {
+        let mut panel = Panel::new();
+        panel.push_spec("R<5>(h)").unwrap();
+
+        let mut edges = Vec::new();
+        panel.serialize_gerber_edges(&mut edges).unwrap();
+        let edges = String::from_utf8(edges).unwrap();
+        assert!(edges.contains("%TF.Part,Single*%"));
+        assert!(edges.contains("%TF.FileFunction,Profile,NP*%"));
+
+        let layer = |layer| {
+            let mut out = Vec::new();
+            panel.serialize_gerber_layer(layer, &mut out).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        assert!(layer(Layer::FrontCopper).contains("%TF.FileFunction,Copper,L1,Top*%"));
+        assert!(layer(Layer::BackCopper).contains("%TF.FileFunction,Copper,L2,Bot*%"));
+        assert!(layer(Layer::FrontMask).contains("%TF.FileFunction,Soldermask,Top*%"));
+        assert!(!layer(Layer::PasteBack).contains("%TF.FileFunction"));
+        assert!(!layer(Layer::CourtyardFront).contains("%TF.FileFunction"));
+    }
+
+    #[test]
+    fn test_drill_units() {
+        let mut panel = Panel::new();
+        panel
+            .push_spec("R<@(12.7, 25.4), 20, 10>(drill<1.27>)")
+            .unwrap();
+
+        let coords = |units| {
+            let mut buf = Vec::new();
+            panel.serialize_drill(&mut buf, true, units).unwrap();
+            let out = String::from_utf8(buf).unwrap();
+            let line = out
+                .lines()
+                .find(|l| l.starts_with('X'))
+                .unwrap()
+                .to_string();
+            let y = line.find('Y').unwrap();
+            let (x, y): (f64, f64) = (line[1..y].parse().unwrap(), line[y + 1..].parse().unwrap());
+            (out, x, y)
+        };
+
+        let (inch, ix, iy) = coords(DrillUnits::Inches);
+        let (metric, mx, my) = coords(DrillUnits::Millimeters);
+        assert!(inch.contains("INCH,TZ\n") && inch.contains("C0.0500\n"));
+        assert!(metric.contains("METRIC,LZ\n") && metric.contains("C1.270\n"));
+        assert!((ix * 25.4 - mx).abs() < 0.01 && (iy * 25.4 - my).abs() < 0.01);
+        assert!((mx - 12.7).abs() < 0.01 && (iy - 1.).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_drill_with_slots() {
+        let mut panel = Panel::new();
+        panel
+            .push_spec("R<@(0, 0), 20, 10>(drill<1.27>)\nR<@(25.4, 0), 20, 10>(slot<@(-5.08, 0), @(5.08, 0), 2.54>)")
+            .unwrap();
+
+        let mut buf = Vec::new();
+        panel
+            .serialize_drill(&mut buf, true, DrillUnits::Inches)
+            .unwrap();
+        let out = String::from_utf8(buf).unwrap();
+        assert!(out.contains("C0.0500\n") && out.contains("C0.1000\n"));
+        assert!(out.contains("X0.0000Y0.0000\n"));
+        assert!(out.contains("X0.8000Y0.0000G85X1.2000Y0.0000\nG05\n"));
+
+        let mut buf = Vec::new();
+        panel
+            .serialize_drill(&mut buf, false, DrillUnits::Inches)
+            .unwrap();
+        assert!(!String::from_utf8(buf).unwrap().contains("G85"));
+    }
+
+    #[test]
+    fn test_dxf() {
+        let mut panel = Panel::new();
+        panel
+            .push_spec("[2; R; v-score] R<@(5, 5), 10>(h3)")
+            .unwrap();
+
+        let mut buf = Vec::new();
+        panel.serialize_dxf(&mut buf).unwrap();
+        let out = String::from_utf8(buf).unwrap();
+        assert!(out.contains("  0\nSECTION\n  2\nENTITIES\n"));
+        assert!(out.contains("LWPOLYLINE\n  8\n0\n"));
+        assert!(out.contains("CIRCLE\n  8\nDRILLS\n"));
+        assert!(out.contains("LINE\n  8\nVSCORE\n"));
+        assert!(out.ends_with("  0\nEOF\n"));
+    }
+
+    #[cfg(feature = "tessellate")]
+    #[test]
+    fn test_serialize_stl() {
+        let mut panel = Panel::new();
+        panel.push_spec("R<@(5, 5), 10>(h3)").unwrap();
+        let (_, inds) = panel.tessellate_3d().unwrap();
+
+        let mut buf = Vec::new();
+        panel.serialize_stl(&mut buf).unwrap();
+        let count = u32::from_le_bytes([buf[80], buf[81], buf[82], buf[83]]) as usize;
+        assert_eq!(count, inds.len() / 3);
+        assert_eq!(buf.len(), 84 + 50 * count);
+
+        let mesh = stl_io::read_stl(&mut std::io::Cursor::new(buf)).unwrap();
+        assert_eq!(mesh.faces.len(), count);
+    }
+
+    #[test]
+    fn test_translate_named() {
+        let mut panel = Panel::new();
+        panel
+            .push_spec("R<@(5, 5), 10>\nR<@(20, 5), 4> % tab")
+            .unwrap();
+        assert!(!panel.translate_named("nope", (1., 1.)));
+
+        let before = panel.named_info()[0].bounds;
+        assert!(panel.translate_named("tab", (3., -2.)));
+        let after = panel.named_info()[0].bounds;
+        assert!((after.min().x - before.min().x - 3.).abs() < 1e-9);
+        assert!((after.min().y - before.min().y + 2.).abs() < 1e-9);
+        assert!((after.width() - before.width()).abs() < 1e-9);
+
+        use geo::bounding_rect::BoundingRect;
+        let bounds = panel.edge_geometry().unwrap().bounding_rect().unwrap();
+        assert!(bounds.max().x > 24.99 && bounds.max().x < 25.01);
+    }
+
+    #[cfg(feature = "text")]
+    #[test]
+    fn test_silkscreen_text_gerber() {
+        let mut panel = Panel::new();
+        panel
+            .push_spec("R<@(0, 0), 20, 10>(text<@(-5, 2), \"REV1\", size=1.6>)")
+            .unwrap();
+        let pixels = panel
+            .interior_geometry_layer(Layer::FrontLegend)
+            .into_iter()
+            .filter(|a| matches!(a, InnerAtom::Rect { .. }))
+            .count();
+        assert!(pixels > 0);
+
+        let mut buf = Vec::new();
+        panel
+            .serialize_gerber_layer(Layer::FrontLegend, &mut buf)
+            .unwrap();
+        let out = String::from_utf8(buf).unwrap();
+        assert_eq!(out.matches("D03*").count(), pixels);
+    }
+
+    #[cfg(feature = "tessellate")]
+    #[test]
+    fn test_board_thickness() {
+        let mut panel = Panel::new();
+        panel.push_spec("R<5>").unwrap();
+        let (verts, _) = panel.tessellate_3d().unwrap();
+        assert!(verts.iter().all(|v| v[2] == 0.8 || v[2] == -0.8));
+
+        panel.set_board_thickness(3.2);
+        let (verts, _) = panel.tessellate_3d().unwrap();
+        assert!(verts.iter().all(|v| v[2] == 1.6 || v[2] == -1.6));
+        assert!(verts.iter().any(|v| v[2] == 1.6) && verts.iter().any(|v| v[2] == -1.6));
+    }
+
+    #[cfg(feature = "tessellate")]
+    #[test]
+    fn test_smooth_normals() {
+        // Two triangles forming a flat square.
+        let verts = vec![[0., 0., 0.], [1., 0., 0.], [1., 1., 0.], [0., 1., 0.]];
+        let inds = vec![0, 1, 2, 0, 2, 3];
+        let normals = smooth_normals_from_tessellation(&verts, &inds);
+        assert_eq!(normals.len(), verts.len());
+        for n in &normals {
+            let sq = n[0] * n[0] + n[1] * n[1] + n[2] * n[2];
+            assert!((sq - 1.).abs() < 1e-6);
+            assert!((n[2] - 1.).abs() < 1e-6);
+        }
+
+        let mut panel = Panel::new();
+        panel.push_spec("R<5>(h)").unwrap();
+        let (verts, inds) = panel.tessellate_3d().unwrap();
+        let normals = smooth_normals_from_tessellation(&verts, &inds);
+        assert_eq!(normals.len(), verts.len());
+        assert_eq!(
+            normals_from_tessellation(&verts, &inds).len(),
+            inds.len() / 3
+        );
+    }
+
+    #[cfg(feature = "tessellate")]
+    #[test]
+    fn test_tessellate_2d_with_uv() {
+        let mut panel = Panel::new();
+        panel.push_spec("R<@(5, 2), 10, 4>").unwrap();
+        let buffers = panel.tessellate_2d_with_uv().unwrap();
+        assert!(buffers.indices.len() >= 6);
+
+        let (min_u, max_u, min_v, max_v) = buffers.vertices.iter().fold(
+            (f32::MAX, f32::MIN, f32::MAX, f32::MIN),
+            |(min_u, max_u, min_v, max_v), p| {
+                (
+                    min_u.min(p.u),
+                    max_u.max(p.u),
+                    min_v.min(p.v),
+                    max_v.max(p.v),
+                )
+            },
+        );
+        assert!(min_u.abs() < 1e-6 && min_v.abs() < 1e-6);
+        assert!((max_u - 1.).abs() < 1e-6 && (max_v - 1.).abs() < 1e-6);
+
+        let corner = buffers.vertices.iter().find(|p| p.x > 9.99).unwrap();
+        assert!((corner.u - 1.).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_intersect() {
+        use geo::algorithm::area::Area;
+        let rect = features::Rect::with_center([0., 0.].into(), 5., 5.);
+        let circle = features::Circle::with_radius(3.);
+
+        let mut panel = Panel::new();
+        panel.push_spec("intersect { R<5> C<3> }").unwrap();
+        let edge = panel.edge_geometry().unwrap();
+        let area = edge.unsigned_area();
+        assert!(area > 0.);
+
+        for child in [rect.edge_union().unwrap(), circle.edge_union().unwrap()].iter() {
+            assert!(area < child.unsigned_area());
+        }
+
+        use geo::algorithm::contains::Contains;
+        // Within both the rectangle and the circle.
+        assert!(edge.contains(&geo::Point::new(0., 0.)));
+        assert!(edge.contains(&geo::Point::new(2.4, 0.)));
+        // Corners of the rectangle lie outside the circle.
+        assert!(!edge.contains(&geo::Point::new(2.4, 2.4)));
+        // The circle extends beyond the sides of the rectangle.
+        assert!(!edge.contains(&geo::Point::new(0., 2.9)));
+    }
+
+    #[test]
+    fn test_interference_check() {
+        let mut panel = Panel::new();
+        panel
+            .push_spec("R<@(0, 0), 5> % a\nR<@(3, 0), 5> % b\nR<@(20, 0), 5> % c\nR<@(2, 0), 5>")
+            .unwrap();
+        assert_eq!(
+            panel.interference_check(),
+            vec![("a".to_string(), "b".to_string())]
+        );
+
+        let mut panel = Panel::new();
+        panel
+            .push_spec("R<@(0, 0), 5> % a\nR<@(5, 0), 5> % b")
+            .unwrap();
+        assert!(panel.interference_check().is_empty());
+    }
 }
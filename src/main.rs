@@ -1,4 +1,4 @@
-use maker_panel::{Layer, Panel};
+use maker_panel::{DrillUnits, Layer, Panel};
 use std::path::PathBuf;
 use structopt::StructOpt;
 
@@ -6,8 +6,8 @@ use structopt::StructOpt;
 enum Err {
     IO(std::io::Error),
     General(maker_panel::Err),
-    Zip(zip::result::ZipError),
     SpecError(usize, String, maker_panel::SpecErr),
+    Rules(String),
 }
 
 /// Represents an output format provided for the gen command.
@@ -20,50 +20,61 @@ pub enum Fmt {
     BackCopper,
     BackMask,
     BackLegend,
+    FrontPaste,
+    BackPaste,
+    FrontCourtyard,
+    BackCourtyard,
     PlatedDrill,
     NonPlatedDrill,
     Zip,
     #[cfg(feature = "tessellate")]
     Stl,
     FabInstructions,
+    PickAndPlace,
+    Bom,
+    DrillReport,
+    Dxf,
+    #[cfg(feature = "compress")]
+    Svgz,
 }
 
 impl Fmt {
-    fn all_formats() -> &'static [Fmt] {
-        &[
-            Fmt::Edge,
-            Fmt::FrontCopper,
-            Fmt::FrontMask,
-            Fmt::FrontLegend,
-            Fmt::BackCopper,
-            Fmt::BackMask,
-            Fmt::BackLegend,
-            Fmt::PlatedDrill,
-            Fmt::NonPlatedDrill,
-            // Fmt::Stl, - exclude from list so its not generated by default
-            // Fmt::FabInstructions - exclude from list as special-case
-        ]
-    }
-
+    /// Returns the name of the file the format is written to, when
+    /// generating into a directory.
     fn file_suffix(&self) -> &'static str {
         match self {
-            Fmt::Edge => "Edge.Cuts.gm1",
-            Fmt::FrontCopper => "F.Cu.gtl",
-            Fmt::FrontMask => "F.Mask.gts",
-            Fmt::FrontLegend => "F.SilkS.gto",
-            Fmt::BackCopper => "B.Cu.gbl",
-            Fmt::BackMask => "B.Mask.gbs",
-            Fmt::BackLegend => "B.SilkS.gto",
-            Fmt::PlatedDrill => "PTH.drl",
-            Fmt::NonPlatedDrill => "NPTH.drl",
+            Fmt::Edge => maker_panel::EDGE_FILE_SUFFIX,
+            Fmt::FrontCopper => Layer::FrontCopper.file_suffix(),
+            Fmt::FrontMask => Layer::FrontMask.file_suffix(),
+            Fmt::FrontLegend => Layer::FrontLegend.file_suffix(),
+            Fmt::BackCopper => Layer::BackCopper.file_suffix(),
+            Fmt::BackMask => Layer::BackMask.file_suffix(),
+            Fmt::BackLegend => Layer::BackLegend.file_suffix(),
+            Fmt::FrontPaste => Layer::PasteFront.file_suffix(),
+            Fmt::BackPaste => Layer::PasteBack.file_suffix(),
+            Fmt::FrontCourtyard => Layer::CourtyardFront.file_suffix(),
+            Fmt::BackCourtyard => Layer::CourtyardBack.file_suffix(),
+            Fmt::PlatedDrill => maker_panel::drill_file_suffix(true),
+            Fmt::NonPlatedDrill => maker_panel::drill_file_suffix(false),
             Fmt::Zip => "gerbers.zip",
             #[cfg(feature = "tessellate")]
             Fmt::Stl => "extrusion.stl",
-            Fmt::FabInstructions => "Cmts.User",
+            Fmt::FabInstructions => Layer::FabricationInstructions.file_suffix(),
+            Fmt::PickAndPlace => "pos.csv",
+            Fmt::Bom => "bom.csv",
+            Fmt::DrillReport => "drill-report.txt",
+            Fmt::Dxf => "edge.dxf",
+            #[cfg(feature = "compress")]
+            Fmt::Svgz => "panel.svgz",
         }
     }
 
-    fn serialize_to(&self, panel: &Panel, w: &mut impl std::io::Write) -> Result<(), Err> {
+    fn serialize_to(
+        &self,
+        panel: &Panel,
+        drill_units: DrillUnits,
+        w: &mut impl std::io::Write,
+    ) -> Result<(), Err> {
         match self {
             Fmt::Edge => panel.serialize_gerber_edges(w).map_err(|e| Err::General(e)),
             Fmt::FrontCopper => panel
@@ -84,68 +95,49 @@ impl Fmt {
             Fmt::BackLegend => panel
                 .serialize_gerber_layer(Layer::BackLegend, w)
                 .map_err(|e| Err::General(e)),
+            Fmt::FrontPaste => panel
+                .serialize_gerber_layer(Layer::PasteFront, w)
+                .map_err(|e| Err::General(e)),
+            Fmt::BackPaste => panel
+                .serialize_gerber_layer(Layer::PasteBack, w)
+                .map_err(|e| Err::General(e)),
+            Fmt::FrontCourtyard => panel
+                .serialize_gerber_layer(Layer::CourtyardFront, w)
+                .map_err(|e| Err::General(e)),
+            Fmt::BackCourtyard => panel
+                .serialize_gerber_layer(Layer::CourtyardBack, w)
+                .map_err(|e| Err::General(e)),
             Fmt::FabInstructions => panel
                 .serialize_gerber_layer(Layer::FabricationInstructions, w)
                 .map_err(|e| Err::General(e)),
-            Fmt::PlatedDrill => panel.serialize_drill(w, true).map_err(|e| Err::IO(e)),
-            Fmt::NonPlatedDrill => panel.serialize_drill(w, false).map_err(|e| Err::IO(e)),
+            Fmt::PlatedDrill => panel
+                .serialize_drill(w, true, drill_units)
+                .map_err(|e| Err::IO(e)),
+            Fmt::NonPlatedDrill => panel
+                .serialize_drill(w, false, drill_units)
+                .map_err(|e| Err::IO(e)),
+            Fmt::PickAndPlace => panel.serialize_pick_and_place(w).map_err(|e| Err::IO(e)),
+            Fmt::Bom => panel.serialize_bom(w).map_err(|e| Err::IO(e)),
+            Fmt::Dxf => panel.serialize_dxf(w).map_err(|e| Err::General(e)),
+            Fmt::DrillReport => {
+                panel
+                    .serialize_drill_report(w, true)
+                    .map_err(|e| Err::IO(e))?;
+                panel
+                    .serialize_drill_report(w, false)
+                    .map_err(|e| Err::IO(e))
+            }
+            #[cfg(feature = "compress")]
+            Fmt::Svgz => {
+                let svgz = panel.to_svgz_bytes().map_err(|e| Err::General(e))?;
+                w.write_all(&svgz).map_err(|e| Err::IO(e))
+            }
             Fmt::Zip => {
-                let mut cursor = std::io::Cursor::new(Vec::with_capacity(4 * 1024));
-                let mut zip = zip::ZipWriter::new(&mut cursor);
-                let options = zip::write::FileOptions::default()
-                    .compression_method(zip::CompressionMethod::Stored)
-                    .unix_permissions(0o755);
-
-                for fmt in Fmt::all_formats() {
-                    zip.start_file(fmt.file_suffix(), options)
-                        .map_err(|e| Err::Zip(e))?;
-                    fmt.serialize_to(panel, &mut zip)?;
-                }
-                if panel.has_fab_markings() {
-                    zip.start_file(Fmt::FabInstructions.file_suffix(), options)
-                        .map_err(|e| Err::Zip(e))?;
-                    Fmt::FabInstructions.serialize_to(panel, &mut zip)?;
-
-                    zip.start_file("fab-notes.txt", options)
-                        .map_err(|e| Err::Zip(e))?;
-                    use std::io::Write;
-                    zip.write(b"V-SCORE: See Cmts.User gerber file.\n")
-                        .map_err(|e| Err::IO(e))?;
-                }
-                zip.finish().map_err(|e| Err::Zip(e))?;
-
-                drop(zip);
-                w.write(&cursor.into_inner()).map_err(|e| Err::IO(e))?;
-                Ok(())
+                let zip = panel.export_gerbers_zip().map_err(|e| Err::General(e))?;
+                w.write_all(&zip).map_err(|e| Err::IO(e))
             }
             #[cfg(feature = "tessellate")]
-            Fmt::Stl => {
-                let (verts, inds) = panel.tessellate_3d().map_err(|e| Err::General(e))?;
-                let normals = maker_panel::normals_from_tessellation(&verts, &inds);
-                use stl_io::{Normal, Triangle, Vertex};
-
-                let v_conv = |idx: u16| {
-                    let v = verts[idx as usize];
-                    Vertex::new([v[0] as f32, v[1] as f32, v[2] as f32])
-                };
-
-                let mesh = inds
-                    .chunks_exact(3)
-                    .zip(normals)
-                    .map(|(inds, normal)| {
-                        let verts = [v_conv(inds[0]), v_conv(inds[1]), v_conv(inds[2])];
-
-                        Triangle {
-                            normal: Normal::new(normal),
-                            vertices: verts,
-                        }
-                    })
-                    .collect::<Vec<_>>();
-
-                // println!("indices: {:?}", inds.chunks_exact(3).collect::<Vec<_>>());
-                stl_io::write_stl(w, mesh.iter()).unwrap();
-                Ok(())
-            }
+            Fmt::Stl => panel.serialize_stl(w).map_err(|e| Err::General(e)),
         }
     }
 }
@@ -162,21 +154,33 @@ impl std::str::FromStr for Fmt {
             "b.cu" => Ok(Fmt::BackCopper),
             "b.mask" => Ok(Fmt::BackMask),
             "b.legend" => Ok(Fmt::BackLegend),
+            "f.paste" => Ok(Fmt::FrontPaste),
+            "b.paste" => Ok(Fmt::BackPaste),
+            "f.crtyd" => Ok(Fmt::FrontCourtyard),
+            "b.crtyd" => Ok(Fmt::BackCourtyard),
             "drl" | "pdrl" => Ok(Fmt::PlatedDrill),
             "ndrl" | "npdrl" => Ok(Fmt::NonPlatedDrill),
             "zip" | "all" => Ok(Fmt::Zip),
             #[cfg(feature = "tessellate")]
             "stl" => Ok(Fmt::Stl),
             "fab" | "cmts.user" => Ok(Fmt::FabInstructions),
+            "pos" | "pnp" => Ok(Fmt::PickAndPlace),
+            "bom" => Ok(Fmt::Bom),
+            "drill-report" => Ok(Fmt::DrillReport),
+            "dxf" => Ok(Fmt::Dxf),
+            #[cfg(feature = "compress")]
+            "svgz" => Ok(Fmt::Svgz),
             _ => Err(format!("no such fmt: {}", s).to_string()),
         }
     }
 }
 
 /// Represents the --size parameter from the command line.
+#[cfg(feature = "render")]
 #[derive(Debug, PartialEq, Clone)]
 pub struct RenderFitTo(usvg::FitTo);
 
+#[cfg(feature = "render")]
 impl std::str::FromStr for RenderFitTo {
     type Err = String;
 
@@ -202,6 +206,7 @@ impl std::str::FromStr for RenderFitTo {
 
 #[derive(StructOpt, Debug, PartialEq, Clone)]
 pub enum Cmd {
+    #[cfg(feature = "render")]
     #[structopt(name = "png", about = "Renders a PNG visualizing the panel.")]
     Render {
         #[structopt(
@@ -238,10 +243,25 @@ pub enum Cmd {
             name = "output",
             short = "o",
             long = "output",
-            about = "File path where the generated output should be written"
+            about = "File path where the generated output should be written, or a directory to write it into"
         )]
         output: Option<PathBuf>,
+
+        #[structopt(
+            name = "drill-units",
+            long = "drill-units",
+            about = "Units used in drill files, either 'in' or 'mm'",
+            default_value = "in"
+        )]
+        drill_units: DrillUnits,
     },
+    #[structopt(name = "info", about = "Prints information about the panel.")]
+    Info {},
+    #[structopt(
+        name = "drill-summary",
+        about = "Prints the number of drill hits of each size."
+    )]
+    DrillSummary {},
 }
 
 #[derive(Debug, StructOpt, Clone)]
@@ -266,6 +286,28 @@ struct Opt {
     )]
     convex_hull: bool,
 
+    #[structopt(
+        name = "validate",
+        long = "validate",
+        about = "Whether to check the panel against design rules before running the command"
+    )]
+    validate: bool,
+
+    #[structopt(
+        name = "rules",
+        long = "rules",
+        about = "File path to the design rules to validate against, if not the defaults"
+    )]
+    rules: Option<PathBuf>,
+
+    #[structopt(
+        name = "thickness",
+        long = "thickness",
+        about = "Thickness of the board in millimeters, used for 3d output",
+        default_value = "1.6"
+    )]
+    thickness: f64,
+
     input_spec: Vec<String>,
 
     #[structopt(subcommand)]
@@ -275,20 +317,28 @@ struct Opt {
 impl Opt {
     fn panel(&self, panel: &mut Panel) -> Result<(), Err> {
         panel.convex_hull(self.convex_hull);
+        panel.set_board_thickness(self.thickness);
 
         for (i, s) in self.input_spec.iter().enumerate() {
-            let content = if self.from_files {
-                use std::fs::read;
-                String::from_utf8_lossy(&read(s).map_err(|e| Err::IO(e))?).to_string()
+            if self.from_files {
+                panel.push_spec_file(std::path::Path::new(s))
             } else {
-                s.to_string()
-            };
-            panel
-                .push_spec(&content)
-                .map_err(|e| Err::SpecError(i, s.clone(), e))?;
+                panel.push_spec(s)
+            }
+            .map_err(|e| Err::SpecError(i, s.clone(), e))?;
         }
         Ok(())
     }
+
+    fn rules(&self) -> Result<maker_panel::DesignRules, Err> {
+        match &self.rules {
+            Some(p) => std::fs::read_to_string(p)
+                .map_err(|e| Err::IO(e))?
+                .parse()
+                .map_err(|e| Err::Rules(e)),
+            None => Ok(maker_panel::DesignRules::default()),
+        }
+    }
 }
 
 fn main() {
@@ -298,15 +348,33 @@ fn main() {
     match args.panel(&mut panel) {
         Ok(_) => {}
         Err(e) => {
-            if let Err::SpecError(idx, fname, maker_panel::SpecErr::Parse(msg)) = e {
+            if let Err::SpecError(idx, fname, e @ maker_panel::SpecErr::Parse { .. }) = e {
                 eprintln!("Input spec {} ({}) had parsing errors:", idx + 1, fname);
-                eprint!("{}", msg);
+                eprintln!("{}", e);
             } else {
                 eprintln!("Error: {:?}", e);
             }
             std::process::exit(1);
         }
     };
+    if args.validate {
+        let rules = match args.rules() {
+            Ok(rules) => rules,
+            Err(e) => {
+                eprintln!("Error: {:?}", e);
+                std::process::exit(1);
+            }
+        };
+        let violations = panel.validate(&rules);
+        if !violations.is_empty() {
+            eprintln!("Panel violates design rules:");
+            for v in violations {
+                eprintln!("  {}", v);
+            }
+            std::process::exit(1);
+        }
+    }
+
     // panel.convex_hull(true);
     // panel.push(Rect::with_center([0.0, -2.5].into(), 5., 5.));
     // panel.push_spec(DEMO_SPEC).unwrap();
@@ -321,6 +389,7 @@ fn run_cmd(args: Opt, mut panel: Panel) -> Result<(), Err> {
     let mut stdout = std::io::stdout();
 
     match args.cmd {
+        #[cfg(feature = "render")]
         Cmd::Render {
             output,
             fit_to,
@@ -335,13 +404,47 @@ fn run_cmd(args: Opt, mut panel: Panel) -> Result<(), Err> {
                 .unwrap();
             Ok(())
         }
-        Cmd::Gen { fmt, output: None } => fmt.serialize_to(&panel, &mut stdout),
+        Cmd::Info {} => {
+            let mut counts: Vec<_> = panel.feature_type_counts().into_iter().collect();
+            counts.sort();
+            println!("Features:");
+            for (name, count) in counts {
+                println!("  {}: {}", name, count);
+            }
+            if let Some(length) = panel.outline_length() {
+                println!("Outline length: {:.2}mm", length);
+            }
+            Ok(())
+        }
+        Cmd::DrillSummary {} => {
+            println!("{:>10}  {:>6}  {}", "Diameter", "Count", "Type");
+            for s in panel.drill_summary() {
+                println!(
+                    "{:>8.3}mm  {:>6}  {}",
+                    s.diameter_mm,
+                    s.count,
+                    if s.plated { "PTH" } else { "NPTH" }
+                );
+            }
+            Ok(())
+        }
+        Cmd::Gen {
+            fmt,
+            output: None,
+            drill_units,
+        } => fmt.serialize_to(&panel, drill_units, &mut stdout),
         Cmd::Gen {
             fmt,
             output: Some(p),
+            drill_units,
         } => {
+            let p = if p.is_dir() {
+                p.join(fmt.file_suffix())
+            } else {
+                p
+            };
             let mut file = std::fs::File::create(&p).map_err(|e| Err::IO(e))?;
-            fmt.serialize_to(&panel, &mut file)
+            fmt.serialize_to(&panel, drill_units, &mut file)
         }
     }
 }
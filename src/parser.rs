@@ -1,13 +1,14 @@
 use crate::Direction;
 use nom::branch::alt;
-use nom::bytes::complete::{tag, tag_no_case, take_while};
-use nom::character::complete::{multispace0, one_of};
-use nom::combinator::{all_consuming, cut, map, opt};
+use nom::bytes::complete::{tag, tag_no_case, take_while, take_while1};
+use nom::character::complete::{multispace0, multispace1, one_of};
+use nom::combinator::{all_consuming, cut, map, not, opt, verify};
 use nom::error::{context, VerboseError};
-use nom::multi::{fold_many1, many0};
-use nom::sequence::{delimited, tuple};
+use nom::multi::{fold_many1, many0, separated_list0};
+use nom::sequence::{delimited, preceded, tuple};
 use nom::IResult;
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone)]
 pub enum Value {
@@ -17,6 +18,7 @@ pub enum Value {
 }
 
 impl Value {
+    #[cfg(test)]
     fn float(&self) -> f64 {
         match self {
             Value::Float(f) => *f,
@@ -53,15 +55,38 @@ enum Variable {
     Number(f64),
 }
 
+/// Math functions available to CEL expressions, in addition to those
+/// provided by the interpreter. Each accepts a single number and returns
+/// a float.
+const CEL_MATH_FUNCTIONS: &[(&str, fn(f64) -> f64)] = &[
+    ("sin", f64::sin),
+    ("cos", f64::cos),
+    ("sqrt", f64::sqrt),
+    ("abs", f64::abs),
+    ("floor", f64::floor),
+    ("ceil", f64::ceil),
+];
+
 #[derive(Debug, Clone, Default)]
 struct ResolverContext {
     pub definitions: HashMap<String, Variable>,
+    /// Layer used by single-sided features when none is specified.
+    pub default_layer: Option<crate::Layer>,
+    /// Directory imports are resolved relative to. Imports are not
+    /// permitted when the spec was not read from a file.
+    pub base_path: Option<PathBuf>,
+    /// Files currently being imported, used to detect cycles.
+    pub import_stack: Vec<PathBuf>,
+    /// User-defined functions, as their parameter names and body.
+    pub functions: HashMap<String, (Vec<String>, AST)>,
+    /// Functions currently being expanded, used to reject recursion.
+    pub call_stack: Vec<String>,
 }
 
 impl ResolverContext {
     fn handle_assignment(&mut self, var: String, ast: Box<AST>) {
         match *ast {
-            AST::Cel(exp) => {
+            AST::Cel(exp, _) => {
                 use cel_interpreter::objects::CelType;
                 match self.eval_cel(exp) {
                     CelType::UInt(n) => {
@@ -94,6 +119,59 @@ impl ResolverContext {
             }
         }
 
+        for (name, op) in CEL_MATH_FUNCTIONS {
+            let op = *op;
+            ctx.add_function(
+                name.to_string(),
+                Box::new(
+                    // The interpreter resolves the first argument of a call
+                    // and passes it as the target.
+                    move |target: Option<&objects::CelType>,
+                          _: &[cel_parser::ast::Expression],
+                          _: &context::Context| match target {
+                        Some(objects::CelType::Float(n)) => objects::CelType::Float(op(*n)),
+                        Some(objects::CelType::Int(n)) => objects::CelType::Float(op(*n as f64)),
+                        Some(objects::CelType::UInt(n)) => objects::CelType::Float(op(*n as f64)),
+                        _ => objects::CelType::Null,
+                    },
+                ),
+            );
+        }
+
+        // Loops are iterated by eval_range(), but the range is also exposed
+        // as a list so that it passes the checks made while parsing.
+        ctx.add_function(
+            "range".to_string(),
+            Box::new(
+                |target: Option<&objects::CelType>,
+                 args: &[cel_parser::ast::Expression],
+                 ctx: &context::Context| {
+                    let int = |v: &objects::CelType| match v {
+                        objects::CelType::Int(n) => Some(*n as i64),
+                        objects::CelType::UInt(n) => Some(*n as i64),
+                        objects::CelType::Float(n) => Some(*n as i64),
+                        _ => None,
+                    };
+                    let bounds = match (target.and_then(int), args.first()) {
+                        (Some(end), None) => Some((0, end)),
+                        (Some(start), Some(end)) => {
+                            int(&objects::CelType::resolve(end, ctx)).map(|end| (start, end))
+                        }
+                        _ => None,
+                    };
+                    match bounds {
+                        Some((start, end)) => objects::CelType::List(
+                            (start..end)
+                                .map(|n| objects::CelType::Int(n as i32))
+                                .collect::<Vec<_>>()
+                                .into(),
+                        ),
+                        None => objects::CelType::Null,
+                    }
+                },
+            ),
+        );
+
         ctx
     }
 
@@ -107,40 +185,357 @@ impl ResolverContext {
             Err(e) => panic!("{}", e), // Should never panic: we checked while parsing
         }
     }
+
+    /// Substitutes each `$ident` in a feature name with the value of
+    /// the numeric variable it references.
+    fn interpolate_name(&self, name: &str) -> Result<String, Err> {
+        let mut parts = name.split('$');
+        let mut out = parts.next().unwrap_or_default().to_string();
+        for part in parts {
+            let end = part.find(|c| !is_ident_char(c)).unwrap_or(part.len());
+            let (ident, rest) = part.split_at(end);
+            out.push_str(&Value::Ref(ident.to_string()).rfloat(self)?.to_string());
+            out.push_str(rest);
+        }
+        Ok(out)
+    }
+
+    /// Evaluates the range iterated over by a loop. Ranges are written as
+    /// `range(start, end)` or `range(end)`, where each bound is a CEL
+    /// expression. Any other expression is treated as the end of a range
+    /// starting at zero.
+    fn eval_range(&self, exp: String) -> Result<std::ops::Range<i64>, Err> {
+        let bound = |exp: &str| -> Result<i64, Err> {
+            if cel_interpreter::Program::compile(exp).is_err() {
+                return Err(Err::parse(format!("invalid range bound: {:?}", exp)));
+            }
+            Ok(Value::Cel(exp.to_string()).rfloat(self)? as i64)
+        };
+
+        let exp = exp.trim();
+        let args = match exp.strip_prefix("range(").and_then(|s| s.strip_suffix(')')) {
+            Some(args) => args,
+            None => return Ok(0..bound(exp)?),
+        };
+
+        // Bounds are separated by the first comma not nested in brackets.
+        let mut depth = 0;
+        let split = args.char_indices().find(|(_, c)| {
+            match c {
+                '(' | '[' => depth += 1,
+                ')' | ']' => depth -= 1,
+                _ => {}
+            };
+            *c == ',' && depth == 0
+        });
+        Ok(match split {
+            Some((idx, _)) => bound(&args[..idx])?..bound(&args[idx + 1..])?,
+            None => 0..bound(args)?,
+        })
+    }
+
+    fn eval_condition(&self, exp: String) -> Result<bool, Err> {
+        use cel_interpreter::objects::CelType;
+        // Variables are bound as floats, and the interpreter orders values
+        // of different types by their type rather than their value, so
+        // literals are promoted to floats before comparing.
+        let parsed = match cel_parser::parser::ExpressionParser::new().parse(&exp) {
+            Ok(parsed) => cel_float_literals(parsed),
+            Err(e) => panic!("{}", e), // Should never panic: we checked while parsing
+        };
+        match CelType::resolve(&parsed, &self.cel_ctx()) {
+            CelType::Bool(b) => Ok(b),
+            CelType::Int(n) => Ok(n != 0),
+            CelType::UInt(n) => Ok(n != 0),
+            CelType::Float(n) => Ok(n != 0.),
+            _ => Err(Err::BadType(exp)),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub enum Err {
-    Parse(String),
+    /// The spec could not be parsed. The line and column are 1-based, and
+    /// are zero if the error is not associated with a position in the spec.
+    Parse {
+        message: String,
+        line: usize,
+        col: usize,
+        snippet: String,
+    },
     UndefinedVariable(String),
     BadType(String),
+    UndefinedFunction(String),
+    RecursiveFunction(String),
+    BadArgumentCount {
+        function: String,
+        expected: usize,
+        got: usize,
+    },
+}
+
+impl std::fmt::Display for Err {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Err::Parse {
+                message, line: 0, ..
+            } => write!(f, "parse error: {}", message),
+            Err::Parse {
+                message,
+                line,
+                col,
+                snippet,
+            } => write!(
+                f,
+                "parse error: line {}, column {}: {}\n{}",
+                line, col, message, snippet
+            ),
+            Err::UndefinedVariable(ident) => write!(f, "undefined variable: {}", ident),
+            Err::BadType(ident) => write!(f, "unexpected type for {}", ident),
+            Err::UndefinedFunction(ident) => write!(f, "undefined function: {}", ident),
+            Err::RecursiveFunction(ident) => {
+                write!(f, "function {} cannot call itself recursively", ident)
+            }
+            Err::BadArgumentCount {
+                function,
+                expected,
+                got,
+            } => write!(
+                f,
+                "function {} takes {} arguments but {} were given",
+                function, expected, got
+            ),
+        }
+    }
+}
+
+impl Err {
+    /// Returns a parse error which is not associated with a position in
+    /// the spec.
+    fn parse(message: String) -> Self {
+        Err::Parse {
+            message,
+            line: 0,
+            col: 0,
+            snippet: String::new(),
+        }
+    }
+
+    /// Returns a parse error at the given byte offset into the spec, with a
+    /// snippet of the offending line and a caret pointing at the error.
+    fn parse_at(spec: &str, offset: usize, message: String) -> Self {
+        let offset = offset.min(spec.len());
+        let line_start = spec[..offset].rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let line_end = spec[offset..]
+            .find('\n')
+            .map(|i| offset + i)
+            .unwrap_or(spec.len());
+        let col = spec[line_start..offset].chars().count() + 1;
+
+        Err::Parse {
+            message,
+            line: spec[..offset].matches('\n').count() + 1,
+            col,
+            snippet: format!("{}\n{}^", &spec[line_start..line_end], " ".repeat(col - 1)),
+        }
+    }
+
+    fn from_nom(spec: &str, e: nom::Err<VerboseError<&str>>) -> Self {
+        use nom::error::VerboseErrorKind;
+        let e = match e {
+            nom::Err::Error(e) | nom::Err::Failure(e) => e,
+            _ => unreachable!(),
+        };
+
+        // The first error is the innermost, so is closest to the
+        // offending text. Leading whitespace is skipped so the error
+        // points at the start of a statement rather than the line before.
+        let offset = match e.errors.first() {
+            Some((remaining, _)) => spec.len() - remaining.trim_start().len(),
+            None => spec.len(),
+        };
+        let message = e
+            .errors
+            .iter()
+            .find_map(|(_, kind)| match kind {
+                VerboseErrorKind::Context(ctx) => Some(format!("invalid {}", ctx)),
+                _ => None,
+            })
+            .or_else(|| match e.errors.first() {
+                Some((_, VerboseErrorKind::Char(c))) => Some(format!("expected '{}'", c)),
+                _ => None,
+            })
+            .unwrap_or_else(|| "unexpected input".to_string());
+        Err::parse_at(spec, offset, message)
+    }
+}
+
+impl std::error::Error for Err {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        None
+    }
 }
 
 #[derive(Debug, Clone)]
 pub enum InnerAST {
     ScrewHole(Value),
+    CounterSunkScrewHole {
+        dia: Value,
+        angle: Value,
+    },
     Smiley,
-    MechanicalSolderPoint(Option<(Value, Value)>),
+    Drill {
+        dia: Value,
+        plated: bool,
+    },
+    MechanicalSolderPoint {
+        size: Option<(Value, Value)>,
+        plated: bool,
+        paste: bool,
+    },
+    Line {
+        start: (Value, Value),
+        end: (Value, Value),
+        width: Value,
+    },
+    Slot {
+        start: (Value, Value),
+        end: (Value, Value),
+        width: Value,
+    },
+    Fiducial(Option<(Value, Value)>),
+    Via {
+        coords: (Value, Value),
+        dia: Value,
+        ring: Option<Value>,
+    },
+    Text {
+        coords: (Value, Value),
+        text: String,
+        size: Option<Value>,
+    },
+    Pad {
+        coords: (Value, Value),
+        size: (Value, Value),
+        back: bool,
+    },
 }
 
 impl InnerAST {
     fn into_inner_feature<'a>(
         self,
-        _ctx: &mut ResolverContext,
-    ) -> Box<dyn super::features::InnerFeature + 'a> {
-        use super::features::{MechanicalSolderPoint, ScrewHole, Smiley};
-
-        match self {
-            InnerAST::ScrewHole(dia) => Box::new(ScrewHole::with_diameter(dia.float())),
+        ctx: &mut ResolverContext,
+    ) -> Result<Box<dyn super::features::InnerFeature + 'a>, Err> {
+        use super::features::{
+            Drill, Fiducial, MechanicalSolderPoint, PlatedSlot, SMDPad, ScrewHole, SilkscreenLine,
+            SilkscreenText, Smiley, ViaHole,
+        };
+
+        Ok(match self {
+            InnerAST::ScrewHole(dia) => Box::new(ScrewHole::with_diameter(dia.rfloat(ctx)?)),
+            InnerAST::CounterSunkScrewHole { dia, angle } => {
+                let dia = dia.rfloat(ctx)?;
+                Box::new(
+                    ScrewHole::with_diameter(dia).with_counter_sink(angle.rfloat(ctx)?, 2. * dia),
+                )
+            }
             InnerAST::Smiley => Box::new(Smiley::default()),
-            InnerAST::MechanicalSolderPoint(sz) => Box::new(match sz {
-                Some((x, y)) => MechanicalSolderPoint::with_size((x.float(), y.float())),
-                None => MechanicalSolderPoint::default(),
+            InnerAST::Drill { dia, plated } => {
+                let dia = dia.rfloat(ctx)?;
+                Box::new(match plated {
+                    true => Drill::plated(dia),
+                    false => Drill::new(dia),
+                })
+            }
+            InnerAST::MechanicalSolderPoint {
+                size,
+                plated,
+                paste,
+            } => {
+                let mut msp = match size {
+                    Some((x, y)) => {
+                        MechanicalSolderPoint::with_size((x.rfloat(ctx)?, y.rfloat(ctx)?))
+                    }
+                    None => MechanicalSolderPoint::default(),
+                };
+                if !plated {
+                    msp = msp.non_plated();
+                }
+                if !paste {
+                    msp = msp.no_paste();
+                }
+                if let Some(layer) = &ctx.default_layer {
+                    msp = msp.single_sided(layer.clone());
+                }
+                Box::new(msp)
+            }
+            InnerAST::Line { start, end, width } => Box::new(SilkscreenLine::new(
+                [start.0.rfloat(ctx)?, start.1.rfloat(ctx)?].into(),
+                [end.0.rfloat(ctx)?, end.1.rfloat(ctx)?].into(),
+                width.rfloat(ctx)?,
+            )),
+            InnerAST::Slot { start, end, width } => Box::new(PlatedSlot::new(
+                [start.0.rfloat(ctx)?, start.1.rfloat(ctx)?].into(),
+                [end.0.rfloat(ctx)?, end.1.rfloat(ctx)?].into(),
+                width.rfloat(ctx)?,
+            )),
+            InnerAST::Fiducial(coords) => Box::new(match coords {
+                Some((x, y)) => Fiducial::new([x.rfloat(ctx)?, y.rfloat(ctx)?].into()),
+                None => Fiducial::default(),
             }),
-        }
+            InnerAST::Via { coords, dia, ring } => {
+                let via = ViaHole::new(
+                    [coords.0.rfloat(ctx)?, coords.1.rfloat(ctx)?].into(),
+                    dia.rfloat(ctx)?,
+                );
+                Box::new(match ring {
+                    Some(ring) => via.with_ring(ring.rfloat(ctx)?),
+                    None => via,
+                })
+            }
+            InnerAST::Text { coords, text, size } => {
+                let size = match size {
+                    Some(size) => size.rfloat(ctx)?,
+                    None => 1.,
+                };
+                let text = SilkscreenText::new(
+                    text,
+                    [coords.0.rfloat(ctx)?, coords.1.rfloat(ctx)?].into(),
+                    size,
+                );
+                Box::new(match ctx.default_layer.as_ref().and_then(|l| l.side()) {
+                    Some(crate::Side::Back) => text.on_layer(crate::Layer::BackLegend),
+                    _ => text,
+                })
+            }
+            InnerAST::Pad { coords, size, back } => {
+                let pad = SMDPad::new(
+                    [coords.0.rfloat(ctx)?, coords.1.rfloat(ctx)?].into(),
+                    size.0.rfloat(ctx)?,
+                    size.1.rfloat(ctx)?,
+                );
+                // Pads follow the default layer unless placed explicitly.
+                let side = match back {
+                    true => Some(crate::Side::Back),
+                    false => ctx.default_layer.as_ref().and_then(|l| l.side()),
+                };
+                Box::new(match side {
+                    Some(side) => pad.on_side(side),
+                    None => pad,
+                })
+            }
+        })
     }
 }
 
+/// The shape of a triangle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TriangleKind {
+    RightAngle,
+    Isoceles,
+    Equilateral,
+}
+
 #[derive(Debug, Clone)]
 pub enum WrapPosition {
     Cardinal {
@@ -157,147 +552,542 @@ pub enum WrapPosition {
         angle: Value,
         offset: Value,
     },
+    Absolute {
+        x: Value,
+        y: Value,
+    },
+    Distribute {
+        side: Direction,
+        count: usize,
+    },
+    Stack {
+        side: Direction,
+        gap: Option<Value>,
+    },
 }
 
 impl WrapPosition {
-    fn into_positioning(self, r: &ResolverContext) -> Result<crate::features::Positioning, Err> {
+    fn into_positionings(
+        self,
+        r: &ResolverContext,
+    ) -> Result<Vec<crate::features::Positioning>, Err> {
         match self {
             WrapPosition::Cardinal {
                 side,
                 offset,
                 align,
-            } => Ok(crate::features::Positioning::Cardinal {
+            } => Ok(vec![crate::features::Positioning::Cardinal {
                 side,
                 align,
                 centerline_adjustment: offset.rfloat(r)?,
-            }),
+            }]),
             WrapPosition::Corner {
                 side,
                 opposite,
                 align,
-            } => Ok(crate::features::Positioning::Corner {
+            } => Ok(vec![crate::features::Positioning::Corner {
                 side,
                 align,
                 opposite,
-            }),
-            WrapPosition::Angle { angle, offset } => Ok(crate::features::Positioning::Angle {
-                degrees: angle.rfloat(r)?,
-                amount: offset.rfloat(r)?,
-            }),
+            }]),
+            WrapPosition::Angle { angle, offset } => {
+                Ok(vec![crate::features::Positioning::Angle {
+                    degrees: angle.rfloat(r)?,
+                    amount: offset.rfloat(r)?,
+                }])
+            }
+            WrapPosition::Absolute { x, y } => Ok(vec![crate::features::Positioning::AbsoluteXY {
+                x: x.rfloat(r)?,
+                y: y.rfloat(r)?,
+            }]),
+            WrapPosition::Distribute { side, count } => {
+                Ok(crate::features::Positioning::distributed(side, count))
+            }
+            WrapPosition::Stack { side, gap } => Ok(vec![crate::features::Positioning::Stack {
+                side,
+                gap: match gap {
+                    Some(g) => g.rfloat(r)?,
+                    None => 0.,
+                },
+            }]),
         }
     }
 }
 
+/// Byte offsets (start, end) of a node within the parsed spec.
+pub type Span = (usize, usize);
+
+/// A node in the syntax tree of a panel spec.
 #[derive(Debug, Clone)]
 pub enum AST {
-    Assign(String, Box<AST>),
-    VarRef(String),
-    Comment(String),
-    Cel(String),
+    Assign(String, Box<AST>, Span),
+    VarRef(String, Span),
+    Comment(String, Span),
+    Import(String, Span),
+    Cel(String, Span),
+    FnDef {
+        name: String,
+        params: Vec<String>,
+        body: Box<AST>,
+        span: Span,
+    },
+    FnCall {
+        name: String,
+        args: Vec<Value>,
+        span: Span,
+    },
+    Conditional {
+        condition: String,
+        then_branch: Vec<Box<AST>>,
+        else_branch: Vec<Box<AST>>,
+        span: Span,
+    },
+    ForLoop {
+        var: String,
+        range_expr: String,
+        body: Vec<Box<AST>>,
+        span: Span,
+    },
     Rect {
         coords: Option<(Value, Value)>,
         size: Option<(Value, Value)>,
         inner: Option<InnerAST>,
         rounded: Option<Value>,
+        span: Span,
     },
     Circle {
         coords: Option<(Value, Value)>,
         radius: Value,
         inner: Option<InnerAST>,
+        span: Span,
+    },
+    Ellipse {
+        coords: Option<(Value, Value)>,
+        radii: (Value, Value),
+        inner: Option<InnerAST>,
+        span: Span,
+    },
+    RegularPolygon {
+        coords: Option<(Value, Value)>,
+        sides: Value,
+        radius: Value,
+        inner: Option<InnerAST>,
+        span: Span,
     },
     Triangle {
         size: (Value, Value),
+        kind: TriangleKind,
         inner: Option<InnerAST>,
+        span: Span,
+    },
+    Trapezoid {
+        coords: Option<(Value, Value)>,
+        top: Value,
+        bottom: Value,
+        height: Value,
+        inner: Option<InnerAST>,
+        span: Span,
     },
     RMount {
         depth: Value,
         dir: crate::Direction,
+        span: Span,
+    },
+    MouseBite {
+        coords: Option<(Value, Value)>,
+        length: Value,
+        hole_diameter: Value,
+        spacing: Option<Value>,
+        dir: crate::Direction,
+        span: Span,
+    },
+    Tab {
+        width: Value,
+        length: Value,
+        dir: crate::Direction,
+        span: Span,
+    },
+    Castellation {
+        count: Value,
+        hole_diameter: Value,
+        side: crate::Direction,
+        plated: bool,
+        span: Span,
+    },
+    Bezier {
+        points: Vec<(Value, Value)>,
+        span: Span,
+    },
+    GoldFingers {
+        count: Value,
+        pitch: Value,
+        pad_size: (Value, Value),
+        double_sided: bool,
+        span: Span,
+    },
+    Sector {
+        coords: Option<(Value, Value)>,
+        radius: Value,
+        angles: (Value, Value),
+        span: Span,
     },
     Array {
         dir: crate::Direction,
         num: usize,
         inner: Box<AST>,
         vscore: bool,
+        flip: bool,
+        gap: Option<Value>,
+        span: Span,
+    },
+    CircularArray {
+        count: usize,
+        radius: Value,
+        start_angle: Option<Value>,
+        inner: Box<AST>,
+        span: Span,
+    },
+    Grid {
+        rows: usize,
+        cols: usize,
+        gap: Option<Value>,
+        inner: Box<AST>,
+        span: Span,
     },
     ColumnLayout {
+        coords: Option<(Value, Value)>,
+        align: crate::Align,
+        gap: Option<Value>,
+        inners: Vec<Box<AST>>,
+        span: Span,
+    },
+    RowLayout {
         coords: Option<(Value, Value)>,
         align: crate::Align,
         inners: Vec<Box<AST>>,
+        span: Span,
     },
     Wrap {
         inner: Box<AST>,
         features: Vec<(WrapPosition, Box<AST>)>,
+        span: Span,
     },
     Tuple {
         inners: Vec<Box<AST>>,
+        span: Span,
     },
     Negative {
         inners: Vec<Box<AST>>,
+        span: Span,
     },
     Rotate {
         rotation: Value,
         inners: Vec<Box<AST>>,
+        span: Span,
+    },
+    Mirror {
+        axis: crate::features::MirrorAxis,
+        inners: Vec<Box<AST>>,
+        span: Span,
+    },
+    Intersect {
+        left: Box<AST>,
+        right: Box<AST>,
+        span: Span,
+    },
+    Offset {
+        amount: Value,
+        inner: Box<AST>,
+        span: Span,
+    },
+    Translate {
+        offset: (Value, Value),
+        inners: Vec<Box<AST>>,
+        span: Span,
     },
     Name {
         name: String,
         inner: Box<AST>,
+        span: Span,
     },
 }
 
 impl AST {
+    /// Returns the byte offsets of the text this node was parsed from.
+    pub fn span(&self) -> Span {
+        match self {
+            AST::Assign(_, _, span)
+            | AST::VarRef(_, span)
+            | AST::Comment(_, span)
+            | AST::Import(_, span)
+            | AST::Cel(_, span)
+            | AST::FnDef { span, .. }
+            | AST::FnCall { span, .. }
+            | AST::Conditional { span, .. }
+            | AST::ForLoop { span, .. }
+            | AST::Rect { span, .. }
+            | AST::Circle { span, .. }
+            | AST::Ellipse { span, .. }
+            | AST::RegularPolygon { span, .. }
+            | AST::Triangle { span, .. }
+            | AST::Trapezoid { span, .. }
+            | AST::RMount { span, .. }
+            | AST::MouseBite { span, .. }
+            | AST::Tab { span, .. }
+            | AST::Castellation { span, .. }
+            | AST::Bezier { span, .. }
+            | AST::GoldFingers { span, .. }
+            | AST::Sector { span, .. }
+            | AST::Array { span, .. }
+            | AST::Grid { span, .. }
+            | AST::CircularArray { span, .. }
+            | AST::ColumnLayout { span, .. }
+            | AST::RowLayout { span, .. }
+            | AST::Wrap { span, .. }
+            | AST::Tuple { span, .. }
+            | AST::Negative { span, .. }
+            | AST::Rotate { span, .. }
+            | AST::Mirror { span, .. }
+            | AST::Intersect { span, .. }
+            | AST::Offset { span, .. }
+            | AST::Translate { span, .. }
+            | AST::Name { span, .. } => *span,
+        }
+    }
+
+    fn span_mut(&mut self) -> &mut Span {
+        match self {
+            AST::Assign(_, _, span)
+            | AST::VarRef(_, span)
+            | AST::Comment(_, span)
+            | AST::Import(_, span)
+            | AST::Cel(_, span)
+            | AST::FnDef { span, .. }
+            | AST::FnCall { span, .. }
+            | AST::Conditional { span, .. }
+            | AST::ForLoop { span, .. }
+            | AST::Rect { span, .. }
+            | AST::Circle { span, .. }
+            | AST::Ellipse { span, .. }
+            | AST::RegularPolygon { span, .. }
+            | AST::Triangle { span, .. }
+            | AST::Trapezoid { span, .. }
+            | AST::RMount { span, .. }
+            | AST::MouseBite { span, .. }
+            | AST::Tab { span, .. }
+            | AST::Castellation { span, .. }
+            | AST::Bezier { span, .. }
+            | AST::GoldFingers { span, .. }
+            | AST::Sector { span, .. }
+            | AST::Array { span, .. }
+            | AST::Grid { span, .. }
+            | AST::CircularArray { span, .. }
+            | AST::ColumnLayout { span, .. }
+            | AST::RowLayout { span, .. }
+            | AST::Wrap { span, .. }
+            | AST::Tuple { span, .. }
+            | AST::Negative { span, .. }
+            | AST::Rotate { span, .. }
+            | AST::Mirror { span, .. }
+            | AST::Intersect { span, .. }
+            | AST::Offset { span, .. }
+            | AST::Translate { span, .. }
+            | AST::Name { span, .. } => span,
+        }
+    }
+
+    // While parsing, spans are recorded as the length of the input remaining
+    // at the start and end of the node, as the parsers only see the
+    // unconsumed suffix of the spec. This converts them into offsets from
+    // the start of a spec of the given length.
+    fn rebase_span(&mut self, len: usize) {
+        let span = self.span_mut();
+        *span = (len - span.0, len - span.1);
+
+        for child in self.children_mut() {
+            child.rebase_span(len);
+        }
+    }
+
+    /// Returns the nodes directly contained within this node.
+    fn children_mut(&mut self) -> Vec<&mut AST> {
+        match self {
+            AST::Assign(_, inner, _)
+            | AST::Array { inner, .. }
+            | AST::Grid { inner, .. }
+            | AST::CircularArray { inner, .. }
+            | AST::Offset { inner, .. }
+            | AST::Name { inner, .. }
+            | AST::FnDef { body: inner, .. } => vec![inner.as_mut()],
+            AST::ColumnLayout { inners, .. }
+            | AST::RowLayout { inners, .. }
+            | AST::Tuple { inners, .. }
+            | AST::Negative { inners, .. }
+            | AST::Rotate { inners, .. }
+            | AST::Mirror { inners, .. }
+            | AST::Translate { inners, .. }
+            | AST::ForLoop { body: inners, .. } => inners.iter_mut().map(|i| i.as_mut()).collect(),
+            AST::Intersect { left, right, .. } => vec![left.as_mut(), right.as_mut()],
+            AST::Conditional {
+                then_branch,
+                else_branch,
+                ..
+            } => then_branch
+                .iter_mut()
+                .chain(else_branch.iter_mut())
+                .map(|i| i.as_mut())
+                .collect(),
+            AST::Wrap {
+                inner, features, ..
+            } => std::iter::once(inner.as_mut())
+                .chain(features.iter_mut().map(|(_, f)| f.as_mut()))
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    /// Appends the given index to the names of this node and any named
+    /// nodes it contains. Names which reference a variable are left
+    /// as-is, as they are made unique by substitution instead.
+    fn name_index(&mut self, idx: usize) {
+        if let AST::Name { name, .. } = self {
+            if !name.contains('$') {
+                name.push_str(&idx.to_string());
+            }
+        }
+        for child in self.children_mut() {
+            child.name_index(idx);
+        }
+    }
+
+    /// Resolves the node into the features it produces, which may be
+    /// none for a conditional whose selected branch is empty.
+    fn into_features<'a>(
+        self,
+        ctx: &mut ResolverContext,
+    ) -> Result<Vec<Box<dyn super::Feature + 'a>>, Err> {
+        match self {
+            AST::Conditional {
+                condition,
+                then_branch,
+                else_branch,
+                ..
+            } => {
+                let branch = if ctx.eval_condition(condition)? {
+                    then_branch
+                } else {
+                    else_branch
+                };
+                let mut out = Vec::with_capacity(branch.len());
+                for inner in branch.into_iter() {
+                    out.append(&mut inner.into_features(ctx)?);
+                }
+                Ok(out)
+            }
+            AST::ForLoop {
+                var,
+                range_expr,
+                body,
+                ..
+            } => {
+                let mut out = Vec::new();
+                for (idx, n) in ctx.eval_range(range_expr)?.enumerate() {
+                    let mut iter_ctx = ctx.clone();
+                    iter_ctx
+                        .definitions
+                        .insert(var.clone(), Variable::Number(n as f64));
+                    for inner in body.iter() {
+                        let mut inner = inner.clone();
+                        inner.name_index(idx);
+                        out.append(&mut inner.into_features(&mut iter_ctx)?);
+                    }
+                }
+                Ok(out)
+            }
+            AST::Translate { offset, inners, .. } => {
+                use crate::features::Feature;
+                let v: geo::Coordinate<f64> = [offset.0.rfloat(ctx)?, offset.1.rfloat(ctx)?].into();
+                let mut out = Vec::with_capacity(inners.len());
+                for inner in inners.into_iter() {
+                    out.append(&mut inner.into_features(ctx)?);
+                }
+                for f in out.iter_mut() {
+                    f.translate(v);
+                }
+                Ok(out)
+            }
+            AST::Comment(..) => Ok(vec![]),
+            _ => Ok(vec![self.into_feature(ctx)?]),
+        }
+    }
+
     fn into_feature<'a>(
         self,
         ctx: &mut ResolverContext,
     ) -> Result<Box<dyn super::Feature + 'a>, Err> {
-        use super::features::{Circle, RMount, Rect, Triangle};
+        use super::features::{
+            Castellation, Circle, Ellipse, MouseBite, RMount, Rect, RegularPolygon, Tab, Trapezoid,
+            Triangle,
+        };
 
         match self {
             AST::Rect {
                 coords,
                 size,
                 inner,
-                rounded: _,
-            } => Ok(if let Some(inner) = inner {
-                let r = Rect::with_inner(inner.into_inner_feature(ctx));
-                let (w, h) = if let Some((w, h)) = size {
-                    (w.rfloat(ctx)?, h.rfloat(ctx)?)
-                } else {
-                    (2., 2.)
+                rounded,
+                ..
+            } => {
+                let rounding = match rounded {
+                    Some(r) => r.rfloat(ctx)?,
+                    None => 0.,
                 };
-                let r = if let Some((x, y)) = coords {
-                    r.dimensions((x.rfloat(ctx)?, y.rfloat(ctx)?).into(), w, h)
+                Ok(if let Some(inner) = inner {
+                    let r = Rect::with_inner(inner.into_inner_feature(ctx)?);
+                    let (w, h) = if let Some((w, h)) = size {
+                        (w.rfloat(ctx)?, h.rfloat(ctx)?)
+                    } else {
+                        (2., 2.)
+                    };
+                    let r = if let Some((x, y)) = coords {
+                        r.dimensions((x.rfloat(ctx)?, y.rfloat(ctx)?).into(), w, h)
+                    } else {
+                        r.dimensions([0., 0.].into(), w, h)
+                    };
+                    Box::new(r.rounded(rounding))
                 } else {
-                    r.dimensions([0., 0.].into(), w, h)
-                };
-                Box::new(r)
-            } else {
-                Box::new(match (coords, size) {
-                    (Some((x, y)), Some((w, h))) => Rect::with_center(
-                        (x.rfloat(ctx)?, y.rfloat(ctx)?).into(),
-                        w.rfloat(ctx)?,
-                        h.rfloat(ctx)?,
-                    ),
-                    (None, Some((w, h))) => {
-                        Rect::with_center([0., 0.].into(), w.rfloat(ctx)?, h.rfloat(ctx)?)
-                    }
-                    (Some((x, y)), None) => {
-                        Rect::with_center((x.rfloat(ctx)?, y.rfloat(ctx)?).into(), 2., 2.)
-                    }
-                    (None, None) => Rect::with_center([-1f64, -1f64].into(), 2., 2.),
+                    Box::new(
+                        match (coords, size) {
+                            (Some((x, y)), Some((w, h))) => Rect::with_center(
+                                (x.rfloat(ctx)?, y.rfloat(ctx)?).into(),
+                                w.rfloat(ctx)?,
+                                h.rfloat(ctx)?,
+                            ),
+                            (None, Some((w, h))) => {
+                                Rect::with_center([0., 0.].into(), w.rfloat(ctx)?, h.rfloat(ctx)?)
+                            }
+                            (Some((x, y)), None) => {
+                                Rect::with_center((x.rfloat(ctx)?, y.rfloat(ctx)?).into(), 2., 2.)
+                            }
+                            (None, None) => Rect::with_center([-1f64, -1f64].into(), 2., 2.),
+                        }
+                        .rounded(rounding),
+                    )
                 })
-            }),
+            }
             AST::Circle {
                 coords,
                 radius,
                 inner,
+                ..
             } => Ok(match (inner, coords) {
                 (Some(i), Some((x, y))) => Box::new(Circle::with_inner(
-                    i.into_inner_feature(ctx),
+                    i.into_inner_feature(ctx)?,
                     (x.rfloat(ctx)?, y.rfloat(ctx)?).into(),
                     radius.rfloat(ctx)?,
                 )),
                 (Some(i), None) => Box::new(Circle::wrap_with_radius(
-                    i.into_inner_feature(ctx),
+                    i.into_inner_feature(ctx)?,
                     radius.rfloat(ctx)?,
                 )),
                 (None, Some((x, y))) => Box::new(Circle::new(
@@ -306,33 +1096,249 @@ impl AST {
                 )),
                 (None, None) => Box::new(Circle::with_radius(radius.rfloat(ctx)?)),
             }),
-            AST::Triangle { size, inner } => Ok(match inner {
-                Some(i) => Box::new(Triangle::with_inner(i.into_inner_feature(ctx)).dimensions(
-                    [0., 0.].into(),
-                    size.0.rfloat(ctx)?,
-                    size.1.rfloat(ctx)?,
-                )),
-                None => Box::new(Triangle::right_angle(
-                    size.0.rfloat(ctx)?,
-                    size.1.rfloat(ctx)?,
-                )),
-            }),
-            AST::RMount { depth, dir } => {
+            AST::Ellipse {
+                coords,
+                radii: (a, b),
+                inner,
+                ..
+            } => {
+                let (a, b) = (a.rfloat(ctx)?, b.rfloat(ctx)?);
+                Ok(match (inner, coords) {
+                    (Some(i), Some((x, y))) => Box::new(Ellipse::with_inner(
+                        i.into_inner_feature(ctx)?,
+                        (x.rfloat(ctx)?, y.rfloat(ctx)?).into(),
+                        a,
+                        b,
+                    )),
+                    (Some(i), None) => {
+                        Box::new(Ellipse::wrap_with_radii(i.into_inner_feature(ctx)?, a, b))
+                    }
+                    (None, Some((x, y))) => {
+                        Box::new(Ellipse::new((x.rfloat(ctx)?, y.rfloat(ctx)?).into(), a, b))
+                    }
+                    (None, None) => Box::new(Ellipse::with_radii(a, b)),
+                })
+            }
+            AST::RegularPolygon {
+                coords,
+                sides,
+                radius,
+                inner,
+                ..
+            } => {
+                let (sides, r) = (sides.rfloat(ctx)? as u32, radius.rfloat(ctx)?);
+                let center = match coords {
+                    Some((x, y)) => (x.rfloat(ctx)?, y.rfloat(ctx)?).into(),
+                    None => [0., 0.].into(),
+                };
+                Ok(match inner {
+                    Some(i) => Box::new(RegularPolygon::with_inner(
+                        i.into_inner_feature(ctx)?,
+                        center,
+                        sides,
+                        r,
+                    )),
+                    None => Box::new(RegularPolygon::new(center, sides, r)),
+                })
+            }
+            AST::MouseBite {
+                coords,
+                length,
+                hole_diameter,
+                spacing,
+                dir,
+                ..
+            } => {
+                let mut mb =
+                    MouseBite::new(length.rfloat(ctx)?, hole_diameter.rfloat(ctx)?).direction(dir);
+                if let Some((x, y)) = coords {
+                    mb = mb.at((x.rfloat(ctx)?, y.rfloat(ctx)?).into());
+                }
+                if let Some(spacing) = spacing {
+                    mb = mb.spacing(spacing.rfloat(ctx)?);
+                }
+                Ok(Box::new(mb))
+            }
+            AST::Tab {
+                width, length, dir, ..
+            } => Ok(Box::new(
+                Tab::new(width.rfloat(ctx)?, length.rfloat(ctx)?).direction(dir),
+            )),
+            AST::Castellation {
+                count,
+                hole_diameter,
+                side,
+                plated,
+                ..
+            } => {
+                let c = Castellation::new(count.rfloat(ctx)? as usize, hole_diameter.rfloat(ctx)?)
+                    .side(side);
+                Ok(Box::new(if plated { c } else { c.non_plated() }))
+            }
+            AST::Triangle {
+                size, kind, inner, ..
+            } => {
+                let (x, y) = (size.0.rfloat(ctx)?, size.1.rfloat(ctx)?);
+                Ok(match (inner, kind) {
+                    (Some(i), TriangleKind::RightAngle) => {
+                        Box::new(Triangle::with_inner(i.into_inner_feature(ctx)?).dimensions(
+                            [0., 0.].into(),
+                            x,
+                            y,
+                        ))
+                    }
+                    (Some(i), TriangleKind::Isoceles) => Box::new(
+                        Triangle::with_inner(i.into_inner_feature(ctx)?).isoceles_dimensions(
+                            [0., 0.].into(),
+                            x,
+                            y,
+                        ),
+                    ),
+                    (Some(i), TriangleKind::Equilateral) => Box::new(
+                        Triangle::with_inner(i.into_inner_feature(ctx)?)
+                            .equilateral_dimensions([0., 0.].into(), x),
+                    ),
+                    (None, TriangleKind::RightAngle) => Box::new(Triangle::right_angle(x, y)),
+                    (None, TriangleKind::Isoceles) => Box::new(Triangle::isoceles(x, y)),
+                    (None, TriangleKind::Equilateral) => Box::new(Triangle::equilateral(x)),
+                })
+            }
+            AST::Trapezoid {
+                coords,
+                top,
+                bottom,
+                height,
+                inner,
+                ..
+            } => {
+                let (t, b, h) = (top.rfloat(ctx)?, bottom.rfloat(ctx)?, height.rfloat(ctx)?);
+                let center = match coords {
+                    Some((x, y)) => [x.rfloat(ctx)?, y.rfloat(ctx)?].into(),
+                    None => [0., 0.].into(),
+                };
+                Ok(match inner {
+                    Some(i) => Box::new(Trapezoid::with_inner(
+                        i.into_inner_feature(ctx)?,
+                        center,
+                        t,
+                        b,
+                        h,
+                    )),
+                    None => Box::new(Trapezoid::with_inner(
+                        crate::features::Unit,
+                        center,
+                        t,
+                        b,
+                        h,
+                    )),
+                })
+            }
+            AST::RMount { depth, dir, .. } => {
                 Ok(Box::new(RMount::new(depth.rfloat(ctx)?).direction(dir)))
             }
+            AST::Bezier { points, .. } => Ok(Box::new(crate::features::BezierCurve::fill_region(
+                points
+                    .into_iter()
+                    .map(|(x, y)| Ok([x.rfloat(ctx)?, y.rfloat(ctx)?].into()))
+                    .collect::<Result<Vec<_>, Err>>()?,
+            ))),
+            AST::GoldFingers {
+                count,
+                pitch,
+                pad_size,
+                double_sided,
+                ..
+            } => {
+                let g = crate::features::GoldFinger::new(
+                    count.rfloat(ctx)? as usize,
+                    pitch.rfloat(ctx)?,
+                    pad_size.0.rfloat(ctx)?,
+                    pad_size.1.rfloat(ctx)?,
+                );
+                Ok(if double_sided {
+                    Box::new(g.double_sided())
+                } else {
+                    Box::new(g)
+                })
+            }
+            AST::Sector {
+                coords,
+                radius,
+                angles,
+                ..
+            } => Ok(Box::new(Circle::arc_sector(
+                match coords {
+                    Some((x, y)) => [x.rfloat(ctx)?, y.rfloat(ctx)?].into(),
+                    None => [0., 0.].into(),
+                },
+                radius.rfloat(ctx)?,
+                angles.0.rfloat(ctx)?,
+                angles.1.rfloat(ctx)?,
+            ))),
             AST::Array {
                 dir,
                 num,
                 inner,
                 vscore,
-            } => Ok(Box::new(
-                crate::features::repeating::Tile::new(inner.into_feature(ctx)?, dir, num)
-                    .v_score(vscore),
-            )),
-            AST::ColumnLayout {
-                align,
-                inners,
-                coords,
+                flip,
+                gap,
+                ..
+            } => Ok(Box::new({
+                let mut tile =
+                    crate::features::repeating::Tile::new(inner.into_feature(ctx)?, dir, num)
+                        .v_score(vscore);
+                if let Some(g) = gap {
+                    tile = tile.with_spacing(g.rfloat(ctx)?);
+                }
+                if flip {
+                    tile.alternating_flip(dir)
+                } else {
+                    tile
+                }
+            })),
+            AST::Grid {
+                rows,
+                cols,
+                gap,
+                inner,
+                ..
+            } => {
+                let gap = match gap {
+                    Some(g) => g.rfloat(ctx)?,
+                    None => 0.,
+                };
+                Ok(Box::new(
+                    crate::features::Grid::new(inner.into_feature(ctx)?, rows, cols)
+                        .row_gap(gap)
+                        .col_gap(gap),
+                ))
+            }
+            AST::CircularArray {
+                count,
+                radius,
+                start_angle,
+                inner,
+                ..
+            } => {
+                let start = match start_angle {
+                    Some(a) => a.rfloat(ctx)?,
+                    None => 0.,
+                };
+                Ok(Box::new(
+                    crate::features::CircularArray::new(
+                        inner.into_feature(ctx)?,
+                        count,
+                        radius.rfloat(ctx)?,
+                    )
+                    .start_angle(start),
+                ))
+            }
+            AST::ColumnLayout {
+                align,
+                inners,
+                coords,
+                gap,
+                ..
             } => Ok(Box::new({
                 let mut layout = match align {
                     crate::Align::Start => crate::features::Column::align_left(
@@ -354,20 +1360,49 @@ impl AST {
                             .collect::<Result<Vec<_>, Err>>()?,
                     ),
                 };
+                if let Some(g) = gap {
+                    layout = layout.with_gap(g.rfloat(ctx)?);
+                }
+                if let Some((x, y)) = coords {
+                    use crate::features::Feature;
+                    layout.translate([x.rfloat(ctx)?, y.rfloat(ctx)?].into());
+                };
+                layout
+            })),
+            AST::RowLayout {
+                align,
+                inners,
+                coords,
+                ..
+            } => Ok(Box::new({
+                let inners = inners
+                    .into_iter()
+                    .map(|i| i.into_feature(ctx))
+                    .collect::<Result<Vec<_>, Err>>()?;
+                let mut layout = match align {
+                    crate::Align::Start => crate::features::Row::align_top(inners),
+                    crate::Align::Center => crate::features::Row::align_center(inners),
+                    crate::Align::End => crate::features::Row::align_bottom(inners),
+                };
                 if let Some((x, y)) = coords {
                     use crate::features::Feature;
                     layout.translate([x.rfloat(ctx)?, y.rfloat(ctx)?].into());
                 };
                 layout
             })),
-            AST::Wrap { inner, features } => {
+            AST::Wrap {
+                inner, features, ..
+            } => {
                 let mut pos = crate::features::AtPos::new(inner.into_feature(ctx)?);
                 for (position, feature) in features {
-                    pos.push(feature.into_feature(ctx)?, position.into_positioning(ctx)?);
+                    let feature = feature.into_feature(ctx)?;
+                    for p in position.into_positionings(ctx)? {
+                        pos.push(feature.clone(), p);
+                    }
                 }
                 Ok(Box::new(pos))
             }
-            AST::Tuple { inners } => {
+            AST::Tuple { inners, .. } => {
                 let mut out: Option<Box<dyn super::Feature>> = None;
                 for inner in inners.into_iter() {
                     out = match out {
@@ -389,27 +1424,100 @@ impl AST {
 
                 Ok(out.unwrap())
             }
-            AST::Negative { inners } => Ok(Box::new(crate::features::Negative::new(
+            AST::Offset { amount, inner, .. } => Ok(Box::new(crate::features::Offset::new(
+                amount.rfloat(ctx)?,
+                inner.into_feature(ctx)?,
+            ))),
+            AST::Intersect { left, right, .. } => Ok(Box::new(crate::features::Intersect::new(
+                left.into_feature(ctx)?,
+                right.into_feature(ctx)?,
+            ))),
+            AST::Negative { inners, .. } => Ok(Box::new(crate::features::Negative::new(
                 inners
                     .into_iter()
-                    .map(|f| f.into_feature(ctx))
-                    .collect::<Result<Vec<_>, Err>>()?,
+                    .map(|f| f.into_features(ctx))
+                    .collect::<Result<Vec<_>, Err>>()?
+                    .into_iter()
+                    .flatten()
+                    .collect(),
             ))),
-            AST::Rotate { rotation, inners } => Ok(Box::new(crate::features::Rotate::new(
+            AST::Rotate {
+                rotation, inners, ..
+            } => Ok(Box::new(crate::features::Rotate::new(
                 rotation.rfloat(ctx)?,
                 inners
                     .into_iter()
-                    .map(|f| f.into_feature(ctx))
-                    .collect::<Result<Vec<_>, Err>>()?,
+                    .map(|f| f.into_features(ctx))
+                    .collect::<Result<Vec<_>, Err>>()?
+                    .into_iter()
+                    .flatten()
+                    .collect(),
+            ))),
+            AST::Mirror { axis, inners, .. } => Ok(Box::new(crate::features::Mirror::new(
+                axis,
+                inners
+                    .into_iter()
+                    .map(|f| f.into_features(ctx))
+                    .collect::<Result<Vec<_>, Err>>()?
+                    .into_iter()
+                    .flatten()
+                    .collect(),
             ))),
-            AST::Name { inner, name } => Ok(Box::new(crate::features::Named::new(
-                name,
+            AST::Name { inner, name, .. } => Ok(Box::new(crate::features::Named::new(
+                ctx.interpolate_name(&name)?,
                 inner.into_feature(ctx)?,
             ))),
-            AST::Assign(_, _) => unreachable!(),
-            AST::Comment(_) => unreachable!(),
-            AST::Cel(_) => unreachable!(),
-            AST::VarRef(ident) => match ctx.definitions.get(&ident) {
+            AST::Assign(..) => unreachable!(),
+            AST::Comment(..) => Err(Err::parse(
+                "a comment was found where a feature was expected".to_string(),
+            )),
+            AST::Import(path, _) => Err(Err::parse(format!(
+                "import of {:?} must be a top-level statement",
+                path
+            ))),
+            AST::Cel(..) => unreachable!(),
+            c @ AST::Conditional { .. } | c @ AST::ForLoop { .. } | c @ AST::Translate { .. } => {
+                let mut features = c.into_features(ctx)?;
+                if features.len() != 1 {
+                    return Err(Err::parse(format!(
+                        "{} features were produced where one was expected",
+                        features.len()
+                    )));
+                }
+                Ok(features.remove(0))
+            }
+            AST::FnDef { name, .. } => Err(Err::parse(format!(
+                "definition of function {} must be a top-level statement",
+                name
+            ))),
+            AST::FnCall { name, args, .. } => {
+                let (params, body) = match ctx.functions.get(&name) {
+                    Some(f) => f.clone(),
+                    None => return Err(Err::UndefinedFunction(name)),
+                };
+                if ctx.call_stack.contains(&name) {
+                    return Err(Err::RecursiveFunction(name));
+                }
+                if params.len() != args.len() {
+                    return Err(Err::BadArgumentCount {
+                        function: name,
+                        expected: params.len(),
+                        got: args.len(),
+                    });
+                }
+
+                // Arguments are evaluated in the caller's scope, and bound
+                // in a copy of it so they don't leak out of the call.
+                let mut call_ctx = ctx.clone();
+                for (param, arg) in params.into_iter().zip(args.into_iter()) {
+                    call_ctx
+                        .definitions
+                        .insert(param, Variable::Number(arg.rfloat(ctx)?));
+                }
+                call_ctx.call_stack.push(name);
+                body.into_feature(&mut call_ctx)
+            }
+            AST::VarRef(ident, _) => match ctx.definitions.get(&ident) {
                 Some(var) => match var {
                     Variable::Geo(ast) => ast.clone().into_feature(ctx),
                     _ => Err(Err::BadType(ident)),
@@ -420,6 +1528,110 @@ impl AST {
     }
 }
 
+/// Returns the expression with its integer literals replaced by floats.
+/// Indexes are left as integers, as lists can only be indexed by them.
+fn cel_float_literals(exp: cel_parser::Expression) -> cel_parser::Expression {
+    use cel_parser::{Atom, Expression, Member};
+    let b = |e: Box<Expression>| Box::new(cel_float_literals(*e));
+
+    match exp {
+        Expression::Atom(Atom::Int(n)) => Expression::Atom(Atom::Float(n as f64)),
+        Expression::Atom(Atom::UInt(n)) => Expression::Atom(Atom::Float(n as f64)),
+        Expression::Arithmetic(l, op, r) => Expression::Arithmetic(b(l), op, b(r)),
+        Expression::Relation(l, op, r) => Expression::Relation(b(l), op, b(r)),
+        Expression::Ternary(c, l, r) => Expression::Ternary(b(c), b(l), b(r)),
+        Expression::Or(l, r) => Expression::Or(b(l), b(r)),
+        Expression::And(l, r) => Expression::And(b(l), b(r)),
+        Expression::Unary(op, e) => Expression::Unary(op, b(e)),
+        Expression::List(items) => {
+            Expression::List(items.into_iter().map(cel_float_literals).collect())
+        }
+        Expression::Map(items) => Expression::Map(
+            items
+                .into_iter()
+                .map(|(k, v)| (k, cel_float_literals(v)))
+                .collect(),
+        ),
+        Expression::Member(e, m) => Expression::Member(
+            b(e),
+            Box::new(match *m {
+                Member::FunctionCall(args) => {
+                    Member::FunctionCall(args.into_iter().map(cel_float_literals).collect())
+                }
+                Member::Fields(fields) => Member::Fields(
+                    fields
+                        .into_iter()
+                        .map(|(k, v)| (k, cel_float_literals(v)))
+                        .collect(),
+                ),
+                m => m,
+            }),
+        ),
+        exp => exp,
+    }
+}
+
+/// Returns the name of the first function called within the expression
+/// which is not registered in the given context.
+fn cel_unknown_function(
+    exp: &cel_parser::Expression,
+    ctx: &cel_interpreter::context::Context,
+) -> Option<String> {
+    use cel_parser::{Expression, Member};
+    let check = |name: &str| {
+        if ctx.functions.contains_key(name) {
+            None
+        } else {
+            Some(name.to_string())
+        }
+    };
+
+    match exp {
+        Expression::Arithmetic(a, _, b)
+        | Expression::Relation(a, _, b)
+        | Expression::Or(a, b)
+        | Expression::And(a, b) => {
+            cel_unknown_function(a, ctx).or_else(|| cel_unknown_function(b, ctx))
+        }
+        Expression::Ternary(a, b, c) => cel_unknown_function(a, ctx)
+            .or_else(|| cel_unknown_function(b, ctx))
+            .or_else(|| cel_unknown_function(c, ctx)),
+        Expression::Unary(_, a) => cel_unknown_function(a, ctx),
+        Expression::List(items) => items.iter().find_map(|e| cel_unknown_function(e, ctx)),
+        Expression::Map(items) => items.iter().find_map(|(k, v)| {
+            cel_unknown_function(k, ctx).or_else(|| cel_unknown_function(v, ctx))
+        }),
+        Expression::Atom(_) | Expression::Ident(_) => None,
+        Expression::Member(left, member) => {
+            let callee = match (left.as_ref(), member.as_ref()) {
+                (Expression::Ident(name), Member::FunctionCall(_)) => check(name),
+                (Expression::Member(_, m), Member::FunctionCall(_)) => match m.as_ref() {
+                    Member::Attribute(name) => check(name),
+                    _ => None,
+                },
+                _ => None,
+            };
+            let args = match member.as_ref() {
+                Member::FunctionCall(args) => {
+                    args.iter().find_map(|e| cel_unknown_function(e, ctx))
+                }
+                Member::Index(e) => cel_unknown_function(e, ctx),
+                Member::Fields(fields) => fields
+                    .iter()
+                    .find_map(|(_, e)| cel_unknown_function(e, ctx)),
+                Member::Attribute(_) => None,
+            };
+            callee.or(args).or_else(|| match left.as_ref() {
+                // The callee of a method call is checked above.
+                Expression::Member(target, m) if matches!(m.as_ref(), Member::Attribute(_)) => {
+                    cel_unknown_function(target, ctx)
+                }
+                left => cel_unknown_function(left, ctx),
+            })
+        }
+    }
+}
+
 fn parse_cel(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
     let (start, _) = multispace0(i)?;
     let (i, exp) = context(
@@ -427,7 +1639,14 @@ fn parse_cel(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
         delimited(tag("!{"), cut(take_while(|c| c != '}')), tag("}")),
     )(start)?;
 
-    if let Err(_) = cel_interpreter::Program::compile(exp) {
+    // Variables are only known once the spec is resolved, but the functions
+    // are fixed, so calls are checked against those registered by
+    // ResolverContext::cel_ctx().
+    let valid = match cel_parser::parser::ExpressionParser::new().parse(exp) {
+        Ok(exp) => cel_unknown_function(&exp, &ResolverContext::default().cel_ctx()).is_none(),
+        Err(_) => false,
+    };
+    if !valid {
         return Err(nom::Err::Error(VerboseError {
             errors: vec![(
                 start,
@@ -436,7 +1655,7 @@ fn parse_cel(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
         }));
     }
 
-    Ok((i, AST::Cel(exp.to_string())))
+    Ok((i, AST::Cel(exp.to_string(), (0, 0))))
 }
 
 fn parse_ident(i: &str) -> IResult<&str, String, VerboseError<&str>> {
@@ -450,6 +1669,32 @@ fn parse_ident(i: &str) -> IResult<&str, String, VerboseError<&str>> {
     Ok((i, s.into()))
 }
 
+fn is_ident_char(c: char) -> bool {
+    c == '_' || c.is_ascii_alphanumeric()
+}
+
+/// Parses the name given to a feature, which may reference variables
+/// as `$ident` to be substituted when the feature is resolved.
+fn parse_name(i: &str) -> IResult<&str, String, VerboseError<&str>> {
+    let (i, _) = multispace0(i)?;
+    context(
+        "name",
+        fold_many1(
+            alt((
+                map(preceded(tag("$"), take_while1(is_ident_char)), |v| {
+                    format!("${}", v)
+                }),
+                map(take_while1(is_ident_char), |s: &str| s.to_string()),
+            )),
+            String::new(),
+            |mut acc, s| {
+                acc.push_str(&s);
+                acc
+            },
+        ),
+    )(i)
+}
+
 fn parse_uint(i: &str) -> IResult<&str, usize, VerboseError<&str>> {
     let (i, _) = multispace0(i)?;
     let (i, s) = context("uint", take_while(|c| c == '-' || (c >= '0' && c <= '9')))(i)?;
@@ -478,7 +1723,7 @@ fn parse_float(i: &str) -> IResult<&str, Value, VerboseError<&str>> {
     // Handle CEL expressions
     if let Ok((i, ast)) = parse_cel(i) {
         match ast {
-            AST::Cel(exp) => {
+            AST::Cel(exp, _) => {
                 return Ok((i, Value::Cel(exp)));
             }
             _ => unreachable!(),
@@ -489,17 +1734,23 @@ fn parse_float(i: &str) -> IResult<&str, Value, VerboseError<&str>> {
         "float",
         take_while(|c| c == '.' || c == '+' || c == '-' || (c >= '0' && c <= '9')),
     )(i)?;
+    let f: f64 = s.parse().map_err(|_e| {
+        nom::Err::Error(VerboseError {
+            errors: vec![(
+                i,
+                nom::error::VerboseErrorKind::Nom(nom::error::ErrorKind::Digit),
+            )],
+        })
+    })?;
 
+    // Values are in millimeters, unless suffixed with a unit.
+    let (i, unit) = opt(alt((tag_no_case("mm"), tag_no_case("in"))))(i)?;
     Ok((
         i,
-        Value::Float(s.parse().map_err(|_e| {
-            nom::Err::Error(VerboseError {
-                errors: vec![(
-                    i,
-                    nom::error::VerboseErrorKind::Nom(nom::error::ErrorKind::Digit),
-                )],
-            })
-        })?),
+        Value::Float(match unit {
+            Some(u) if u.eq_ignore_ascii_case("in") => f * 25.4,
+            _ => f,
+        }),
     ))
 }
 
@@ -521,12 +1772,20 @@ fn parse_inner(i: &str) -> IResult<&str, InnerAST, VerboseError<&str>> {
     let (i, inner) = delimited(
         tuple((tag("("), multispace0)),
         alt((
+            parse_inner_counter_sink,
             map(tuple((tag("h"), parse_float)), |(_, f)| {
                 InnerAST::ScrewHole(f)
             }),
             map(tag("h"), |_| InnerAST::ScrewHole(Value::Float(3.1))),
             map(tag("smiley"), |_| InnerAST::Smiley),
+            parse_inner_drill,
             parse_inner_msp,
+            parse_inner_line,
+            parse_inner_slot,
+            parse_inner_fiducial,
+            parse_inner_via,
+            parse_inner_text,
+            parse_inner_pad,
         )),
         tuple((multispace0, tag(")"))),
     )(i)?;
@@ -534,9 +1793,51 @@ fn parse_inner(i: &str) -> IResult<&str, InnerAST, VerboseError<&str>> {
     Ok((i, inner))
 }
 
+fn parse_inner_counter_sink(i: &str) -> IResult<&str, InnerAST, VerboseError<&str>> {
+    let (i, (_, dia, _, _, _, angle)) = tuple((
+        tag("h"),
+        opt(parse_float),
+        tuple((multispace0, tag(","), multispace0)),
+        tag_no_case("cs"),
+        tuple((multispace0, tag("="))),
+        parse_float,
+    ))(i)?;
+
+    Ok((
+        i,
+        InnerAST::CounterSunkScrewHole {
+            dia: dia.unwrap_or(Value::Float(3.1)),
+            angle,
+        },
+    ))
+}
+
+fn parse_inner_drill(i: &str) -> IResult<&str, InnerAST, VerboseError<&str>> {
+    let (i, _) = tag_no_case("drill")(i)?;
+    let (i, deets) = context("drill details", parse_details_with_flags(&["npth"]))(i)?;
+    if deets.extra.len() != 1 {
+        return Err(nom::Err::Failure(nom::error::make_error(
+            i,
+            nom::error::ErrorKind::Satisfy,
+        )));
+    }
+
+    Ok((
+        i,
+        InnerAST::Drill {
+            dia: deets.extra[0].clone(),
+            plated: !deets.flags.iter().any(|f| f == "npth"),
+        },
+    ))
+}
+
 fn parse_inner_msp(i: &str) -> IResult<&str, InnerAST, VerboseError<&str>> {
     let (i, _) = tag_no_case("msp")(i)?;
-    match context("msp details", parse_details)(i) {
+    match context(
+        "msp details",
+        parse_details_with_flags(&["npth", "nopaste"]),
+    )(i)
+    {
         Ok((i2, deets)) => {
             let size = if let Some((x, y)) = deets.size {
                 Some((x, y))
@@ -548,17 +1849,191 @@ fn parse_inner_msp(i: &str) -> IResult<&str, InnerAST, VerboseError<&str>> {
                 None
             };
 
-            Ok((i2, InnerAST::MechanicalSolderPoint(size)))
+            Ok((
+                i2,
+                InnerAST::MechanicalSolderPoint {
+                    size,
+                    plated: !deets.flags.iter().any(|f| f == "npth"),
+                    paste: !deets.flags.iter().any(|f| f == "nopaste"),
+                },
+            ))
         }
-        Err(_) => Ok((i, InnerAST::MechanicalSolderPoint(None))),
+        Err(_) => Ok((
+            i,
+            InnerAST::MechanicalSolderPoint {
+                size: None,
+                plated: true,
+                paste: true,
+            },
+        )),
     }
 }
 
+fn parse_inner_line(i: &str) -> IResult<&str, InnerAST, VerboseError<&str>> {
+    let (i, _) = tuple((tag_no_case("line"), multispace0, tag("<"), multispace0))(i)?;
+    let (i, (_, start, _, _, end)) = context(
+        "line endpoints",
+        tuple((
+            tag("@"),
+            parse_coords,
+            tuple((multispace0, tag(","), multispace0)),
+            tag("@"),
+            parse_coords,
+        )),
+    )(i)?;
+    let (i, width) = opt(preceded(
+        tuple((
+            multispace0,
+            tag(","),
+            multispace0,
+            tag_no_case("width"),
+            multispace0,
+            tag("="),
+        )),
+        parse_float,
+    ))(i)?;
+    let (i, _) = tuple((multispace0, tag(">")))(i)?;
+
+    Ok((
+        i,
+        InnerAST::Line {
+            start,
+            end,
+            width: width.unwrap_or(Value::Float(0.15)),
+        },
+    ))
+}
+
+fn parse_inner_slot(i: &str) -> IResult<&str, InnerAST, VerboseError<&str>> {
+    let (i, _) = tuple((tag_no_case("slot"), multispace0, tag("<"), multispace0))(i)?;
+    let (i, (_, start, _, _, end, _, width, _)) = context(
+        "slot details",
+        cut(tuple((
+            tag("@"),
+            parse_coords,
+            tuple((multispace0, tag(","), multispace0)),
+            tag("@"),
+            parse_coords,
+            tuple((multispace0, tag(","))),
+            parse_float,
+            tuple((multispace0, tag(">"))),
+        ))),
+    )(i)?;
+
+    Ok((i, InnerAST::Slot { start, end, width }))
+}
+
+fn parse_inner_fiducial(i: &str) -> IResult<&str, InnerAST, VerboseError<&str>> {
+    let (i, _) = tag_no_case("fiducial")(i)?;
+    let (i, coords) = opt(delimited(
+        tuple((multispace0, tag("<"), multispace0, tag("@"))),
+        parse_coords,
+        tuple((multispace0, tag(">"))),
+    ))(i)?;
+
+    Ok((i, InnerAST::Fiducial(coords)))
+}
+
+fn parse_inner_via(i: &str) -> IResult<&str, InnerAST, VerboseError<&str>> {
+    let (i, _) = tuple((tag_no_case("via"), multispace0, tag("<"), multispace0))(i)?;
+    let (i, (_, coords, _, dia, ring, _)) = context(
+        "via details",
+        cut(tuple((
+            tag("@"),
+            parse_coords,
+            tuple((multispace0, tag(","))),
+            parse_float,
+            opt(preceded(
+                tuple((
+                    multispace0,
+                    tag(","),
+                    multispace0,
+                    tag_no_case("ring"),
+                    multispace0,
+                    tag("="),
+                )),
+                parse_float,
+            )),
+            tuple((multispace0, tag(">"))),
+        ))),
+    )(i)?;
+
+    Ok((i, InnerAST::Via { coords, dia, ring }))
+}
+
+fn parse_inner_text(i: &str) -> IResult<&str, InnerAST, VerboseError<&str>> {
+    let (i, _) = tuple((tag_no_case("text"), multispace0, tag("<"), multispace0))(i)?;
+    let (i, (_, coords, _, text, _, size, _)) = context(
+        "text details",
+        cut(tuple((
+            tag("@"),
+            parse_coords,
+            tuple((multispace0, tag(","), multispace0, tag("\""))),
+            take_while(|c| c != '"' && c != '\n'),
+            tag("\""),
+            opt(preceded(
+                tuple((
+                    multispace0,
+                    tag(","),
+                    multispace0,
+                    tag_no_case("size"),
+                    multispace0,
+                    tag("="),
+                )),
+                parse_float,
+            )),
+            tuple((multispace0, tag(">"))),
+        ))),
+    )(i)?;
+
+    Ok((
+        i,
+        InnerAST::Text {
+            coords,
+            text: text.to_string(),
+            size,
+        },
+    ))
+}
+
+fn parse_inner_pad(i: &str) -> IResult<&str, InnerAST, VerboseError<&str>> {
+    let (i, _) = tuple((tag_no_case("pad"), multispace0, tag("<"), multispace0))(i)?;
+    let (i, (_, coords, _, w, _, h, back, _)) = context(
+        "pad details",
+        cut(tuple((
+            tag("@"),
+            parse_coords,
+            tuple((multispace0, tag(","))),
+            parse_float,
+            tuple((multispace0, tag(","))),
+            parse_float,
+            opt(tuple((
+                multispace0,
+                tag(","),
+                multispace0,
+                tag_no_case("back"),
+            ))),
+            tuple((multispace0, tag(">"))),
+        ))),
+    )(i)?;
+
+    Ok((
+        i,
+        InnerAST::Pad {
+            coords,
+            size: (w, h),
+            back: back.is_some(),
+        },
+    ))
+}
+
 enum DetailFragment {
     Coord(Value, Value),
     Size(Value, Value),
     Radius(Value),
     Rounding(Value),
+    Flag(String),
+    Named(String, Value),
     Extra(Value),
 }
 
@@ -568,6 +2043,8 @@ struct Details {
     size: Option<(Value, Value)>,
     radius: Option<Value>,
     extra: Vec<Value>,
+    flags: Vec<String>,
+    named: HashMap<String, Value>,
     inner: Option<InnerAST>,
     rounded: Option<Value>,
 }
@@ -616,6 +2093,37 @@ impl Details {
         ))(i)?;
         Ok((i, DetailFragment::Rounding(r)))
     }
+    fn parse_flag<'a>(
+        flags: &'static [&'static str],
+    ) -> impl FnMut(&'a str) -> IResult<&'a str, DetailFragment, VerboseError<&'a str>> {
+        move |i| {
+            let (i, _) = multispace0(i)?;
+            let (i, f) = verify(take_while(|c: char| c.is_ascii_alphabetic()), |f: &str| {
+                flags.iter().any(|flag| flag.eq_ignore_ascii_case(f))
+            })(i)?;
+            Ok((i, DetailFragment::Flag(f.to_lowercase())))
+        }
+    }
+    fn parse_named(i: &str) -> IResult<&str, DetailFragment, VerboseError<&str>> {
+        let (i, _) = multispace0(i)?;
+        let (i, (n, _, _, _, v)) = tuple((
+            alt((
+                tag_no_case("top"),
+                tag_no_case("bottom"),
+                tag_no_case("height"),
+                tag_no_case("h"),
+            )),
+            multispace0,
+            tag("="),
+            multispace0,
+            cut(parse_float),
+        ))(i)?;
+        let n = match n.to_lowercase().as_str() {
+            "height" => "h".to_string(),
+            n => n.to_string(),
+        };
+        Ok((i, DetailFragment::Named(n, v)))
+    }
 
     fn with_inner(mut self, inner: Option<InnerAST>) -> Self {
         self.inner = inner;
@@ -624,83 +2132,108 @@ impl Details {
 }
 
 fn parse_details(i: &str) -> IResult<&str, Details, VerboseError<&str>> {
-    let (i, _) = multispace0(i)?;
-
-    let (i, deets) = delimited(
-        tuple((tag("<"), multispace0)),
-        cut(fold_many1(
-            alt((
-                tuple((
-                    context("pos", Details::parse_pos),
-                    multispace0,
-                    opt(tag(",")),
-                )),
-                tuple((
-                    context("size", Details::parse_size),
-                    multispace0,
-                    opt(tag(",")),
-                )),
-                tuple((
-                    context("radius", Details::parse_radius),
-                    multispace0,
-                    opt(tag(",")),
-                )),
-                tuple((
-                    context("rounding", Details::parse_rounding),
-                    multispace0,
-                    opt(tag(",")),
-                )),
-                tuple((Details::parse_extra, multispace0, opt(tag(",")))),
-            )),
-            Details::default(),
-            |mut acc: Details, (fragment, _, _)| {
-                match fragment {
-                    DetailFragment::Coord(x, y) => {
-                        acc.coords = Some((x, y));
-                    }
-                    DetailFragment::Size(x, y) => {
-                        acc.size = Some((x, y));
-                    }
-                    DetailFragment::Radius(r) => {
-                        acc.radius = Some(r);
-                    }
-                    DetailFragment::Rounding(r) => {
-                        acc.rounded = Some(r);
-                    }
-                    DetailFragment::Extra(f) => acc.extra.push(f),
-                }
-                acc
-            },
-        )),
-        tuple((tag(">"), multispace0)),
-    )(i)?;
-
-    let (i, inner) = opt(parse_inner)(i)?;
-    Ok((i, deets.with_inner(inner)))
+    parse_details_with_flags(&[])(i)
 }
 
-fn parse_rect(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
-    let (i, _) = multispace0(i)?;
-    let (i, _) = tag_no_case("R")(i)?;
-    let (i, deets) = context("rectangle details", parse_details)(i)?;
-
-    let size = if let Some((x, y)) = deets.size {
-        Some((x, y))
-    } else if deets.extra.len() == 2 {
-        Some((deets.extra[0].clone(), deets.extra[1].clone()))
-    } else if deets.extra.len() == 1 {
-        Some((deets.extra[0].clone(), deets.extra[0].clone()))
-    } else {
-        None
-    };
+/// Parses the details of a feature which accepts the given flags. Flags
+/// not in the list are rejected.
+fn parse_details_with_flags<'a>(
+    flags: &'static [&'static str],
+) -> impl FnMut(&'a str) -> IResult<&'a str, Details, VerboseError<&'a str>> {
+    move |i| {
+        let (i, _) = multispace0(i)?;
 
-    Ok((
-        i,
-        AST::Rect {
-            size,
-            coords: deets.coords,
+        let (i, deets) = delimited(
+            tuple((tag("<"), multispace0)),
+            cut(fold_many1(
+                alt((
+                    tuple((
+                        context("pos", Details::parse_pos),
+                        multispace0,
+                        opt(tag(",")),
+                    )),
+                    tuple((
+                        context("size", Details::parse_size),
+                        multispace0,
+                        opt(tag(",")),
+                    )),
+                    tuple((
+                        context("radius", Details::parse_radius),
+                        multispace0,
+                        opt(tag(",")),
+                    )),
+                    tuple((
+                        context("rounding", Details::parse_rounding),
+                        multispace0,
+                        opt(tag(",")),
+                    )),
+                    tuple((
+                        context("flag", Details::parse_flag(flags)),
+                        multispace0,
+                        opt(tag(",")),
+                    )),
+                    tuple((
+                        context("named", Details::parse_named),
+                        multispace0,
+                        opt(tag(",")),
+                    )),
+                    tuple((Details::parse_extra, multispace0, opt(tag(",")))),
+                )),
+                Details::default(),
+                |mut acc: Details, (fragment, _, _)| {
+                    match fragment {
+                        DetailFragment::Coord(x, y) => {
+                            acc.coords = Some((x, y));
+                        }
+                        DetailFragment::Size(x, y) => {
+                            acc.size = Some((x, y));
+                        }
+                        DetailFragment::Radius(r) => {
+                            acc.radius = Some(r);
+                        }
+                        DetailFragment::Rounding(r) => {
+                            acc.rounded = Some(r);
+                        }
+                        DetailFragment::Flag(f) => acc.flags.push(f),
+                        DetailFragment::Named(n, v) => {
+                            acc.named.insert(n, v);
+                        }
+                        DetailFragment::Extra(f) => acc.extra.push(f),
+                    }
+                    acc
+                },
+            )),
+            tuple((tag(">"), multispace0)),
+        )(i)?;
+
+        let (i, inner) = opt(parse_inner)(i)?;
+        Ok((i, deets.with_inner(inner)))
+    }
+}
+
+fn parse_rect(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
+    let (i, _) = multispace0(i)?;
+    let (i, _) = tag_no_case("R")(i)?;
+    let (i, deets) = context("rectangle details", parse_details)(i)?;
+
+    let size = if let Some((x, y)) = deets.size {
+        Some((x, y))
+    } else if deets.extra.len() == 2 {
+        Some((deets.extra[0].clone(), deets.extra[1].clone()))
+    } else if deets.extra.len() == 1 {
+        Some((deets.extra[0].clone(), deets.extra[0].clone()))
+    } else {
+        None
+    };
+
+    Ok((
+        i,
+        AST::Rect {
+            size,
+            coords: deets.coords,
             inner: deets.inner,
             rounded: deets.rounded,
+            span: (0, 0),
         },
     ))
 }
@@ -727,6 +2260,224 @@ fn parse_circle(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
             coords: deets.coords,
             radius: r,
             inner: deets.inner,
+            span: (0, 0),
+        },
+    ))
+}
+
+fn parse_ellipse(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
+    let (i, _) = multispace0(i)?;
+    let (i, _) = tag_no_case("E")(i)?;
+    let (i2, deets) = context("ellipse details", parse_details)(i)?;
+
+    let radii = if deets.extra.len() == 2 {
+        (deets.extra[0].clone(), deets.extra[1].clone())
+    } else {
+        return Err(nom::Err::Failure(nom::error::make_error(
+            i,
+            nom::error::ErrorKind::Satisfy,
+        )));
+    };
+
+    Ok((
+        i2,
+        AST::Ellipse {
+            coords: deets.coords,
+            radii,
+            inner: deets.inner,
+            span: (0, 0),
+        },
+    ))
+}
+
+fn parse_trapezoid(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
+    let (i, _) = multispace0(i)?;
+    let (i, _) = tag_no_case("Z")(i)?;
+    let (i2, mut deets) = context("trapezoid details", parse_details)(i)?;
+
+    let (top, bottom, height) = match (
+        deets.named.remove("top"),
+        deets.named.remove("bottom"),
+        deets.named.remove("h"),
+    ) {
+        (Some(t), Some(b), Some(h)) => (t, b, h),
+        _ if deets.extra.len() == 3 => (
+            deets.extra[0].clone(),
+            deets.extra[1].clone(),
+            deets.extra[2].clone(),
+        ),
+        _ => {
+            return Err(nom::Err::Failure(nom::error::make_error(
+                i,
+                nom::error::ErrorKind::Satisfy,
+            )));
+        }
+    };
+
+    Ok((
+        i2,
+        AST::Trapezoid {
+            coords: deets.coords,
+            top,
+            bottom,
+            height,
+            inner: deets.inner,
+            span: (0, 0),
+        },
+    ))
+}
+
+fn parse_regular_polygon(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
+    let (i, _) = multispace0(i)?;
+    let (i, _) = tag_no_case("P")(i)?;
+    let (i2, deets) = context("polygon details", parse_details)(i)?;
+
+    let (sides, radius) = if deets.extra.len() == 2 {
+        (deets.extra[0].clone(), deets.extra[1].clone())
+    } else {
+        return Err(nom::Err::Failure(nom::error::make_error(
+            i,
+            nom::error::ErrorKind::Satisfy,
+        )));
+    };
+
+    Ok((
+        i2,
+        AST::RegularPolygon {
+            coords: deets.coords,
+            sides,
+            radius,
+            inner: deets.inner,
+            span: (0, 0),
+        },
+    ))
+}
+
+fn parse_mouse_bite(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
+    let (i, _) = multispace0(i)?;
+    let (i, _) = tag_no_case("mouse_bite")(i)?;
+    let (i2, deets) = context(
+        "mouse bite details",
+        cut(parse_details_with_flags(&["left", "right", "up", "down"])),
+    )(i)?;
+
+    if deets.extra.len() < 2 || deets.extra.len() > 3 {
+        return Err(nom::Err::Failure(nom::error::make_error(
+            i,
+            nom::error::ErrorKind::Satisfy,
+        )));
+    }
+    let dir = if deets.flags.iter().any(|f| f == "left") {
+        crate::Direction::Left
+    } else if deets.flags.iter().any(|f| f == "up") {
+        crate::Direction::Up
+    } else if deets.flags.iter().any(|f| f == "down") {
+        crate::Direction::Down
+    } else {
+        crate::Direction::Right
+    };
+
+    Ok((
+        i2,
+        AST::MouseBite {
+            coords: deets.coords,
+            length: deets.extra[0].clone(),
+            hole_diameter: deets.extra[1].clone(),
+            spacing: deets.extra.get(2).cloned(),
+            dir,
+            span: (0, 0),
+        },
+    ))
+}
+
+fn parse_tab(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
+    let (i, _) = multispace0(i)?;
+    let (i, _) = tuple((tag_no_case("tab"), multispace0, tag("<")))(i)?;
+    let (i, (width, _, length, dir, _)) = context(
+        "tab details",
+        cut(tuple((
+            parse_float,
+            tuple((multispace0, tag(","))),
+            parse_float,
+            opt(preceded(
+                tuple((
+                    multispace0,
+                    tag(","),
+                    multispace0,
+                    tag_no_case("dir"),
+                    multispace0,
+                    tag("="),
+                    multispace0,
+                )),
+                parse_direction,
+            )),
+            tuple((multispace0, tag(">"))),
+        ))),
+    )(i)?;
+
+    Ok((
+        i,
+        AST::Tab {
+            width,
+            length,
+            dir: dir.unwrap_or(crate::Direction::Right),
+            span: (0, 0),
+        },
+    ))
+}
+
+fn parse_direction(i: &str) -> IResult<&str, crate::Direction, VerboseError<&str>> {
+    alt((
+        map(tag_no_case("left"), |_| crate::Direction::Left),
+        map(tag_no_case("right"), |_| crate::Direction::Right),
+        map(alt((tag_no_case("up"), tag_no_case("top"))), |_| {
+            crate::Direction::Up
+        }),
+        map(alt((tag_no_case("down"), tag_no_case("bottom"))), |_| {
+            crate::Direction::Down
+        }),
+    ))(i)
+}
+
+fn parse_castellation(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
+    let (i, _) = multispace0(i)?;
+    let (i, _) = tuple((tag_no_case("castellation"), multispace0, tag("<")))(i)?;
+    let (i, (count, _, hole_diameter, side, npth, _)) = context(
+        "castellation details",
+        cut(tuple((
+            parse_float,
+            tuple((multispace0, tag(","))),
+            parse_float,
+            opt(preceded(
+                tuple((
+                    multispace0,
+                    tag(","),
+                    multispace0,
+                    tag_no_case("side"),
+                    multispace0,
+                    tag("="),
+                    multispace0,
+                )),
+                parse_direction,
+            )),
+            opt(tuple((
+                multispace0,
+                tag(","),
+                multispace0,
+                tag_no_case("npth"),
+            ))),
+            tuple((multispace0, tag(">"))),
+        ))),
+    )(i)?;
+
+    Ok((
+        i,
+        AST::Castellation {
+            count,
+            hole_diameter,
+            side: side.unwrap_or(crate::Direction::Left),
+            plated: npth.is_none(),
+            span: (0, 0),
         },
     ))
 }
@@ -734,7 +2485,18 @@ fn parse_circle(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
 fn parse_triangle(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
     let (i, _) = multispace0(i)?;
     let (i, _) = tag_no_case("T")(i)?;
-    let (i2, deets) = context("triangle details", cut(parse_details))(i)?;
+    let (i2, deets) = context(
+        "triangle details",
+        cut(parse_details_with_flags(&["iso", "equi"])),
+    )(i)?;
+
+    let kind = if deets.flags.iter().any(|f| f == "equi") {
+        TriangleKind::Equilateral
+    } else if deets.flags.iter().any(|f| f == "iso") {
+        TriangleKind::Isoceles
+    } else {
+        TriangleKind::RightAngle
+    };
 
     let size = if let Some((x, y)) = deets.size {
         (x, y)
@@ -753,7 +2515,9 @@ fn parse_triangle(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
         i2,
         AST::Triangle {
             size,
+            kind,
             inner: deets.inner,
+            span: (0, 0),
         },
     ))
 }
@@ -787,6 +2551,92 @@ fn parse_rmount(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
                 "mount_cut_down" => crate::Direction::Down,
                 _ => crate::Direction::Up,
             },
+            span: (0, 0),
+        },
+    ))
+}
+
+fn parse_bezier(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
+    let (i, _) = multispace0(i)?;
+    let (i, _) = tag_no_case("bezier")(i)?;
+    let (i2, points) = context(
+        "bezier",
+        delimited(
+            tuple((multispace0, tag("["), multispace0)),
+            cut(fold_many1(
+                tuple((parse_coords, multispace0, opt(tag(",")), multispace0)),
+                Vec::new(),
+                |mut acc, (c, _, _, _)| {
+                    acc.push(c);
+                    acc
+                },
+            )),
+            tuple((tag("]"), multispace0)),
+        ),
+    )(i)?;
+
+    if points.len() < 3 {
+        return Err(nom::Err::Failure(nom::error::make_error(
+            i,
+            nom::error::ErrorKind::Satisfy,
+        )));
+    }
+
+    Ok((
+        i2,
+        AST::Bezier {
+            points,
+            span: (0, 0),
+        },
+    ))
+}
+
+fn parse_gold_fingers(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
+    let (i, _) = multispace0(i)?;
+    let (i, _) = tag_no_case("gold_fingers")(i)?;
+    let (i2, deets) = context(
+        "gold finger details",
+        cut(parse_details_with_flags(&["double"])),
+    )(i)?;
+
+    if deets.extra.len() != 4 {
+        return Err(nom::Err::Failure(nom::error::make_error(
+            i,
+            nom::error::ErrorKind::Satisfy,
+        )));
+    }
+
+    Ok((
+        i2,
+        AST::GoldFingers {
+            count: deets.extra[0].clone(),
+            pitch: deets.extra[1].clone(),
+            pad_size: (deets.extra[2].clone(), deets.extra[3].clone()),
+            double_sided: deets.flags.iter().any(|f| f == "double"),
+            span: (0, 0),
+        },
+    ))
+}
+
+fn parse_sector(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
+    let (i, _) = multispace0(i)?;
+    let (i, _) = tag_no_case("sector")(i)?;
+    let (i2, deets) = context("sector details", cut(parse_details))(i)?;
+
+    if deets.extra.len() != 3 {
+        return Err(nom::Err::Failure(nom::error::make_error(
+            i,
+            nom::error::ErrorKind::Satisfy,
+        )));
+    }
+
+    Ok((
+        i2,
+        AST::Sector {
+            coords: deets.coords,
+            radius: deets.extra[0].clone(),
+            angles: (deets.extra[1].clone(), deets.extra[2].clone()),
+            span: (0, 0),
         },
     ))
 }
@@ -801,11 +2651,25 @@ fn parse_array(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
             cut(tuple((
                 parse_uint,
                 opt(tuple((multispace0, tag(";"), multispace0, one_of("UDRL")))),
-                opt(tuple((
+                many0(tuple((
                     multispace0,
                     tag(";"),
                     multispace0,
-                    alt((tag_no_case("vscore"), tag_no_case("v-score"))),
+                    alt((
+                        map(tag_no_case("vscore"), |t| (t, None)),
+                        map(tag_no_case("v-score"), |t| (t, None)),
+                        map(tag_no_case("flip"), |t| (t, None)),
+                        map(
+                            tuple((
+                                tag_no_case("gap"),
+                                multispace0,
+                                tag("="),
+                                multispace0,
+                                cut(parse_float),
+                            )),
+                            |t| (t.0, Some(t.4)),
+                        ),
+                    )),
                 ))),
             ))),
             tuple((tag("]"), multispace0)),
@@ -813,7 +2677,15 @@ fn parse_array(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
     )(i)?;
     let (i, geo) = parse_geo(i)?;
 
-    let (num, dir, vscore) = params;
+    let (num, dir, opts) = params;
+    let has_opt = |o: &str| {
+        opts.iter()
+            .any(|(_, _, _, (v, _))| v.eq_ignore_ascii_case(o))
+    };
+    let vscore = has_opt("vscore") || has_opt("v-score");
+    let flip = has_opt("flip");
+    let gap = opts.iter().find_map(|(_, _, _, (_, g))| g.clone());
+
     let dir = if let Some((_, _, _, s)) = dir {
         match s {
             'L' => crate::Direction::Left,
@@ -837,7 +2709,50 @@ fn parse_array(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
             dir,
             num,
             inner: Box::new(geo),
-            vscore: vscore.is_some(),
+            vscore,
+            flip,
+            gap,
+            span: (0, 0),
+        },
+    ))
+}
+
+fn parse_grid(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
+    let (i, _) = multispace0(i)?;
+
+    let (i, (rows, _, _, cols, gap)) = context(
+        "grid",
+        delimited(
+            tuple((tag("["), multispace0)),
+            tuple((
+                parse_uint,
+                multispace0,
+                tag_no_case("x"),
+                cut(parse_uint),
+                opt(tuple((
+                    multispace0,
+                    tag(";"),
+                    multispace0,
+                    tag_no_case("gap"),
+                    multispace0,
+                    tag("="),
+                    multispace0,
+                    cut(parse_float),
+                ))),
+            )),
+            tuple((multispace0, tag("]"), multispace0)),
+        ),
+    )(i)?;
+    let (i, geo) = parse_geo(i)?;
+
+    Ok((
+        i,
+        AST::Grid {
+            rows,
+            cols,
+            gap: gap.map(|g| g.7),
+            inner: Box::new(geo),
+            span: (0, 0),
         },
     ))
 }
@@ -845,7 +2760,7 @@ fn parse_array(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
 fn parse_column_layout(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
     let (i, _) = multispace0(i)?;
 
-    let (i, (dir, _, pos, _, _, inners)) = context(
+    let (i, (dir, _, pos, _, gap, _, inners)) = context(
         "column",
         delimited(
             tuple((tag_no_case("column"), multispace0)),
@@ -858,10 +2773,18 @@ fn parse_column_layout(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
                 multispace0,
                 opt(tuple((tag("@"), parse_coords))),
                 multispace0,
-                tag("{"),
-                fold_many1(
-                    tuple((parse_geo, multispace0, opt(tag(",")))),
-                    Vec::new(),
+                opt(tuple((
+                    tag_no_case("gap"),
+                    multispace0,
+                    tag("="),
+                    multispace0,
+                    cut(parse_float),
+                    multispace0,
+                ))),
+                tag("{"),
+                fold_many1(
+                    tuple((parse_geo, multispace0, opt(tag(",")))),
+                    Vec::new(),
                     |mut acc, (inner, _, _)| {
                         acc.push(Box::new(inner));
                         acc
@@ -882,11 +2805,112 @@ fn parse_column_layout(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
             },
             inners: inners,
             coords: pos.map(|x| x.1),
+            gap: gap.map(|g| g.4),
+            span: (0, 0),
+        },
+    ))
+}
+
+fn parse_row_layout(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
+    let (i, _) = multispace0(i)?;
+
+    let (i, (dir, _, pos, _, _, inners)) = context(
+        "row",
+        delimited(
+            tuple((tag_no_case("row"), multispace0)),
+            tuple((
+                alt((
+                    tag_no_case("top"),
+                    tag_no_case("center"),
+                    tag_no_case("bottom"),
+                )),
+                multispace0,
+                opt(tuple((tag("@"), parse_coords))),
+                multispace0,
+                tag("{"),
+                fold_many1(
+                    tuple((parse_geo, multispace0, opt(tag(",")))),
+                    Vec::new(),
+                    |mut acc, (inner, _, _)| {
+                        acc.push(Box::new(inner));
+                        acc
+                    },
+                ),
+            )),
+            tuple((tag("}"), multispace0)),
+        ),
+    )(i)?;
+
+    Ok((
+        i,
+        AST::RowLayout {
+            align: match dir.to_lowercase().as_str() {
+                "top" => crate::Align::Start,
+                "bottom" => crate::Align::End,
+                _ => crate::Align::Center,
+            },
+            inners: inners,
+            coords: pos.map(|x| x.1),
+            span: (0, 0),
         },
     ))
 }
 
 fn parse_pos_spec(i: &str) -> IResult<&str, WrapPosition, VerboseError<&str>> {
+    alt((
+        parse_absolute_spec,
+        parse_distribute_spec,
+        parse_stack_spec,
+        parse_cardinal_spec,
+    ))(i)
+}
+
+fn parse_stack_spec(i: &str) -> IResult<&str, WrapPosition, VerboseError<&str>> {
+    let (i, (_, side, gap, _, _)) = tuple((
+        tuple((multispace0, tag_no_case("stack("), multispace0)),
+        parse_direction,
+        opt(tuple((multispace0, tag(","), parse_float))),
+        tuple((multispace0, tag(")"))),
+        tuple((multispace0, tag("=>"))),
+    ))(i)?;
+
+    Ok((
+        i,
+        WrapPosition::Stack {
+            side,
+            gap: gap.map(|g| g.2),
+        },
+    ))
+}
+
+fn parse_distribute_spec(i: &str) -> IResult<&str, WrapPosition, VerboseError<&str>> {
+    let (i, (_, count, _, _, side, _, _)) = tuple((
+        tuple((multispace0, tag("["))),
+        parse_uint,
+        tuple((multispace0, tag("]"))),
+        multispace0,
+        parse_direction,
+        multispace0,
+        tag("=>"),
+    ))(i)?;
+
+    Ok((i, WrapPosition::Distribute { side, count }))
+}
+
+fn parse_absolute_spec(i: &str) -> IResult<&str, WrapPosition, VerboseError<&str>> {
+    let (i, (_, x, _, y, _, _)) = tuple((
+        tuple((multispace0, tag_no_case("absolute("))),
+        parse_float,
+        tuple((multispace0, tag(","))),
+        parse_float,
+        tuple((multispace0, tag(")"))),
+        tuple((multispace0, tag("=>"))),
+    ))(i)?;
+
+    Ok((i, WrapPosition::Absolute { x, y }))
+}
+
+fn parse_cardinal_spec(i: &str) -> IResult<&str, WrapPosition, VerboseError<&str>> {
     let (i, (_, side, offset, _, align, _)) = tuple((
         multispace0,
         alt((
@@ -1080,6 +3104,7 @@ fn parse_wrap(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
         AST::Wrap {
             inner: Box::new(inner),
             features: elements,
+            span: (0, 0),
         },
     ))
 }
@@ -1098,20 +3123,183 @@ fn parse_assign(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
         opt(tag(";")),
     ))(i)?;
 
-    Ok((i, AST::Assign(var, Box::new(geo))))
+    Ok((i, AST::Assign(var, Box::new(geo), (0, 0))))
+}
+
+fn parse_fn_def(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
+    let (i, _) = multispace0(i)?;
+    let (i, (_, _, name, _, params, _, _, body, _, _)) = tuple((
+        tag("fn"),
+        multispace1,
+        parse_ident,
+        tuple((multispace0, tag("("), multispace0)),
+        separated_list0(
+            tuple((multispace0, tag(","))),
+            verify(parse_ident, |p: &String| !p.is_empty()),
+        ),
+        tuple((multispace0, tag(")"), multispace0)),
+        tag("="),
+        parse_geo,
+        multispace0,
+        opt(tag(";")),
+    ))(i)?;
+
+    Ok((
+        i,
+        AST::FnDef {
+            name,
+            params,
+            body: Box::new(body),
+            span: (0, 0),
+        },
+    ))
+}
+
+fn parse_fn_call(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
+    let (i, _) = multispace0(i)?;
+    let (i, name) = verify(parse_ident, |name: &String| !name.is_empty())(i)?;
+
+    let (i, args) = delimited(
+        tuple((multispace0, tag("("))),
+        separated_list0(tuple((multispace0, tag(","))), parse_float),
+        tuple((multispace0, tag(")"))),
+    )(i)?;
+
+    // Built-in features which take arguments in parentheses, such as
+    // rotate() or wrap(), are followed by a body, which a call is not.
+    let (i, _) = not(tuple((multispace0, alt((tag("{"), tag_no_case("with"))))))(i)?;
+
+    Ok((
+        i,
+        AST::FnCall {
+            name,
+            args,
+            span: (0, 0),
+        },
+    ))
+}
+
+fn parse_block(i: &str) -> IResult<&str, Vec<Box<AST>>, VerboseError<&str>> {
+    delimited(
+        tuple((multispace0, tag("{"))),
+        many0(map(
+            tuple((parse_geo, multispace0, opt(tag(",")))),
+            |(inner, _, _)| Box::new(inner),
+        )),
+        tuple((multispace0, tag("}"))),
+    )(i)
+}
+
+fn parse_conditional(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
+    let (i, _) = multispace0(i)?;
+    let (i, (_, condition)) = tuple((tag("if"), parse_cel))(i)?;
+    let (i, (then_branch, else_branch)) = context(
+        "if",
+        cut(tuple((
+            parse_block,
+            opt(preceded(tuple((multispace0, tag("else"))), parse_block)),
+        ))),
+    )(i)?;
+
+    let condition = match condition {
+        AST::Cel(exp, _) => exp,
+        _ => unreachable!(),
+    };
+    Ok((
+        i,
+        AST::Conditional {
+            condition,
+            then_branch,
+            else_branch: else_branch.unwrap_or_default(),
+            span: (0, 0),
+        },
+    ))
+}
+
+fn parse_for_loop(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
+    let (i, _) = multispace0(i)?;
+    let (i, (_, _, var, _, _, range_expr)) = tuple((
+        tag("for"),
+        multispace1,
+        parse_ident,
+        multispace1,
+        tag("in"),
+        parse_cel,
+    ))(i)?;
+    let (i, body) = context("for", cut(parse_block))(i)?;
+
+    let range_expr = match range_expr {
+        AST::Cel(exp, _) => exp,
+        _ => unreachable!(),
+    };
+    Ok((
+        i,
+        AST::ForLoop {
+            var,
+            range_expr,
+            body,
+            span: (0, 0),
+        },
+    ))
 }
 
 fn parse_var(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
     let (i, _) = multispace0(i)?;
     let (i, (_, var)) = tuple((tag("$"), parse_ident))(i)?;
-    Ok((i, AST::VarRef(var)))
+    Ok((i, AST::VarRef(var, (0, 0))))
 }
 
 pub fn parse_comment(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
     let (i, _) = multispace0(i)?;
     let (i, _) = alt((tag("#"), tag("//")))(i)?;
     let (i, v) = take_while(|chr| chr != '\n')(i)?;
-    Ok((i, AST::Comment(v.to_string())))
+    Ok((i, AST::Comment(v.to_string(), (0, 0))))
+}
+
+/// Parses a `/* ... */` comment, which may contain nested block comments.
+fn parse_block_comment(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
+    let (start, _) = multispace0(i)?;
+    let (mut i, _) = tag("/*")(start)?;
+
+    let mut depth = 1;
+    while depth > 0 {
+        if let Some(rest) = i.strip_prefix("/*") {
+            depth += 1;
+            i = rest;
+        } else if let Some(rest) = i.strip_prefix("*/") {
+            depth -= 1;
+            i = rest;
+        } else {
+            let mut chars = i.chars();
+            if chars.next().is_none() {
+                return Err(nom::Err::Failure(VerboseError {
+                    errors: vec![(
+                        start,
+                        nom::error::VerboseErrorKind::Context("block comment"),
+                    )],
+                }));
+            }
+            i = chars.as_str();
+        }
+    }
+
+    let body = &start[2..start.len() - i.len() - 2];
+    Ok((i, AST::Comment(body.to_string(), (0, 0))))
+}
+
+fn parse_import(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
+    let (i, _) = multispace0(i)?;
+    let (i, _) = tag("import")(i)?;
+    let (i, (_, path, _, _)) = context(
+        "import",
+        cut(tuple((
+            tuple((multispace0, tag("\""))),
+            take_while(|c| c != '"' && c != '\n'),
+            tag("\""),
+            opt(tuple((multispace0, tag(";")))),
+        ))),
+    )(i)?;
+    Ok((i, AST::Import(path.to_string(), (0, 0))))
 }
 
 fn parse_tuple(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
@@ -1128,7 +3316,13 @@ fn parse_tuple(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
     )(i)?;
     let (i, _) = tuple((multispace0, tag(")"), multispace0))(i)?;
 
-    Ok((i, AST::Tuple { inners: elements }))
+    Ok((
+        i,
+        AST::Tuple {
+            inners: elements,
+            span: (0, 0),
+        },
+    ))
 }
 
 fn parse_negative(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
@@ -1154,7 +3348,169 @@ fn parse_negative(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
         ),
     )(i)?;
 
-    Ok((i, AST::Negative { inners: inners }))
+    Ok((
+        i,
+        AST::Negative {
+            inners: inners,
+            span: (0, 0),
+        },
+    ))
+}
+
+fn parse_offset(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
+    let (i, _) = multispace0(i)?;
+
+    let (i, (_, _, _, amount, _, _, _)) = context(
+        "offset",
+        tuple((
+            tag_no_case("offset"),
+            multispace0,
+            tag("("),
+            parse_float,
+            multispace0,
+            tag(")"),
+            multispace0,
+        )),
+    )(i)?;
+
+    let (i, (_, inner, _, _)) = context(
+        "offset_body",
+        delimited(
+            tag("{"),
+            cut(tuple((multispace0, parse_geo, multispace0, opt(tag(","))))),
+            tuple((multispace0, tag("}"), multispace0)),
+        ),
+    )(i)?;
+
+    Ok((
+        i,
+        AST::Offset {
+            amount,
+            inner: Box::new(inner),
+            span: (0, 0),
+        },
+    ))
+}
+
+fn parse_intersect(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
+    let (i, _) = multispace0(i)?;
+
+    let (i, (_, _, left, _, _, right, _, _)) = context(
+        "intersect",
+        delimited(
+            tuple((tag_no_case("intersect"), multispace0)),
+            cut(tuple((
+                multispace0,
+                tag("{"),
+                parse_geo,
+                multispace0,
+                opt(tag(",")),
+                parse_geo,
+                multispace0,
+                opt(tag(",")),
+            ))),
+            tuple((multispace0, tag("}"), multispace0)),
+        ),
+    )(i)?;
+
+    Ok((
+        i,
+        AST::Intersect {
+            left: Box::new(left),
+            right: Box::new(right),
+            span: (0, 0),
+        },
+    ))
+}
+
+fn parse_circular_array(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
+    let (i, _) = multispace0(i)?;
+
+    let (i, (_, _, _, (count, _, _, radius, start_angle), _, _, _)) = context(
+        "circle_array",
+        tuple((
+            tag_no_case("circle_array"),
+            multispace0,
+            tag("("),
+            cut(tuple((
+                parse_uint,
+                multispace0,
+                tag(","),
+                parse_float,
+                opt(tuple((multispace0, tag(","), parse_float))),
+            ))),
+            multispace0,
+            tag(")"),
+            multispace0,
+        )),
+    )(i)?;
+
+    let (i, (_, inner, _)) = context(
+        "circle_array_body",
+        delimited(
+            tag("{"),
+            tuple((multispace0, parse_geo, multispace0)),
+            tuple((tag("}"), multispace0)),
+        ),
+    )(i)?;
+
+    Ok((
+        i,
+        AST::CircularArray {
+            count,
+            radius,
+            start_angle: start_angle.map(|a| a.2),
+            inner: Box::new(inner),
+            span: (0, 0),
+        },
+    ))
+}
+
+fn parse_translate(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
+    let (i, _) = multispace0(i)?;
+
+    let (i, (_, _, _, x, _, y, _, _, _)) = context(
+        "translate",
+        tuple((
+            tag_no_case("translate"),
+            multispace0,
+            tag("("),
+            parse_float,
+            tuple((multispace0, tag(","))),
+            parse_float,
+            multispace0,
+            tag(")"),
+            multispace0,
+        )),
+    )(i)?;
+
+    let (i, (_, inners)) = context(
+        "translate_body",
+        delimited(
+            tag("{"),
+            tuple((
+                multispace0,
+                fold_many1(
+                    tuple((parse_geo, multispace0, opt(tag(",")))),
+                    Vec::new(),
+                    |mut acc, (inner, _, _)| {
+                        acc.push(Box::new(inner));
+                        acc
+                    },
+                ),
+            )),
+            tuple((tag("}"), multispace0)),
+        ),
+    )(i)?;
+
+    Ok((
+        i,
+        AST::Translate {
+            offset: (x, y),
+            inners,
+            span: (0, 0),
+        },
+    ))
 }
 
 fn parse_rotate(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
@@ -1197,29 +3553,108 @@ fn parse_rotate(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
         AST::Rotate {
             rotation: rotation,
             inners: inners,
+            span: (0, 0),
         },
     ))
 }
 
-fn parse_geo(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
-    let (i, feature) = alt((
-        parse_assign,
-        parse_cel,
-        parse_array,
-        parse_rect,
-        parse_circle,
-        parse_triangle,
-        parse_rmount,
-        parse_wrap,
-        parse_column_layout,
-        parse_var,
-        parse_tuple,
-        parse_negative,
-        parse_rotate,
-        parse_comment,
-    ))(i)?;
-
-    let (i, name) = opt(tuple((multispace0, tag("%"), parse_ident)))(i)?;
+fn parse_mirror(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
+    let (i, _) = multispace0(i)?;
+
+    let (i, (_, _, _, axis, _, _, _)) = context(
+        "mirror",
+        tuple((
+            tag_no_case("mirror"),
+            multispace0,
+            tag("("),
+            alt((
+                map(tag_no_case("x"), |_| crate::features::MirrorAxis::X),
+                map(tag_no_case("y"), |_| crate::features::MirrorAxis::Y),
+            )),
+            multispace0,
+            tag(")"),
+            multispace0,
+        )),
+    )(i)?;
+
+    let (i, (_, inners)) = context(
+        "mirror_body",
+        delimited(
+            tag("{"),
+            tuple((
+                multispace0,
+                fold_many1(
+                    tuple((parse_geo, multispace0, opt(tag(",")))),
+                    Vec::new(),
+                    |mut acc, (inner, _, _)| {
+                        acc.push(Box::new(inner));
+                        acc
+                    },
+                ),
+            )),
+            tuple((tag("}"), multispace0)),
+        ),
+    )(i)?;
+
+    Ok((
+        i,
+        AST::Mirror {
+            axis,
+            inners,
+            span: (0, 0),
+        },
+    ))
+}
+
+fn parse_geo(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
+    let (start, _) = multispace0(i)?;
+    // Alternatives are grouped, as alt() accepts a limited number of
+    // parsers.
+    let (i, mut feature) = alt((
+        alt((
+            parse_assign,
+            parse_fn_def,
+            parse_fn_call,
+            parse_cel,
+            parse_grid,
+            parse_array,
+            parse_rect,
+            parse_castellation,
+            parse_circular_array,
+            parse_circle,
+            parse_ellipse,
+            parse_regular_polygon,
+            parse_translate,
+            parse_tab,
+            parse_triangle,
+            parse_trapezoid,
+            parse_rmount,
+            parse_mouse_bite,
+            parse_mirror,
+        )),
+        alt((
+            parse_bezier,
+            parse_gold_fingers,
+            parse_sector,
+            parse_wrap,
+            parse_column_layout,
+            parse_row_layout,
+            parse_var,
+            parse_tuple,
+            parse_negative,
+            parse_intersect,
+            parse_offset,
+            parse_rotate,
+            parse_conditional,
+            parse_for_loop,
+            parse_import,
+            parse_block_comment,
+            parse_comment,
+        )),
+    ))(start)?;
+    *feature.span_mut() = span_remaining(start, i);
+
+    let (i, name) = opt(tuple((multispace0, tag("%"), parse_name)))(i)?;
 
     if let Some((_, _, name)) = name {
         return Ok((
@@ -1227,6 +3662,7 @@ fn parse_geo(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
             AST::Name {
                 name: name,
                 inner: Box::new(feature),
+                span: span_remaining(start, i),
             },
         ));
     }
@@ -1234,32 +3670,115 @@ fn parse_geo(i: &str) -> IResult<&str, AST, VerboseError<&str>> {
     Ok((i, feature))
 }
 
+/// Computes the (unrebased) span of the text consumed between the
+/// start and end inputs, excluding any trailing whitespace.
+fn span_remaining(start: &str, end: &str) -> Span {
+    let consumed = &start[..start.len() - end.len()];
+    (start.len(), start.len() - consumed.trim_end().len())
+}
+
+/// Parses the provided panel spec and returns the syntax tree of each
+/// top-level statement, without resolving variables or computing geometry.
+pub fn ast(i: &str) -> Result<Vec<AST>, Err> {
+    let (_, (mut g, _)) = all_consuming(tuple((many0(parse_geo), multispace0)))(i)
+        .map_err(|e| Err::from_nom(i, e))?;
+
+    for node in g.iter_mut() {
+        node.rebase_span(i.len());
+    }
+    Ok(g)
+}
+
 /// Parses the provided panel spec and returns the series of features
 /// it represents.
+#[cfg(test)]
 pub fn build<'a>(i: &str) -> Result<Vec<Box<dyn super::Feature + 'a>>, Err> {
-    let mut ctx = ResolverContext::default();
-    let (_, (g, _)) = all_consuming(tuple((many0(parse_geo), multispace0)))(i).map_err(|e| {
-        Err::Parse(nom::error::convert_error(
-            i,
-            match e {
-                nom::Err::Error(e) | nom::Err::Failure(e) => e,
-                _ => unreachable!(),
-            },
-        ))
-    })?;
+    build_with_layer(i, None)
+}
 
-    g.into_iter()
-        .map(|g| match g {
-            AST::Assign(var, geo) => {
-                ctx.handle_assignment(var, geo);
-                None
+/// Builds the features described by the spec, placing single-sided
+/// features on the given layer if one is provided.
+pub fn build_with_layer<'a>(
+    i: &str,
+    default_layer: Option<crate::Layer>,
+) -> Result<Vec<Box<dyn super::Feature + 'a>>, Err> {
+    let mut ctx = ResolverContext {
+        default_layer,
+        ..ResolverContext::default()
+    };
+    build_in_context(i, &mut ctx)
+}
+
+/// Reads and builds the spec at the given path, placing single-sided
+/// features on the given layer if one is provided. Specs built this way
+/// may import other specs, which are resolved relative to the importing
+/// file.
+pub fn build_from_file_with_layer<'a>(
+    path: &Path,
+    default_layer: Option<crate::Layer>,
+) -> Result<Vec<Box<dyn super::Feature + 'a>>, Err> {
+    let mut ctx = ResolverContext {
+        default_layer,
+        ..ResolverContext::default()
+    };
+    build_file_in_context(path, &mut ctx)
+}
+
+fn build_file_in_context<'a>(
+    path: &Path,
+    ctx: &mut ResolverContext,
+) -> Result<Vec<Box<dyn super::Feature + 'a>>, Err> {
+    let read_err = |e: std::io::Error| Err::parse(format!("failed to read {:?}: {}", path, e));
+    let path = path.canonicalize().map_err(read_err)?;
+    if ctx.import_stack.contains(&path) {
+        return Err(Err::parse(format!("circular import of {:?}", path)));
+    }
+    let spec = std::fs::read_to_string(&path).map_err(read_err)?;
+
+    let base_path = std::mem::replace(&mut ctx.base_path, path.parent().map(Path::to_path_buf));
+    ctx.import_stack.push(path);
+    let out = build_in_context(&spec, ctx);
+    ctx.import_stack.pop();
+    ctx.base_path = base_path;
+    out
+}
+
+fn build_in_context<'a>(
+    i: &str,
+    ctx: &mut ResolverContext,
+) -> Result<Vec<Box<dyn super::Feature + 'a>>, Err> {
+    let (_, (g, _)) = all_consuming(tuple((many0(parse_geo), multispace0)))(i)
+        .map_err(|e| Err::from_nom(i, e))?;
+
+    let mut out = Vec::with_capacity(g.len());
+    for g in g.into_iter() {
+        match g {
+            AST::Assign(var, geo, _) => ctx.handle_assignment(var, geo),
+            AST::FnDef {
+                name, params, body, ..
+            } => {
+                ctx.functions.insert(name, (params, *body));
             }
-            AST::Comment(_) => None,
-            _ => Some(g.into_feature(&mut ctx)),
-        })
-        .filter(|f| f.is_some())
-        .map(|f| f.unwrap())
-        .collect()
+            AST::Comment(..) => {}
+            c @ AST::Conditional { .. } | c @ AST::ForLoop { .. } | c @ AST::Translate { .. } => {
+                out.append(&mut c.into_features(ctx)?)
+            }
+            AST::Import(path, _) => {
+                let path = match &ctx.base_path {
+                    Some(base) => base.join(path),
+                    None => {
+                        return Err(Err::parse(format!(
+                        "cannot import {:?}: imports are only supported in specs read from a file",
+                        path
+                    )))
+                    }
+                };
+                out.append(&mut build_file_in_context(&path, ctx)?);
+            }
+            _ => out.push(g.into_feature(ctx)?),
+        }
+    }
+    Ok(out)
 }
 
 #[cfg(test)]
@@ -1270,14 +3789,14 @@ mod tests {
     fn test_rect() {
         let out = parse_geo("R<@(1,2)>");
         assert!(
-            matches!(out, Ok(("", AST::Rect{ coords: Some((Value::Float(x), Value::Float(y))), size: None, inner: _, rounded: None })) if
+            matches!(out, Ok(("", AST::Rect{ coords: Some((Value::Float(x), Value::Float(y))), size: None, inner: _, rounded: None, .. })) if
                 x > 0.99 && x < 1.01 && y > 1.99 && y < 2.01
             )
         );
 
         let out = parse_geo("R<@(1,2), 2, 4>");
         assert!(
-            matches!(out, Ok(("", AST::Rect{ coords: Some((Value::Float(x), Value::Float(y))), size: Some((Value::Float(w), Value::Float(h))), inner: _, rounded: None })) if
+            matches!(out, Ok(("", AST::Rect{ coords: Some((Value::Float(x), Value::Float(y))), size: Some((Value::Float(w), Value::Float(h))), inner: _, rounded: None, .. })) if
                 x > 0.99 && x < 1.01 && y > 1.99 && y < 2.01 &&
                 w > 1.99 && w < 2.01 && h > 3.99 && h < 4.01
             )
@@ -1285,7 +3804,7 @@ mod tests {
 
         let out = parse_geo("R<@(1,2), 4>");
         assert!(
-            matches!(out, Ok(("", AST::Rect{ coords: Some((Value::Float(x), Value::Float(y))), size: Some((Value::Float(w), Value::Float(h))), inner: _, rounded: None })) if
+            matches!(out, Ok(("", AST::Rect{ coords: Some((Value::Float(x), Value::Float(y))), size: Some((Value::Float(w), Value::Float(h))), inner: _, rounded: None, .. })) if
                 x > 0.99 && x < 1.01 && y > 1.99 && y < 2.01 &&
                 w > 3.99 && w < 4.01 && h > 3.99 && h < 4.01
             )
@@ -1293,14 +3812,14 @@ mod tests {
 
         let out = parse_geo("R<4>");
         assert!(
-            matches!(out, Ok(("", AST::Rect{ coords: None, size: Some((Value::Float(w), Value::Float(h))), inner: _, rounded: None })) if
+            matches!(out, Ok(("", AST::Rect{ coords: None, size: Some((Value::Float(w), Value::Float(h))), inner: _, rounded: None, .. })) if
                 w > 3.99 && w < 4.01 && h > 3.99 && h < 4.01
             )
         );
 
         let out = parse_geo("R<@(1,2), size = (2,4)>");
         assert!(
-            matches!(out, Ok(("", AST::Rect{ coords: Some((Value::Float(x), Value::Float(y))), size: Some((Value::Float(w), Value::Float(h))), inner: _, rounded: None })) if
+            matches!(out, Ok(("", AST::Rect{ coords: Some((Value::Float(x), Value::Float(y))), size: Some((Value::Float(w), Value::Float(h))), inner: _, rounded: None, .. })) if
                 x > 0.99 && x < 1.01 && y > 1.99 && y < 2.01 &&
                 w > 1.99 && w < 2.01 && h > 3.99 && h < 4.01
             )
@@ -1308,7 +3827,7 @@ mod tests {
 
         let out = parse_geo(" R<6>(h)");
         assert!(
-            matches!(out, Ok(("", AST::Rect{ coords: None, size: Some((Value::Float(w), Value::Float(h))), inner: Some(InnerAST::ScrewHole(Value::Float(dia))), rounded: None })) if
+            matches!(out, Ok(("", AST::Rect{ coords: None, size: Some((Value::Float(w), Value::Float(h))), inner: Some(InnerAST::ScrewHole(Value::Float(dia))), rounded: None, .. })) if
                 w > 5.99 && w < 6.01 && h > 5.99 && h < 6.01 &&
                 dia < 3.11 && dia > 3.09
             )
@@ -1316,18 +3835,54 @@ mod tests {
 
         let out = parse_geo(" R<6, round = 2>(h)");
         assert!(
-            matches!(out, Ok(("", AST::Rect{ coords: None, size: Some((Value::Float(w), Value::Float(h))), inner: Some(InnerAST::ScrewHole(Value::Float(dia))), rounded: Some(_) })) if
+            matches!(out, Ok(("", AST::Rect{ coords: None, size: Some((Value::Float(w), Value::Float(h))), inner: Some(InnerAST::ScrewHole(Value::Float(dia))), rounded: Some(_), .. })) if
                 w > 5.99 && w < 6.01 && h > 5.99 && h < 6.01 &&
                 dia < 3.11 && dia > 3.09
             )
         );
     }
 
+    #[test]
+    fn test_ellipse() {
+        let out = parse_geo("E<10, 5>");
+        assert!(
+            matches!(out, Ok(("", AST::Ellipse{ coords: None, radii: (a, b), inner: None, .. })) if
+                a.float() > 9.99 && a.float() < 10.01 && b.float() > 4.99 && b.float() < 5.01
+            )
+        );
+
+        let out = parse_geo("E<@(2, 1), 3, 2>(h)");
+        assert!(
+            matches!(out, Ok(("", AST::Ellipse{ coords: Some((Value::Float(x), Value::Float(y))), inner: Some(InnerAST::ScrewHole(_)), .. })) if
+                y > 0.99 && y < 1.01 && x > 1.99 && x < 2.01
+            )
+        );
+
+        assert!(parse_geo("E<10>").is_err());
+    }
+
+    #[test]
+    fn test_regular_polygon() {
+        let out = parse_geo("P<6, 5>");
+        assert!(
+            matches!(out, Ok(("", AST::RegularPolygon{ coords: None, sides, radius, inner: None, .. })) if
+                sides.float() > 5.99 && sides.float() < 6.01 && radius.float() > 4.99 && radius.float() < 5.01
+            )
+        );
+
+        let out = parse_geo("P<@(2, 1), 4, 3>(h)");
+        assert!(
+            matches!(out, Ok(("", AST::RegularPolygon{ coords: Some((Value::Float(x), Value::Float(y))), inner: Some(InnerAST::ScrewHole(_)), .. })) if
+                y > 0.99 && y < 1.01 && x > 1.99 && x < 2.01
+            )
+        );
+    }
+
     #[test]
     fn test_circle() {
         let out = parse_geo("C < @ ( 2 , 1 ), 4.5>");
         assert!(
-            matches!(out, Ok(("", AST::Circle{ coords: Some((Value::Float(x), Value::Float(y))), radius: r, inner: _ })) if
+            matches!(out, Ok(("", AST::Circle{ coords: Some((Value::Float(x), Value::Float(y))), radius: r, inner: _, .. })) if
                 y > 0.99 && y < 1.01 && x > 1.99 && x < 2.01 &&
                 r.float() > 4.49 && r.float() < 4.51
             )
@@ -1335,73 +3890,507 @@ mod tests {
 
         let out = parse_geo("C<@(2, 1), 3.5>");
         assert!(
-            matches!(out, Ok(("", AST::Circle{ coords: Some((Value::Float(x), Value::Float(y))), radius: r, inner: _ })) if
+            matches!(out, Ok(("", AST::Circle{ coords: Some((Value::Float(x), Value::Float(y))), radius: r, inner: _, .. })) if
                 y > 0.99 && y < 1.01 && x > 1.99 && x < 2.01 &&
                 r.float() > 3.49 && r.float() < 3.51
             )
         );
-        let out = parse_geo("C<3.5>");
+        let out = parse_geo("C<3.5>");
+        assert!(
+            matches!(out, Ok(("", AST::Circle{ coords: None, radius: r, inner: _, .. })) if
+                r.float() > 3.49 && r.float() < 3.51
+            )
+        );
+
+        let out = parse_geo("C<@(2, 1), R=3.5>");
+        assert!(
+            matches!(out, Ok(("", AST::Circle{ coords: Some((Value::Float(x), Value::Float(y))), radius: r, inner: _, .. })) if
+                y > 0.99 && y < 1.01 && x> 1.99 && x < 2.01 &&
+                r.float() > 3.49 && r.float() < 3.51
+            )
+        );
+
+        let out = parse_geo("C<3.5> ( h9 )");
+        assert!(
+            matches!(out, Ok(("", AST::Circle{ coords: None, radius: r, inner: Some(InnerAST::ScrewHole(Value::Float(dia))), .. })) if
+                r.float() > 3.49 && r.float() < 3.51 && dia > 8.999 && dia < 9.001
+            )
+        );
+
+        let out = parse_geo("C<3.5>(h3, cs=60)");
+        assert!(
+            matches!(out, Ok(("", AST::Circle{ inner: Some(InnerAST::CounterSunkScrewHole{ dia: Value::Float(dia), angle: Value::Float(angle) }), .. })) if
+                dia > 2.999 && dia < 3.001 && angle > 59.999 && angle < 60.001
+            )
+        );
+        assert!(matches!(
+            build("let a = !{90}\nC<3.5>(h3, cs=$a)"),
+            Ok(f) if f.len() == 1
+        ));
+    }
+
+    #[test]
+    fn test_inner_drill() {
+        let out = parse_geo("C<5>(drill<1.5>)");
+        assert!(matches!(
+            out,
+            Ok(("", AST::Circle { inner: Some(InnerAST::Drill{ dia: Value::Float(d), plated: true }), .. })) if d > 1.49 && d < 1.51
+        ));
+
+        let out = parse_geo("C<5>(drill<2, npth>)");
+        assert!(matches!(
+            out,
+            Ok(("", AST::Circle { inner: Some(InnerAST::Drill{ dia: Value::Float(d), plated: false }), .. })) if d > 1.99 && d < 2.01
+        ));
+
+        let drill_radius = |spec| {
+            build(spec)
+                .unwrap()
+                .iter()
+                .map(|f| f.interior())
+                .flatten()
+                .find_map(|a| match a {
+                    crate::features::InnerAtom::Drill { radius, .. } => Some(radius),
+                    _ => None,
+                })
+        };
+        assert_eq!(drill_radius("let d = !{2}\nR<5>(drill<$d>)"), Some(1.));
+        assert_eq!(drill_radius("R<5>(drill<!{1+2}>)"), Some(1.5));
+        assert!(matches!(
+            build("R<5>(drill<$missing>)"),
+            Err(Err::UndefinedVariable(v)) if v == "missing"
+        ));
+    }
+
+    #[test]
+    fn test_msp() {
+        let out = parse_geo("C<5>(msp)");
+        assert!(matches!(
+            out,
+            Ok((
+                "",
+                AST::Circle {
+                    inner: Some(InnerAST::MechanicalSolderPoint {
+                        size: None,
+                        plated: true,
+                        paste: true,
+                    }),
+                    ..
+                },
+            ))
+        ));
+
+        let out = parse_geo("C<5>(msp<2,1>)");
+        assert!(matches!(
+            out,
+            Ok((
+                "",
+                AST::Circle { inner: Some(InnerAST::MechanicalSolderPoint{ size: Some((Value::Float(w), Value::Float(h))), plated: true, paste: true }), .. },
+            )) if w > 1.99 && w < 2.01 && h > 0.99 && h < 1.01
+        ));
+
+        let out = parse_geo("C<5>(msp<2, 1, npth>)");
+        assert!(matches!(
+            out,
+            Ok((
+                "",
+                AST::Circle {
+                    inner: Some(InnerAST::MechanicalSolderPoint {
+                        size: Some(_),
+                        plated: false,
+                        paste: true
+                    }),
+                    ..
+                },
+            ))
+        ));
+
+        let out = parse_geo("C<5>(msp<2, 1, nopaste>)");
+        assert!(matches!(
+            out,
+            Ok((
+                "",
+                AST::Circle {
+                    inner: Some(InnerAST::MechanicalSolderPoint {
+                        size: Some(_),
+                        plated: true,
+                        paste: false
+                    }),
+                    ..
+                },
+            ))
+        ));
+
+        // Flags are only accepted by the features which use them.
+        assert!(parse_geo("C<5, npth>").is_err());
+        assert!(parse_geo("R<5, iso>").is_err());
+        assert!(build("C<5>(drill<2, nopaste>)").is_err());
+    }
+
+    #[test]
+    fn test_inner_line() {
+        let out = parse_geo("R<10>(line<@(-2, 0), @(2,1), width=0.2>)");
+        assert!(matches!(
+            out,
+            Ok((
+                "",
+                AST::Rect { inner: Some(InnerAST::Line{ start: (Value::Float(x1), _), end: (_, Value::Float(y2)), width: Value::Float(w) }), .. },
+            )) if x1 < -1.99 && x1 > -2.01 && y2 > 0.99 && y2 < 1.01 && w > 0.19 && w < 0.21
+        ));
+
+        let out = parse_geo("R<10>(line<@(0,0), @(1,1)>)");
+        assert!(matches!(
+            out,
+            Ok((
+                "",
+                AST::Rect { inner: Some(InnerAST::Line{ width: Value::Float(w), .. }), .. },
+            )) if w > 0.14 && w < 0.16
+        ));
+        assert!(matches!(
+            build("let x = !{1}\nR<5>(line<@(0,0), @($x,1), width=!{0.2}>)"),
+            Ok(f) if f.len() == 1
+        ));
+    }
+
+    #[test]
+    fn test_inner_text() {
+        let out = parse_geo("R<10>(text<@(-2, 1), \"REV1\", size=1.6>)");
+        assert!(matches!(
+            out,
+            Ok((
+                "",
+                AST::Rect { inner: Some(InnerAST::Text{ coords: (Value::Float(x), _), text, size: Some(Value::Float(s)) }), .. },
+            )) if x < -1.99 && x > -2.01 && text == "REV1" && s > 1.59 && s < 1.61
+        ));
+
+        let out = parse_geo("R<10>(text<@(0,0), \"A\">)");
+        assert!(matches!(
+            out,
+            Ok((
+                "",
+                AST::Rect {
+                    inner: Some(InnerAST::Text { size: None, .. }),
+                    ..
+                }
+            ))
+        ));
+        assert!(parse_geo("R<10>(text<@(0,0), REV1>)").is_err());
+        assert!(matches!(
+            build("let s = !{2}\nR<10>(text<@($s, !{-1}), \"A\", size=$s>)"),
+            Ok(f) if f.len() == 1
+        ));
+    }
+
+    #[test]
+    fn test_inner_pad() {
+        let out = parse_geo("R<10>(pad<@(1, 0), 2, 1>)");
+        assert!(matches!(
+            out,
+            Ok((
+                "",
+                AST::Rect { inner: Some(InnerAST::Pad{ coords: (Value::Float(x), _), size: (Value::Float(w), _), back: false }), .. },
+            )) if x > 0.99 && x < 1.01 && w > 1.99 && w < 2.01
+        ));
+        assert!(matches!(
+            parse_geo("R<10>(pad<@(0,0), 2, 1, back>)"),
+            Ok((
+                "",
+                AST::Rect {
+                    inner: Some(InnerAST::Pad { back: true, .. }),
+                    ..
+                }
+            ))
+        ));
+        assert!(parse_geo("R<10>(pad<@(0,0), 2>)").is_err());
+        assert!(matches!(
+            build("let w = !{2}\nR<10>(pad<@($w, 0), $w, !{0.5 * 2}>)"),
+            Ok(f) if f.len() == 1
+        ));
+
+        let pads =
+            build_with_layer("R<10>(pad<@(0,0), 2, 1>)", Some(crate::Layer::BackCopper)).unwrap();
+        let atoms = pads[0].interior();
+        assert_eq!(atoms.len(), 2);
+        assert!(atoms
+            .iter()
+            .all(|a| a.layer().and_then(|l| l.side()) == Some(crate::Side::Back)));
+    }
+
+    #[test]
+    fn test_inner_slot() {
+        let out = parse_geo("C<5>(slot<@(-1,0), @(1, 0), 0.6>)");
+        assert!(matches!(
+            out,
+            Ok((
+                "",
+                AST::Circle { inner: Some(InnerAST::Slot{ start: (Value::Float(x1), _), end: (Value::Float(x2), _), width: Value::Float(w) }), .. },
+            )) if x1 < -0.99 && x1 > -1.01 && x2 > 0.99 && x2 < 1.01 && w > 0.59 && w < 0.61
+        ));
+        assert!(parse_geo("C<5>(slot<@(-1,0), @(1, 0)>)").is_err());
+        assert!(matches!(
+            build("let w = !{0.6}\nC<5>(slot<@(-1,0), @(!{0.5 * 2}, 0), $w>)"),
+            Ok(f) if f.len() == 1
+        ));
+    }
+
+    #[test]
+    fn test_inner_fiducial() {
+        assert!(matches!(
+            parse_geo("C<5>(fiducial)"),
+            Ok((
+                "",
+                AST::Circle {
+                    inner: Some(InnerAST::Fiducial(None)),
+                    ..
+                },
+            ))
+        ));
+        assert!(matches!(
+            parse_geo("C<5>(fiducial<@(1, -1)>)"),
+            Ok((
+                "",
+                AST::Circle { inner: Some(InnerAST::Fiducial(Some((Value::Float(x), Value::Float(y))))), .. },
+            )) if x > 0.99 && x < 1.01 && y < -0.99 && y > -1.01
+        ));
+        assert!(matches!(
+            build("let x = !{1}\nC<5>(fiducial<@($x, !{-1})>)"),
+            Ok(f) if f.len() == 1
+        ));
+    }
+
+    #[test]
+    fn test_inner_via() {
+        assert!(matches!(
+            parse_geo("C<5>(via<@(1, 0), 0.3>)"),
+            Ok((
+                "",
+                AST::Circle { inner: Some(InnerAST::Via{ coords: (Value::Float(x), _), dia: Value::Float(d), ring: None }), .. },
+            )) if x > 0.99 && x < 1.01 && d > 0.29 && d < 0.31
+        ));
+        assert!(matches!(
+            parse_geo("C<5>(via<@(0, 0), 0.3, ring=0.15>)"),
+            Ok((
+                "",
+                AST::Circle { inner: Some(InnerAST::Via{ ring: Some(Value::Float(r)), .. }), .. },
+            )) if r > 0.14 && r < 0.16
+        ));
+        assert!(parse_geo("C<5>(via<0.3>)").is_err());
+        assert!(matches!(
+            build("fn f(d) = R<5>(via<@(0,0), $d>)\nf(1)"),
+            Ok(f) if f.len() == 1
+        ));
+    }
+
+    #[test]
+    fn test_mouse_bite() {
+        let out = parse_geo("mouse_bite<10, 0.5>");
+        assert!(matches!(
+            out,
+            Ok(("", AST::MouseBite{ coords: None, length: Value::Float(l), hole_diameter: Value::Float(d), spacing: None, dir: crate::Direction::Right, .. })) if
+                l > 9.99 && l < 10.01 && d > 0.49 && d < 0.51
+        ));
+
+        let out = parse_geo("mouse_bite<@(1, 2), 5, 0.5, 0.75, down>");
+        assert!(matches!(
+            out,
+            Ok(("", AST::MouseBite{ coords: Some(_), spacing: Some(Value::Float(s)), dir: crate::Direction::Down, .. })) if
+                s > 0.74 && s < 0.76
+        ));
+
+        assert!(parse_geo("mouse_bite<10>").is_err());
+    }
+
+    #[test]
+    fn test_tab() {
+        let out = parse_geo("tab<2, 5>");
+        assert!(matches!(
+            out,
+            Ok(("", AST::Tab{ width: Value::Float(w), length: Value::Float(l), dir: crate::Direction::Right, .. })) if
+                w > 1.99 && w < 2.01 && l > 4.99 && l < 5.01
+        ));
+
+        let out = parse_geo("tab<2, 5, dir=down>");
+        assert!(matches!(
+            out,
+            Ok((
+                "",
+                AST::Tab {
+                    dir: crate::Direction::Down,
+                    ..
+                }
+            ))
+        ));
+        assert!(parse_geo("tab<2, 5, dir=sideways>").is_err());
+    }
+
+    #[test]
+    fn test_castellation() {
+        let out = parse_geo("castellation<8, 1.0>");
+        assert!(matches!(
+            out,
+            Ok(("", AST::Castellation{ count: Value::Float(c), hole_diameter: Value::Float(d), side: crate::Direction::Left, plated: true, .. })) if
+                c > 7.99 && c < 8.01 && d > 0.99 && d < 1.01
+        ));
+
+        let out = parse_geo("castellation<8, 1.0, side=bottom, npth>");
+        assert!(matches!(
+            out,
+            Ok((
+                "",
+                AST::Castellation {
+                    side: crate::Direction::Down,
+                    plated: false,
+                    ..
+                }
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_triangle() {
+        let out = parse_geo("T<2,1>");
+        assert!(
+            matches!(out, Ok(("", AST::Triangle{ size: (Value::Float(x), Value::Float(y)), inner: _, .. })) if
+                y > 0.99 && y < 1.01 && x > 1.99 && x < 2.01
+            )
+        );
+
+        let out = parse_geo("T<4, 3, iso>");
+        assert!(
+            matches!(out, Ok(("", AST::Triangle{ size: (Value::Float(x), Value::Float(y)), kind: TriangleKind::Isoceles, .. })) if
+                y > 2.99 && y < 3.01 && x > 3.99 && x < 4.01
+            )
+        );
+        let out = parse_geo("T<4, equi>");
+        assert!(
+            matches!(out, Ok(("", AST::Triangle{ size: (Value::Float(x), _), kind: TriangleKind::Equilateral, .. })) if
+                x > 3.99 && x < 4.01
+            )
+        );
+    }
+
+    #[test]
+    fn test_trapezoid() {
+        let out = parse_geo("Z<top=3, bottom=6, h=4>");
+        assert!(
+            matches!(out, Ok(("", AST::Trapezoid{ top: Value::Float(t), bottom: Value::Float(b), height: Value::Float(h), coords: None, inner: None, .. })) if
+                t > 2.99 && t < 3.01 && b > 5.99 && b < 6.01 && h > 3.99 && h < 4.01
+            )
+        );
+
+        let out = parse_geo("Z<@(1, 2), h = 4, top = 3, bottom = 6>(h3)");
+        assert!(matches!(
+            out,
+            Ok((
+                "",
+                AST::Trapezoid {
+                    coords: Some(_),
+                    inner: Some(InnerAST::ScrewHole(_)),
+                    ..
+                }
+            ))
+        ));
+
+        assert!(parse_geo("Z<top=3, h=4>").is_err());
+    }
+
+    #[test]
+    fn test_r_mount() {
+        let out = parse_geo("mount_cut<12>");
+        assert!(matches!(out, Ok(("", AST::RMount{ depth, dir, .. })) if
+            depth.float() > 11.99 && depth.float() < 12.01 && dir == crate::Direction::Up
+        ));
+    }
+
+    #[test]
+    fn test_bezier() {
+        let out = parse_geo("bezier[(0,0), (5,10), (10,0)]");
+        assert!(
+            matches!(out, Ok(("", AST::Bezier{ points, .. })) if points.len() == 3 &&
+                matches!(points[1], (Value::Float(x), Value::Float(y)) if
+                    x > 4.99 && x < 5.01 && y > 9.99 && y < 10.01
+                )
+            )
+        );
+
+        let out = build("bezier[(0,0), (5,10)]");
+        assert!(matches!(out, Err(Err::Parse { .. })));
+    }
+
+    #[test]
+    fn test_gold_fingers() {
+        let out = parse_geo("gold_fingers<8, 2.54, 1.5, 5>");
         assert!(
-            matches!(out, Ok(("", AST::Circle{ coords: None, radius: r, inner: _ })) if
-                r.float() > 3.49 && r.float() < 3.51
+            matches!(out, Ok(("", AST::GoldFingers{ count, pitch, double_sided: false, .. })) if
+                count.float() > 7.99 && count.float() < 8.01 && pitch.float() > 2.53 && pitch.float() < 2.55
             )
         );
+        let out = parse_geo("gold_fingers<8, 2.54, 1.5, 5, double>");
+        assert!(matches!(
+            out,
+            Ok((
+                "",
+                AST::GoldFingers {
+                    double_sided: true,
+                    ..
+                }
+            ))
+        ));
 
-        let out = parse_geo("C<@(2, 1), R=3.5>");
+        let out = build("gold_fingers<8, 2.54>");
+        assert!(matches!(out, Err(Err::Parse { .. })));
+    }
+
+    #[test]
+    fn test_sector() {
+        let out = parse_geo("sector<5, 0, 90>");
         assert!(
-            matches!(out, Ok(("", AST::Circle{ coords: Some((Value::Float(x), Value::Float(y))), radius: r, inner: _ })) if
-                y > 0.99 && y < 1.01 && x> 1.99 && x < 2.01 &&
-                r.float() > 3.49 && r.float() < 3.51
+            matches!(out, Ok(("", AST::Sector{ coords: None, radius, angles: (start, end), .. })) if
+                radius.float() > 4.99 && radius.float() < 5.01 && start.float() < 0.01 && end.float() > 89.99
             )
         );
-
-        let out = parse_geo("C<3.5> ( h9 )");
+        let out = parse_geo("sector<@(1, 2), 5, 0, 90>");
         assert!(
-            matches!(out, Ok(("", AST::Circle{ coords: None, radius: r, inner: Some(InnerAST::ScrewHole(Value::Float(dia))) })) if
-                r.float() > 3.49 && r.float() < 3.51 && dia > 8.999 && dia < 9.001
+            matches!(out, Ok(("", AST::Sector{ coords: Some((x, y)), .. })) if
+                x.float() > 0.99 && x.float() < 1.01 && y.float() > 1.99 && y.float() < 2.01
             )
         );
+
+        let out = build("sector<5, 90>");
+        assert!(matches!(out, Err(Err::Parse { .. })));
     }
 
     #[test]
-    fn test_msp() {
-        let out = parse_geo("C<5>(msp)");
+    fn test_circular_array() {
+        let out = parse_geo("circle_array(6, 10) { C<2>(h) }");
         assert!(matches!(
             out,
-            Ok((
-                "",
-                AST::Circle {
-                    inner: Some(InnerAST::MechanicalSolderPoint(None)),
-                    ..
-                },
-            ))
+            Ok(("", AST::CircularArray { count: 6, radius: Value::Float(r), start_angle: None, inner, .. }))
+            if r > 9.99 && r < 10.01 && matches!(*inner, AST::Circle { .. })
         ));
 
-        let out = parse_geo("C<5>(msp<2,1>)");
+        let out = parse_geo("circle_array(4, 5, 45) { R<1> }");
         assert!(matches!(
             out,
-            Ok((
-                "",
-                AST::Circle { inner: Some(InnerAST::MechanicalSolderPoint(Some((Value::Float(w), Value::Float(h))))), .. },
-            )) if w > 1.99 && w < 2.01 && h > 0.99 && h < 1.01
+            Ok(("", AST::CircularArray { count: 4, start_angle: Some(Value::Float(a)), .. }))
+            if a > 44.99 && a < 45.01
         ));
     }
 
     #[test]
-    fn test_triangle() {
-        let out = parse_geo("T<2,1>");
-        assert!(
-            matches!(out, Ok(("", AST::Triangle{ size: (Value::Float(x), Value::Float(y)), inner: _ })) if
-                y > 0.99 && y < 1.01 && x > 1.99 && x < 2.01
-            )
-        );
-    }
+    fn test_grid() {
+        let out = parse_geo("[3x4]R<5>");
+        assert!(matches!(
+            out,
+            Ok(("", AST::Grid { rows: 3, cols: 4, gap: None, inner, .. }))
+            if matches!(*inner, AST::Rect { .. })
+        ));
 
-    #[test]
-    fn test_r_mount() {
-        let out = parse_geo("mount_cut<12>");
-        assert!(matches!(out, Ok(("", AST::RMount{ depth, dir })) if
-            depth.float() > 11.99 && depth.float() < 12.01 && dir == crate::Direction::Up
+        let out = parse_geo("[2 X 5; gap = 1.5] C<2>");
+        assert!(matches!(
+            out,
+            Ok(("", AST::Grid { rows: 2, cols: 5, gap: Some(Value::Float(g)), .. }))
+            if g > 1.49 && g < 1.51
         ));
     }
 
@@ -1409,17 +4398,61 @@ mod tests {
     fn test_array() {
         let out = parse_geo("[5]C<4.5>");
         assert!(
-            matches!(out, Ok(("", AST::Array{ num: 5, inner: b, dir: crate::Direction::Right, vscore: false})) if
+            matches!(out, Ok(("", AST::Array{ num: 5, inner: b, dir: crate::Direction::Right, vscore: false, .. })) if
                 matches!(&*b, AST::Circle{ radius, .. } if radius.float() > 4.4 && radius.float() < 4.6)
             )
         );
 
         let out = parse_geo("[5; D; v-score]C<4.5>");
         assert!(
-            matches!(out, Ok(("", AST::Array{ num: 5, inner: b, dir: crate::Direction::Down, vscore: true})) if
+            matches!(out, Ok(("", AST::Array{ num: 5, inner: b, dir: crate::Direction::Down, vscore: true, .. })) if
                 matches!(&*b, AST::Circle{ radius, .. } if radius.float() > 4.4 && radius.float() < 4.6)
             )
         );
+
+        let out = parse_geo("[5; R; flip]R<5>");
+        assert!(matches!(
+            out,
+            Ok((
+                "",
+                AST::Array {
+                    num: 5,
+                    dir: crate::Direction::Right,
+                    vscore: false,
+                    flip: true,
+                    ..
+                }
+            ))
+        ));
+        let out = parse_geo("[5; D; v-score; flip]R<5>");
+        assert!(matches!(
+            out,
+            Ok((
+                "",
+                AST::Array {
+                    num: 5,
+                    dir: crate::Direction::Down,
+                    vscore: true,
+                    flip: true,
+                    gap: None,
+                    ..
+                }
+            ))
+        ));
+        let out = parse_geo("[5; gap=1.5]R<5>");
+        assert!(matches!(
+            out,
+            Ok((
+                "",
+                AST::Array {
+                    num: 5,
+                    dir: crate::Direction::Right,
+                    gap: Some(Value::Float(g)),
+                    ..
+                }
+            ))
+            if g > 1.49 && g < 1.51
+        ));
     }
 
     #[test]
@@ -1433,6 +4466,7 @@ mod tests {
                     align: crate::Align::Start,
                     inners: i,
                     coords: None,
+                    ..
                 },
             ))
             if i.len() == 1
@@ -1447,6 +4481,7 @@ mod tests {
                     align: crate::Align::End,
                     inners: i,
                     coords: None,
+                    ..
                 },
             ))
             if i.len() == 2
@@ -1462,6 +4497,7 @@ mod tests {
                     align: crate::Align::Center,
                     inners: i,
                     coords: None,
+                    ..
                 },
             ))
             if i.len() == 1
@@ -1477,6 +4513,7 @@ mod tests {
                     align: crate::Align::Center,
                     inners: i,
                     coords: None,
+                    ..
                 },
             ))
             if i.len() == 1 && matches!(*i[0], AST::Tuple{ .. })
@@ -1492,10 +4529,59 @@ mod tests {
                     align: crate::Align::Center,
                     inners: i,
                     coords: Some((Value::Float(x), Value::Float(y))),
+                    ..
                 },
             ))
             if i.len() == 1 && x > 0.99 && x < 1.01 && y > 1.99 && y < 2.01
         ));
+
+        let out = parse_geo("column left gap=1.0 { R<5> R<5> }");
+        assert!(matches!(
+            out,
+            Ok((
+                "",
+                AST::ColumnLayout {
+                    align: crate::Align::Start,
+                    inners: i,
+                    gap: Some(Value::Float(g)),
+                    ..
+                },
+            ))
+            if i.len() == 2 && g > 0.99 && g < 1.01
+        ));
+    }
+
+    #[test]
+    fn test_row_layout() {
+        let out = parse_geo("row top { R<5> }");
+        assert!(matches!(
+            out,
+            Ok((
+                "",
+                AST::RowLayout {
+                    align: crate::Align::Start,
+                    inners: i,
+                    coords: None,
+                    ..
+                },
+            ))
+            if i.len() == 1
+        ));
+
+        let out = parse_geo("row BOTTOM @(1, 2) { C<5> R<1> }");
+        assert!(matches!(
+            out,
+            Ok((
+                "",
+                AST::RowLayout {
+                    align: crate::Align::End,
+                    inners: i,
+                    coords: Some((Value::Float(x), Value::Float(y))),
+                    ..
+                },
+            ))
+            if i.len() == 2 && x > 0.99 && x < 1.01 && y > 1.99 && y < 2.01
+        ));
     }
 
     #[test]
@@ -1504,8 +4590,8 @@ mod tests {
             "wrap ($inner) with { left-0.5 => C<2>(h), right align exterior => C<2>(h4) }",
         );
         // eprintln!("{:?}", out);
-        assert!(matches!(out, Ok(("", AST::Wrap { inner, features })) if
-            matches!(*inner, AST::VarRef(ref var) if var == "inner") && features.len() == 2 &&
+        assert!(matches!(out, Ok(("", AST::Wrap { inner, features, .. })) if
+            matches!(*inner, AST::VarRef(ref var, _) if var == "inner") && features.len() == 2 &&
             matches!(features[1].0, WrapPosition::Cardinal{ align: crate::Align::End, .. })
         ));
 
@@ -1513,7 +4599,7 @@ mod tests {
             "wrap(column center {[12] R<5>(h)}) with {left-0.5 => C<2>(h), right+0.5 => C<2>(h)}",
         );
         // eprintln!("{:?}", out);
-        assert!(matches!(out, Ok(("", AST::Wrap { inner, features })) if
+        assert!(matches!(out, Ok(("", AST::Wrap { inner, features, .. })) if
             matches!(*inner, AST::ColumnLayout{ .. }) && features.len() == 2 &&
             matches!(features[0].0, WrapPosition::Cardinal{ side: Direction::Left, offset: Value::Float(o1), .. } if
             o1 < -0.4 && o1 > -0.6) &&
@@ -1525,16 +4611,16 @@ mod tests {
             "wrap ($inner) with {\n  left => (C<2>(h), C<2>),\n # test comment\n right => (C<2>(h), C<2>),\n}",
         );
         // eprintln!("{:?}", out);
-        assert!(matches!(out, Ok(("", AST::Wrap { inner, features })) if
-            matches!(*inner, AST::VarRef(ref var) if var == "inner") && features.len() == 2 &&
+        assert!(matches!(out, Ok(("", AST::Wrap { inner, features, .. })) if
+            matches!(*inner, AST::VarRef(ref var, _) if var == "inner") && features.len() == 2 &&
             matches!(features[0].0, WrapPosition::Cardinal{ align: crate::Align::Center, .. })
         ));
 
         let out =
             parse_geo("wrap ($inner) with {\n  angle(90) => C<2>,\n  angle(-90) 25 => C<2>,\n}");
         // eprintln!("{:?}", out);
-        assert!(matches!(out, Ok(("", AST::Wrap { inner, features })) if
-            matches!(*inner, AST::VarRef(ref var) if var == "inner") && features.len() == 2 &&
+        assert!(matches!(out, Ok(("", AST::Wrap { inner, features, .. })) if
+            matches!(*inner, AST::VarRef(ref var, _) if var == "inner") && features.len() == 2 &&
             matches!(features[0].0, WrapPosition::Angle{ angle: Value::Float(a1), .. } if
             a1 < 91. && a1 > 89.) &&
             matches!(features[1].0, WrapPosition::Angle{ offset: Value::Float(o2), .. } if
@@ -1542,18 +4628,43 @@ mod tests {
         ));
 
         let out = parse_geo("wrap ($inner) with {\n  center => C<2>,\n}");
-        assert!(matches!(out, Ok(("", AST::Wrap { inner, features })) if
-            matches!(*inner, AST::VarRef(ref var) if var == "inner") && features.len() == 1 &&
+        assert!(matches!(out, Ok(("", AST::Wrap { inner, features, .. })) if
+            matches!(*inner, AST::VarRef(ref var, _) if var == "inner") && features.len() == 1 &&
             matches!(features[0].0, WrapPosition::Angle{ angle: Value::Float(a1), .. } if
             a1 < 0.1 && a1 > -0.1)
         ));
 
         let out = parse_geo("wrap ($inner) with {\n  min-left align exterior => C<2>,\n}");
         // eprintln!("{:?}", out);
-        assert!(matches!(out, Ok(("", AST::Wrap { inner, features })) if
-            matches!(*inner, AST::VarRef(ref var) if var == "inner") && features.len() == 1 &&
+        assert!(matches!(out, Ok(("", AST::Wrap { inner, features, .. })) if
+            matches!(*inner, AST::VarRef(ref var, _) if var == "inner") && features.len() == 1 &&
             matches!(features[0].0, WrapPosition::Corner{ side: Direction::Left, align: crate::Align::End, opposite: false})
         ));
+
+        let out = parse_geo("wrap ($inner) with {\n  absolute(10, 12.5) => C<2>,\n}");
+        assert!(
+            matches!(out, Ok(("", AST::Wrap { features, .. })) if features.len() == 1 &&
+                matches!(features[0].0, WrapPosition::Absolute{ x: Value::Float(x), y: Value::Float(y) } if
+                x > 9.9 && x < 10.1 && y > 12.4 && y < 12.6)
+            )
+        );
+
+        let out = parse_geo("wrap (R<20>) with { [5] left => C<2>(h) }");
+        assert!(
+            matches!(out, Ok(("", AST::Wrap { features, .. })) if features.len() == 1 &&
+                matches!(features[0].0, WrapPosition::Distribute{ side: Direction::Left, count: 5 })
+            )
+        );
+
+        let out =
+            parse_geo("wrap (R<10>) with { stack(right) => C<2.5>, stack(right, 1) => C<2.5> }");
+        assert!(
+            matches!(out, Ok(("", AST::Wrap { features, .. })) if features.len() == 2 &&
+                matches!(features[0].0, WrapPosition::Stack{ side: Direction::Right, gap: None }) &&
+                matches!(features[1].0, WrapPosition::Stack{ side: Direction::Right, gap: Some(Value::Float(g)) } if
+                g > 0.99 && g < 1.01)
+            )
+        );
     }
 
     #[test]
@@ -1561,8 +4672,8 @@ mod tests {
         let out = parse_geo("(C<2>(h))");
         eprintln!("{:?}", out);
         assert!(
-            matches!(out, Ok(("", AST::Tuple{ inners })) if inners.len() == 1 &&
-                matches!(&*inners[0], AST::Circle{ coords: None, radius: r, inner: Some(_) }  if
+            matches!(out, Ok(("", AST::Tuple{ inners, .. })) if inners.len() == 1 &&
+                matches!(&*inners[0], AST::Circle{ coords: None, radius: r, inner: Some(_), .. }  if
                     r.float() > 1.99 && r.float() < 2.01
                 )
             )
@@ -1570,8 +4681,8 @@ mod tests {
 
         let out = parse_geo("(C<2>(h), R<4>)");
         assert!(
-            matches!(out, Ok(("", AST::Tuple{ inners })) if inners.len() == 2 &&
-                matches!(*inners[1], AST::Rect{ coords: None, size: Some((Value::Float(w), Value::Float(h))), inner: _, rounded: None } if
+            matches!(out, Ok(("", AST::Tuple{ inners, .. })) if inners.len() == 2 &&
+                matches!(*inners[1], AST::Rect{ coords: None, size: Some((Value::Float(w), Value::Float(h))), inner: _, rounded: None, .. } if
                     w > 3.99 && w < 4.01 && h > 3.99 && h < 4.01
                 )
             )
@@ -1580,9 +4691,9 @@ mod tests {
         let out = parse_geo("(C<2>(h), (C<2>(h), R<4>))");
         // eprintln!("{:?}", out);
         assert!(
-            matches!(out, Ok(("", AST::Tuple{ inners })) if inners.len() == 2 &&
-                matches!(&*inners[1], AST::Tuple{ inners } if inners.len() == 2 &&
-                    matches!(*inners[1], AST::Rect{ coords: None, size: Some((Value::Float(w), Value::Float(h))), inner: _, rounded: None } if
+            matches!(out, Ok(("", AST::Tuple{ inners, .. })) if inners.len() == 2 &&
+                matches!(&*inners[1], AST::Tuple{ inners, .. } if inners.len() == 2 &&
+                    matches!(*inners[1], AST::Rect{ coords: None, size: Some((Value::Float(w), Value::Float(h))), inner: _, rounded: None, .. } if
                         w > 3.99 && w < 4.01 && h > 3.99 && h < 4.01
                     )
                 )
@@ -1590,13 +4701,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_intersect() {
+        let out = parse_geo("intersect { R<5> C<3> }");
+        assert!(matches!(out, Ok(("", AST::Intersect{ left, right, .. })) if
+            matches!(*left, AST::Rect{ .. }) && matches!(*right, AST::Circle{ .. })
+        ));
+
+        let out = parse_geo("intersect {\n  C<2>,\n  [2; R]R<2>\n}");
+        assert!(matches!(out, Ok(("", AST::Intersect{ left, right, .. })) if
+            matches!(*left, AST::Circle{ .. }) && matches!(*right, AST::Array{ .. })
+        ));
+
+        assert!(parse_geo("intersect { R<5> }").is_err());
+    }
+
+    #[test]
+    fn test_offset() {
+        let out = parse_geo("offset(0.5) { R<5> }");
+        assert!(
+            matches!(out, Ok(("", AST::Offset{ amount: Value::Float(a), inner, .. })) if
+                a > 0.49 && a < 0.51 && matches!(*inner, AST::Rect{ .. })
+            )
+        );
+
+        let out = parse_geo("offset(-1){C<4>}");
+        assert!(
+            matches!(out, Ok(("", AST::Offset{ amount: Value::Float(a), inner, .. })) if
+                a < -0.99 && a > -1.01 && matches!(*inner, AST::Circle{ .. })
+            )
+        );
+    }
+
     #[test]
     fn test_negative() {
         let out = parse_geo("negative{C<2>}");
         //eprintln!("{:?}", out);
         assert!(
-            matches!(out, Ok(("", AST::Negative{ inners })) if inners.len() == 1 &&
-                matches!(&*inners[0], AST::Circle{ coords: None, radius: r, inner: None }  if
+            matches!(out, Ok(("", AST::Negative{ inners, .. })) if inners.len() == 1 &&
+                matches!(&*inners[0], AST::Circle{ coords: None, radius: r, inner: None, .. }  if
                     r.float() > 1.99 && r.float() < 2.01
                 )
             )
@@ -1605,11 +4748,11 @@ mod tests {
         let out = parse_geo("negative {\n C<2>,\n   R<4>\n\n}");
         // eprintln!("{:?}", out);
         assert!(
-            matches!(out, Ok(("", AST::Negative{ inners })) if inners.len() == 2 &&
-                matches!(&*inners[0], AST::Circle{ coords: None, radius: r, inner: None }  if
+            matches!(out, Ok(("", AST::Negative{ inners, .. })) if inners.len() == 2 &&
+                matches!(&*inners[0], AST::Circle{ coords: None, radius: r, inner: None, .. }  if
                     r.float() > 1.99 && r.float() < 2.01
                 ) &&
-                matches!(*inners[1], AST::Rect{ coords: None, size: Some((Value::Float(w), Value::Float(h))), inner: _, rounded: None }  if
+                matches!(*inners[1], AST::Rect{ coords: None, size: Some((Value::Float(w), Value::Float(h))), inner: _, rounded: None, .. }  if
                     w > 3.99 && w < 4.01 && h > 3.99 && h < 4.01
                 )
             )
@@ -1620,12 +4763,12 @@ mod tests {
     fn test_var() {
         let out = parse_geo("let bleh = C<25>");
         // eprintln!("{:?}", out);
-        assert!(matches!(out, Ok(("", AST::Assign(var, circ))) if
+        assert!(matches!(out, Ok(("", AST::Assign(var, circ, _))) if
             var == "bleh".to_string() && matches!(*circ, AST::Circle{ .. })));
 
         let out = parse_geo("$bleh");
         // eprintln!("{:?}", out);
-        assert!(matches!(out, Ok(("", AST::VarRef(var))) if var == "bleh".to_string()));
+        assert!(matches!(out, Ok(("", AST::VarRef(var, _))) if var == "bleh".to_string()));
 
         let out = build(
             "let rect = column center {
@@ -1641,30 +4784,46 @@ mod tests {
     #[test]
     fn test_err_msgs() {
         let out = build("C<a>");
-        assert!(matches!(out, Err(Err::Parse(_))));
+        assert!(matches!(out, Err(Err::Parse { line, .. }) if line > 0));
         let out = build("T<a>");
-        assert!(matches!(out, Err(Err::Parse(_))));
+        assert!(matches!(out, Err(Err::Parse { line, .. }) if line > 0));
 
         let out = build("R<@(a)>");
-        // eprintln!("\n\n{}\n\n", match out.err().unwrap() {
-        //     Err::Parse(e) => e,
-        //     _ => unreachable!(),
-        // });
-        // unreachable!();
-        assert!(matches!(out, Err(Err::Parse(_))));
+        assert!(matches!(out, Err(Err::Parse { line, .. }) if line > 0));
 
         let out = build("(aBC)");
-        assert!(matches!(out, Err(Err::Parse(_))));
+        assert!(matches!(out, Err(Err::Parse { line, .. }) if line > 0));
 
         let out = build("let bleh = !{aa$%dsfsd + 44}");
-        assert!(matches!(out, Err(Err::Parse(_))));
+        assert!(matches!(out, Err(Err::Parse { line, .. }) if line > 0));
+
+        let out = build("C<5>\nR<5>\n  T<a>");
+        assert!(
+            matches!(out, Err(Err::Parse { line: 3, col, snippet, .. }) if
+                col >= 3 && snippet.starts_with("  T<a>\n")
+            )
+        );
+    }
+
+    #[test]
+    fn test_err_display() {
+        let out = build("C<a>");
+        assert!(format!("{}", out.err().unwrap()).starts_with("parse error:"));
+        assert_eq!(
+            format!("{}", Err::UndefinedVariable("v".to_string())),
+            "undefined variable: v"
+        );
+        assert_eq!(
+            format!("{}", Err::BadType("v".to_string())),
+            "unexpected type for v"
+        );
     }
 
     #[test]
     fn test_cel() {
         let out = parse_geo("let bleh = !{44}");
-        assert!(matches!(out, Ok(("", AST::Assign(var, exp))) if
-            var == "bleh".to_string() && matches!(*exp, AST::Cel(_))));
+        assert!(matches!(out, Ok(("", AST::Assign(var, exp, _))) if
+            var == "bleh".to_string() && matches!(*exp, AST::Cel(..))));
 
         let out = build("let bleh = !{1 + 1}");
         assert!(matches!(out, Ok(_)));
@@ -1684,28 +4843,246 @@ mod tests {
         let out = build("let bleh = !{5};\nwrap (R<5>) with { left $bleh => R<2>, }");
         // eprintln!("{:?}", out);
         assert!(matches!(out, Ok(v) if v.len() == 1));
+
+        use cel_interpreter::objects::CelType;
+        let ctx = ResolverContext::default();
+        assert!(matches!(ctx.eval_cel("sqrt(4)".into()), CelType::Float(f) if f == 2.0));
+        assert!(matches!(ctx.eval_cel("floor(3.7)".into()), CelType::Float(f) if f == 3.0));
+        assert!(matches!(ctx.eval_cel("sin(0)".into()), CelType::Float(f) if f == 0.0));
+        assert!(matches!(
+            parse_geo("R<!{sqrt(2) * 5}>"),
+            Ok(("", AST::Rect { .. }))
+        ));
+        assert!(parse_geo("R<!{tan(2) * 5}>").is_err());
+    }
+
+    #[test]
+    fn test_fn_def() {
+        let out = parse_geo("fn board(w, h) = R<$w, $h>(h3)");
+        assert!(
+            matches!(out, Ok(("", AST::FnDef{ name, params, body, .. })) if
+                name == "board" && params == vec!["w".to_string(), "h".to_string()] &&
+                matches!(*body, AST::Rect{ .. })
+            )
+        );
+
+        let out = parse_geo("board(10, 5)");
+        assert!(matches!(out, Ok(("", AST::FnCall{ name, args, .. })) if
+            name == "board" && args.len() == 2 && matches!(args[1], Value::Float(f) if f == 5.0)
+        ));
+
+        let out = parse_geo("rotate(45) { C<1> }");
+        assert!(matches!(out, Ok(("", AST::Rotate { .. }))));
+        let out = parse_geo("offset(1) { C<1> }");
+        assert!(matches!(out, Ok(("", AST::Offset { .. }))));
+        let out = parse_geo("offset(1)");
+        assert!(matches!(out, Ok(("", AST::FnCall{ name, .. })) if name == "offset"));
+
+        assert!(matches!(
+            build("fn f(r) = f($r)\nf(5)"),
+            Err(Err::RecursiveFunction(name)) if name == "f"
+        ));
+        assert!(matches!(
+            build("fn f(r) = C<$r>\nf(5, 2)"),
+            Err(Err::BadArgumentCount {
+                expected: 1,
+                got: 2,
+                ..
+            })
+        ));
+        assert!(matches!(build("g(5)"), Err(Err::UndefinedFunction(name)) if name == "g"));
+    }
+
+    #[test]
+    fn test_conditional() {
+        let out = parse_geo("if !{w > 50} { R<5>(h) } else { }");
+        assert!(
+            matches!(out, Ok(("", AST::Conditional{ condition, then_branch, else_branch, .. })) if
+                condition == "w > 50" && then_branch.len() == 1 && else_branch.is_empty()
+            )
+        );
+
+        let out = parse_geo("if !{1} { C<5>, C<2> }");
+        assert!(
+            matches!(out, Ok(("", AST::Conditional{ then_branch, else_branch, .. })) if
+                then_branch.len() == 2 && else_branch.is_empty()
+            )
+        );
+
+        assert!(matches!(build("if !{0} { C<5> }"), Ok(f) if f.len() == 0));
+        assert!(matches!(build("if !{1} { C<5> }"), Ok(f) if f.len() == 1));
+        assert!(matches!(
+            build("let w = !{60}\nif !{w > 50} { C<5> } else { C<1>, C<2> }"),
+            Ok(f) if f.len() == 1
+        ));
+        assert!(matches!(
+            build("let w = !{40}\nif !{w > 50} { C<5> } else { C<1>, C<2> }"),
+            Ok(f) if f.len() == 2
+        ));
+        assert!(matches!(
+            build("let w = !{50}\nif !{w == 50 && w >= 49.5} { C<5> }"),
+            Ok(f) if f.len() == 1
+        ));
+    }
+
+    #[test]
+    fn test_for_loop() {
+        let out = parse_geo("for i in !{range(0, 5)} { R<$i, 5> % board_ }");
+        assert!(
+            matches!(out, Ok(("", AST::ForLoop{ var, range_expr, body, .. })) if
+                var == "i" && range_expr == "range(0, 5)" && body.len() == 1
+            )
+        );
+
+        use geo::bounding_rect::BoundingRect;
+        let features = build("for i in !{range(0, 3)} { C<!{i+1}> }").unwrap();
+        let widths: Vec<_> = features
+            .iter()
+            .map(|f| f.edge_union().unwrap().bounding_rect().unwrap().width())
+            .collect();
+        assert_eq!(widths.len(), 3);
+        for (w, r) in widths.into_iter().zip(&[1., 2., 3.]) {
+            assert!((w - 2. * r).abs() < 0.01);
+        }
+
+        let features = build("for i in !{range(2)} { R<5> % board_ }").unwrap();
+        let names: Vec<_> = features
+            .iter()
+            .map(|f| f.named_info())
+            .flatten()
+            .map(|n| n.name)
+            .collect();
+        assert_eq!(names, vec!["board_0".to_string(), "board_1".to_string()]);
+
+        // Names can reference the loop variable instead of the index.
+        let features = build("for i in !{range(0, 3)} { R<!{i+1}, 5> % board_$i }").unwrap();
+        let names: Vec<_> = features
+            .iter()
+            .map(|f| f.named_info())
+            .flatten()
+            .map(|n| n.name)
+            .collect();
+        assert_eq!(names, vec!["board_0", "board_1", "board_2"]);
+        assert!(matches!(
+            build("R<5> % board_$missing"),
+            Err(Err::UndefinedVariable(v)) if v == "missing"
+        ));
+    }
+
+    #[test]
+    fn test_block_comment() {
+        let out = parse_geo("/* outer /* inner */ still_outer */");
+        assert!(matches!(out, Ok(("", AST::Comment(body, _))) if
+            body == " outer /* inner */ still_outer "
+        ));
+
+        let out = ast("/* outer /* inner */ still_outer */ R<5>");
+        assert!(matches!(out, Ok(nodes) if nodes.len() == 2 &&
+            matches!(nodes[1], AST::Rect{ .. })
+        ));
+        let out = build("/* outer /* inner */ still_outer */ R<5>");
+        assert!(matches!(out, Ok(features) if features.len() == 1));
+        let out = build("negative { /* C<1> */ C<2> }");
+        assert!(matches!(out, Ok(features) if features.len() == 1));
+
+        let out = build("R<5>\n/* unclosed /* */");
+        assert!(matches!(out, Err(Err::Parse { line: 2, .. })));
+    }
+
+    #[test]
+    fn test_float_units() {
+        assert!(matches!(parse_float("5mm"), Ok(("", Value::Float(f))) if f == 5.));
+        assert!(matches!(parse_float("2in"), Ok(("", Value::Float(f))) if (f - 50.8).abs() < 1e-9));
+        assert!(matches!(parse_float("2IN"), Ok(("", Value::Float(f))) if (f - 50.8).abs() < 1e-9));
+        assert!(matches!(parse_float("2"), Ok(("", Value::Float(f))) if f == 2.));
+        assert!(matches!(parse_float("!{2}"), Ok(("", Value::Cel(exp))) if exp == "2"));
+    }
+
+    #[test]
+    fn test_import() {
+        let out = parse_geo("import \"boards/a.mps\";");
+        assert!(matches!(out, Ok(("", AST::Import(path, _))) if path == "boards/a.mps"));
+
+        let dir = std::env::temp_dir().join(format!("maker-panel-import-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("boards")).unwrap();
+        std::fs::write(dir.join("boards/a.mps"), "let board = R<5>;\nC<1>").unwrap();
+        std::fs::write(dir.join("main.mps"), "import \"boards/a.mps\"\n[2]$board").unwrap();
+        std::fs::write(dir.join("cycle1.mps"), "import \"cycle2.mps\"").unwrap();
+        std::fs::write(dir.join("cycle2.mps"), "import \"cycle1.mps\"").unwrap();
+
+        let features = build_from_file_with_layer(&dir.join("main.mps"), None).unwrap();
+        assert_eq!(features.len(), 2);
+        assert!(
+            matches!(build_from_file_with_layer(&dir.join("cycle1.mps"), None), Err(Err::Parse { message, .. }) if message.contains("circular"))
+        );
+        assert!(matches!(
+            build("import \"boards/a.mps\""),
+            Err(Err::Parse { .. })
+        ));
+        std::fs::remove_dir_all(dir).unwrap();
     }
 
     #[test]
     fn test_comment() {
         let out = parse_geo("# yooooooo");
-        assert!(matches!(out, Ok(("", AST::Comment(msg))) if
+        assert!(matches!(out, Ok(("", AST::Comment(msg, _))) if
             msg == " yooooooo"
         ));
 
         let out = parse_geo("// yeeeeeeeee");
-        assert!(matches!(out, Ok(("", AST::Comment(msg))) if
+        assert!(matches!(out, Ok(("", AST::Comment(msg, _))) if
             msg == " yeeeeeeeee"
         ));
     }
 
+    #[test]
+    fn test_mirror() {
+        let out = parse_geo("mirror(y) { R<3, 1>, C<2>(h) }");
+        assert!(
+            matches!(out, Ok(("", AST::Mirror{ axis: crate::features::MirrorAxis::Y, inners, .. })) if inners.len() == 2 &&
+                matches!(&*inners[0], AST::Rect{ .. })
+            )
+        );
+        assert!(matches!(
+            parse_geo("mirror(X){C<2>}"),
+            Ok((
+                "",
+                AST::Mirror {
+                    axis: crate::features::MirrorAxis::X,
+                    ..
+                }
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_translate() {
+        let out = parse_geo("translate(5, -2) { R<3, 1>, tab<2, 3> }");
+        assert!(
+            matches!(out, Ok(("", AST::Translate{ offset, inners, .. })) if inners.len() == 2 &&
+                matches!(offset, (Value::Float(x), Value::Float(y)) if x == 5.0 && y == -2.0)
+            )
+        );
+
+        let features = build("translate(10, 0) { C<@(1, 1), 1>(h1) }").unwrap();
+        assert_eq!(features.len(), 1);
+        use crate::features::Feature;
+        use geo::bounding_rect::BoundingRect;
+        let b = features[0].edge_union().unwrap().bounding_rect().unwrap();
+        assert!(b.center().x > 10.99 && b.center().x < 11.01);
+        assert!(features[0].interior().iter().any(|a| matches!(
+            a,
+            crate::features::InnerAtom::Drill { center, .. } if center.x > 10.99 && center.x < 11.01
+        )));
+    }
+
     #[test]
     fn test_rotate() {
         let out = parse_geo("rotate(45.0){C<2>}");
         eprintln!("{:?}", out);
         assert!(
-            matches!(out, Ok(("", AST::Rotate{ rotation, inners })) if inners.len() == 1 &&
-                matches!(&*inners[0], AST::Circle{ coords: None, radius: r, inner: None }  if
+            matches!(out, Ok(("", AST::Rotate{ rotation, inners, .. })) if inners.len() == 1 &&
+                matches!(&*inners[0], AST::Circle{ coords: None, radius: r, inner: None, .. }  if
                     r.float() > 1.99 && r.float() < 2.01
                 ) &&
                 matches!(rotation.float(), f if f < 45.01 && f > 44.99)
@@ -1717,11 +5094,32 @@ mod tests {
     fn test_name() {
         let out = parse_geo("C<2> % circle1");
         eprintln!("{:?}", out);
-        assert!(matches!(out, Ok(("", AST::Name{ name, inner })) if
-            matches!(&*inner, AST::Circle{ coords: None, radius: r, inner: None }  if
+        assert!(matches!(out, Ok(("", AST::Name{ name, inner, .. })) if
+            matches!(&*inner, AST::Circle{ coords: None, radius: r, inner: None, .. }  if
                 r.float() > 1.99 && r.float() < 2.01
             ) &&
             name == "circle1"
         ));
     }
+
+    #[test]
+    fn test_ast() {
+        let out = ast("R<5> % myRect");
+        assert!(matches!(out, Ok(ref nodes) if nodes.len() == 1 &&
+            matches!(&nodes[0], AST::Name{ name, inner, span: (0, 13) } if
+                name == "myRect" && matches!(**inner, AST::Rect{ span: (0, 4), .. })
+            )
+        ));
+
+        let out = ast("let r = R<5>\n  C<2>");
+        assert!(matches!(out, Ok(ref nodes) if nodes.len() == 2 &&
+            matches!(&nodes[0], AST::Assign(var, inner, (0, 12)) if
+                var == "r" && matches!(**inner, AST::Rect{ span: (8, 12), .. })
+            ) &&
+            matches!(nodes[1], AST::Circle{ span: (15, 19), .. })
+        ));
+
+        let out = ast("R<a>");
+        assert!(matches!(out, Err(Err::Parse { .. })));
+    }
 }
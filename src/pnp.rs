@@ -0,0 +1,108 @@
+//! Generates pick-and-place (component position) files.
+
+use super::features::NamedInfo;
+use super::{InnerAtom, Side};
+
+/// Returns true if the name looks like a component reference designator,
+/// such as `U1`, `C12`, or `SW3`.
+pub fn is_component(name: &str) -> bool {
+    let letters = name.chars().take_while(|c| c.is_ascii_uppercase()).count();
+    let digits = name[letters..]
+        .chars()
+        .filter(|c| c.is_ascii_digit())
+        .count();
+    letters > 0 && digits > 0 && letters + digits == name.len()
+}
+
+/// Infers which side of the board a component is mounted on, based on
+/// which side most of the layered atoms within its bounds are on.
+pub fn side(bounds: &geo::Rect<f64>, atoms: &[InnerAtom]) -> Side {
+    let within = |p: geo::Coordinate<f64>| {
+        p.x >= bounds.min().x
+            && p.x <= bounds.max().x
+            && p.y >= bounds.min().y
+            && p.y <= bounds.max().y
+    };
+
+    let (front, back) = atoms
+        .iter()
+        .filter_map(|a| match a {
+            InnerAtom::Circle { center, layer, .. } if within(*center) => layer.side(),
+            InnerAtom::Rect { rect, layer } if within(rect.center()) => layer.side(),
+            _ => None,
+        })
+        .fold((0, 0), |(front, back), side| match side {
+            Side::Front => (front + 1, back),
+            Side::Back => (front, back + 1),
+        });
+
+    if back > front {
+        Side::Back
+    } else {
+        Side::Front
+    }
+}
+
+pub fn serialize<W: std::io::Write>(
+    named: &[NamedInfo],
+    atoms: &[InnerAtom],
+    w: &mut W,
+) -> Result<(), std::io::Error> {
+    w.write_all(b"Reference,X,Y,Rotation,Side\n")?;
+
+    for info in named.iter().filter(|i| is_component(&i.name)) {
+        let center = info.bounds.center();
+        w.write_all(
+            format!(
+                "{},{:.4},{:.4},{:.4},{}\n",
+                info.name,
+                center.x,
+                center.y,
+                0.,
+                side(&info.bounds, atoms)
+            )
+            .as_bytes(),
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Layer;
+
+    #[test]
+    fn component_names() {
+        assert!(is_component("U1"));
+        assert!(is_component("SW12"));
+        assert!(!is_component("U"));
+        assert!(!is_component("12"));
+        assert!(!is_component("inner0"));
+        assert!(!is_component("U1a"));
+    }
+
+    #[test]
+    fn side_inference() {
+        let bounds = geo::Rect::new([0., 0.], [2., 2.]);
+        let atoms = vec![
+            InnerAtom::Circle {
+                center: [1., 1.].into(),
+                radius: 0.5,
+                layer: Layer::BackCopper,
+            },
+            InnerAtom::Circle {
+                center: [1., 1.].into(),
+                radius: 0.5,
+                layer: Layer::BackMask,
+            },
+            InnerAtom::Circle {
+                center: [5., 5.].into(),
+                radius: 0.5,
+                layer: Layer::FrontCopper,
+            },
+        ];
+        assert_eq!(side(&bounds, &atoms), Side::Back);
+        assert_eq!(side(&bounds, &vec![]), Side::Front);
+    }
+}
@@ -0,0 +1,153 @@
+//! Design rules imposed by fab houses, and violations of them.
+
+use super::InnerAtom;
+use geo::Coordinate;
+use std::fmt;
+
+/// Describes the manufacturing limits of a fab house. All values are
+/// in millimeters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DesignRules {
+    /// The smallest drill which can be made.
+    pub min_drill_diameter_mm: f64,
+    /// The minimum distance between the wall of a drill and the edge
+    /// of the board.
+    pub min_edge_clearance_mm: f64,
+    /// The minimum gap between separate pieces of copper.
+    pub min_copper_clearance_mm: f64,
+    /// The minimum distance between the walls of two drills.
+    pub min_drill_spacing_mm: f64,
+}
+
+impl Default for DesignRules {
+    /// Conservative rules which most fab houses can meet.
+    fn default() -> Self {
+        Self {
+            min_drill_diameter_mm: 0.3,
+            min_edge_clearance_mm: 0.3,
+            min_copper_clearance_mm: 0.15,
+            min_drill_spacing_mm: 0.5,
+        }
+    }
+}
+
+impl std::str::FromStr for DesignRules {
+    type Err = String;
+
+    /// Parses rules from flat `key = value` lines, such as from a simple
+    /// TOML file. Rules which are not specified take their default value.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut rules = Self::default();
+
+        for (i, line) in s.lines().enumerate() {
+            let line = line.split('#').next().unwrap().trim();
+            if line.is_empty() || line.starts_with('[') {
+                continue;
+            }
+
+            let mut parts = line.splitn(2, '=');
+            let (key, value) = match (parts.next(), parts.next()) {
+                (Some(k), Some(v)) => (k.trim(), v.trim()),
+                _ => return Err(format!("line {}: expected key = value", i + 1)),
+            };
+            let value: f64 = value
+                .parse()
+                .map_err(|_| format!("line {}: invalid number {:?}", i + 1, value))?;
+
+            match key {
+                "min_drill_diameter_mm" => rules.min_drill_diameter_mm = value,
+                "min_edge_clearance_mm" => rules.min_edge_clearance_mm = value,
+                "min_copper_clearance_mm" => rules.min_copper_clearance_mm = value,
+                "min_drill_spacing_mm" => rules.min_drill_spacing_mm = value,
+                _ => return Err(format!("line {}: unknown rule {:?}", i + 1, key)),
+            }
+        }
+        Ok(rules)
+    }
+}
+
+/// A way in which a panel does not meet a set of design rules.
+#[derive(Debug, Clone)]
+pub enum Violation {
+    TooSmallDrill {
+        diameter: f64,
+        center: Coordinate<f64>,
+    },
+    TooCloseToEdge {
+        atom: InnerAtom,
+        distance: f64,
+    },
+    DrillsTooClose {
+        a: Coordinate<f64>,
+        b: Coordinate<f64>,
+        distance: f64,
+    },
+    CopperTooClose {
+        a: InnerAtom,
+        b: InnerAtom,
+        distance: f64,
+    },
+}
+
+fn center(atom: &InnerAtom) -> Coordinate<f64> {
+    match atom.bounds() {
+        Some(b) => b.center(),
+        None => Coordinate { x: 0., y: 0. },
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Violation::TooSmallDrill { diameter, center } => write!(
+                f,
+                "drill at ({:.3}, {:.3}) is too small: {:.3}mm",
+                center.x, center.y, diameter
+            ),
+            Violation::TooCloseToEdge { atom, distance } => {
+                let c = center(atom);
+                write!(
+                    f,
+                    "drill at ({:.3}, {:.3}) is too close to the edge: {:.3}mm",
+                    c.x, c.y, distance
+                )
+            }
+            Violation::DrillsTooClose { a, b, distance } => write!(
+                f,
+                "drills at ({:.3}, {:.3}) and ({:.3}, {:.3}) are too close: {:.3}mm",
+                a.x, a.y, b.x, b.y, distance
+            ),
+            Violation::CopperTooClose { a, b, distance } => {
+                let (a, b) = (center(a), center(b));
+                write!(
+                    f,
+                    "copper at ({:.3}, {:.3}) and ({:.3}, {:.3}) is too close: {:.3}mm",
+                    a.x, a.y, b.x, b.y, distance
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let rules: DesignRules =
+            "# PCBWay\n[rules]\nmin_drill_diameter_mm = 0.2\nmin_edge_clearance_mm=0.4 # ok"
+                .parse()
+                .unwrap();
+        assert_eq!(rules.min_drill_diameter_mm, 0.2);
+        assert_eq!(rules.min_edge_clearance_mm, 0.4);
+        assert_eq!(
+            rules.min_copper_clearance_mm,
+            DesignRules::default().min_copper_clearance_mm
+        );
+
+        assert!("min_drill_diameter_mm = x".parse::<DesignRules>().is_err());
+        assert!("max_layers = 2".parse::<DesignRules>().is_err());
+        assert!("min_drill_diameter_mm".parse::<DesignRules>().is_err());
+    }
+}
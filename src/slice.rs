@@ -0,0 +1,137 @@
+//! Clips features to one side of a cut line, for splitting a panel.
+
+use super::features::{Feature, InnerAtom, NamedInfo};
+use geo::{Coordinate, MultiPolygon};
+use std::fmt;
+
+/// One side of a horizontal or vertical cut line.
+#[derive(Debug, Clone, Copy)]
+pub enum HalfPlane {
+    /// The region where y is less than the given value.
+    Above(f64),
+    /// The region where y is greater than the given value.
+    Below(f64),
+    /// The region where x is less than the given value.
+    Left(f64),
+    /// The region where x is greater than the given value.
+    Right(f64),
+}
+
+impl HalfPlane {
+    fn contains(&self, c: Coordinate<f64>) -> bool {
+        match self {
+            HalfPlane::Above(y) => c.y < *y,
+            HalfPlane::Below(y) => c.y >= *y,
+            HalfPlane::Left(x) => c.x < *x,
+            HalfPlane::Right(x) => c.x >= *x,
+        }
+    }
+
+    pub fn contains_atom(&self, a: &InnerAtom) -> bool {
+        match (self, a) {
+            (HalfPlane::Above(cut), InnerAtom::VScoreH(y)) => y < cut,
+            (HalfPlane::Below(cut), InnerAtom::VScoreH(y)) => y > cut,
+            (HalfPlane::Left(cut), InnerAtom::VScoreV(x)) => x < cut,
+            (HalfPlane::Right(cut), InnerAtom::VScoreV(x)) => x > cut,
+            (_, InnerAtom::VScoreH(_)) | (_, InnerAtom::VScoreV(_)) => true,
+            _ => match a.bounds() {
+                Some(b) => self.contains(b.center()),
+                None => false,
+            },
+        }
+    }
+
+    /// Returns the part of the geometry which lies within the half-plane.
+    fn clip(&self, poly: MultiPolygon<f64>) -> Option<MultiPolygon<f64>> {
+        use geo::bounding_rect::BoundingRect;
+        use geo_booleanop::boolean::BooleanOp;
+
+        let b = poly.bounding_rect()?;
+        let (min, max): (Coordinate<f64>, Coordinate<f64>) = match self {
+            HalfPlane::Above(y) => (b.min(), [b.max().x, (*y).min(b.max().y)].into()),
+            HalfPlane::Below(y) => ([b.min().x, (*y).max(b.min().y)].into(), b.max()),
+            HalfPlane::Left(x) => (b.min(), [(*x).min(b.max().x), b.max().y].into()),
+            HalfPlane::Right(x) => ([(*x).max(b.min().x), b.min().y].into(), b.max()),
+        };
+        if min.x >= max.x || min.y >= max.y {
+            return None;
+        }
+
+        let out = poly.intersection(&geo::Rect::new(min, max).to_polygon());
+        if out.0.len() > 0 {
+            Some(out)
+        } else {
+            None
+        }
+    }
+
+    fn translate(&mut self, v: Coordinate<f64>) {
+        match self {
+            HalfPlane::Above(y) | HalfPlane::Below(y) => *y += v.y,
+            HalfPlane::Left(x) | HalfPlane::Right(x) => *x += v.x,
+        }
+    }
+}
+
+/// A feature which only keeps the geometry of its inner feature
+/// that lies on one side of a cut line.
+#[derive(Debug, Clone)]
+pub struct Slice<'a> {
+    inner: Box<dyn Feature + 'a>,
+    keep: HalfPlane,
+}
+
+impl<'a> Slice<'a> {
+    pub fn new(inner: Box<dyn Feature + 'a>, keep: HalfPlane) -> Self {
+        Self { inner, keep }
+    }
+}
+
+impl fmt::Display for Slice<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "slice({:?}, {})", self.keep, self.inner)
+    }
+}
+
+impl Feature for Slice<'_> {
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn translate(&mut self, v: Coordinate<f64>) {
+        self.inner.translate(v);
+        self.keep.translate(v);
+    }
+
+    fn edge_union(&self) -> Option<MultiPolygon<f64>> {
+        self.keep.clip(self.inner.edge_union()?)
+    }
+
+    fn edge_subtract(&self) -> Option<MultiPolygon<f64>> {
+        self.keep.clip(self.inner.edge_subtract()?)
+    }
+
+    fn interior(&self) -> Vec<InnerAtom> {
+        self.inner
+            .interior()
+            .into_iter()
+            .filter(|a| self.keep.contains_atom(a))
+            .collect()
+    }
+
+    fn named_info(&self) -> Vec<NamedInfo> {
+        self.inner
+            .named_info()
+            .into_iter()
+            .filter(|info| self.keep.contains(info.bounds.center()))
+            .collect()
+    }
+
+    fn parts(&self) -> Vec<&'static str> {
+        self.inner.parts()
+    }
+
+    fn all_names(&self) -> Vec<&'static str> {
+        self.inner.all_names()
+    }
+}
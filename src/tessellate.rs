@@ -103,7 +103,123 @@ pub fn normals_from_tessellation(verts: &Vec<[f64; 3]>, inds: &Vec<u16>) -> Vec<
         .collect()
 }
 
-pub fn tessellate_3d(buffer: VertexBuffers<Point, u16>) -> (Vec<[f64; 3]>, Vec<u16>) {
+/// A vertex of a 2d tessellation, along with texture coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PointWithUV {
+    pub x: f32,
+    pub y: f32,
+    pub u: f32,
+    pub v: f32,
+}
+
+/// Tessellates the polygon as [`tessellate_2d`] does, additionally computing
+/// texture coordinates for each vertex by normalizing its position to the
+/// bounding box of the polygon.
+pub fn tessellate_2d_with_uv(
+    poly: geo::Polygon<f64>,
+    interior: Vec<InnerAtom>,
+) -> Result<VertexBuffers<PointWithUV, u16>, TessellationError> {
+    use geo::bounding_rect::BoundingRect;
+    let bounds = poly.bounding_rect();
+    let buffers = tessellate_2d(poly, interior)?;
+
+    let (min, w, h) = match bounds {
+        Some(b) => (b.min(), b.width() as f32, b.height() as f32),
+        None => ([0., 0.].into(), 1., 1.),
+    };
+    let norm = |v: f32, min: f64, extent: f32| {
+        if extent > 0. {
+            (v - min as f32) / extent
+        } else {
+            0.
+        }
+    };
+
+    Ok(VertexBuffers {
+        vertices: buffers
+            .vertices
+            .iter()
+            .map(|p| PointWithUV {
+                x: p.x,
+                y: p.y,
+                u: norm(p.x, min.x, w),
+                v: norm(p.y, min.y, h),
+            })
+            .collect(),
+        indices: buffers.indices,
+    })
+}
+
+/// Computes a normal for each vertex, by averaging the normals of the
+/// faces which share that vertex. Unlike [`normals_from_tessellation`],
+/// the returned normals are of unit length.
+pub fn smooth_normals_from_tessellation(verts: &[[f64; 3]], inds: &[u16]) -> Vec<[f32; 3]> {
+    let mut sums = vec![[0f64; 3]; verts.len()];
+    for tri in inds.chunks_exact(3) {
+        let (a, b, c) = (
+            verts[tri[0] as usize],
+            verts[tri[1] as usize],
+            verts[tri[2] as usize],
+        );
+        let u = [b[0] - a[0], b[1] - a[1], b[2] - a[2]];
+        let v = [c[0] - a[0], c[1] - a[1], c[2] - a[2]];
+        // Larger faces contribute more, as the cross product is not normalized.
+        let normal = [
+            (u[1] * v[2]) - (u[2] * v[1]),
+            (u[2] * v[0]) - (u[0] * v[2]),
+            (u[0] * v[1]) - (u[1] * v[0]),
+        ];
+        for idx in tri {
+            for (s, n) in sums[*idx as usize].iter_mut().zip(&normal) {
+                *s += n;
+            }
+        }
+    }
+
+    sums.into_iter()
+        .map(|n| {
+            let len = (n[0] * n[0] + n[1] * n[1] + n[2] * n[2]).sqrt();
+            if len > 0. {
+                [
+                    (n[0] / len) as f32,
+                    (n[1] / len) as f32,
+                    (n[2] / len) as f32,
+                ]
+            } else {
+                [0., 0., 0.]
+            }
+        })
+        .collect()
+}
+
+/// Writes a binary STL file describing the given triangles.
+pub fn serialize_stl<W: std::io::Write>(
+    verts: &Vec<[f64; 3]>,
+    inds: &Vec<u16>,
+    w: &mut W,
+) -> Result<(), std::io::Error> {
+    let vertex = |idx: u16| {
+        let v = verts[idx as usize];
+        stl_io::Vertex::new([v[0] as f32, v[1] as f32, v[2] as f32])
+    };
+    let normals = normals_from_tessellation(verts, inds);
+    let mesh: Vec<_> = inds
+        .chunks_exact(3)
+        .zip(normals)
+        .map(|(tri, normal)| stl_io::Triangle {
+            normal: stl_io::Normal::new(normal),
+            vertices: [vertex(tri[0]), vertex(tri[1]), vertex(tri[2])],
+        })
+        .collect();
+    stl_io::write_stl(w, mesh.iter())
+}
+
+/// Extrudes a 2d tessellation into a solid of the given thickness,
+/// centered on the XY plane.
+pub fn tessellate_3d(
+    buffer: VertexBuffers<Point, u16>,
+    thickness: f64,
+) -> (Vec<[f64; 3]>, Vec<u16>) {
     // eprintln!("buffer: {:?} ({})", buffer, buffer.vertices.chunks_exact(3).count());
 
     // Iterate through the edges represented by the indices, building a map
@@ -148,10 +264,10 @@ pub fn tessellate_3d(buffer: VertexBuffers<Point, u16>) -> (Vec<[f64; 3]>, Vec<u
     let mut vertices: Vec<[f64; 3]> =
         Vec::with_capacity(2 * buffer.vertices.len() + 6 * boundary_lines.len());
     for v in &buffer.vertices {
-        vertices.push([v.x.into(), v.y.into(), -0.8]);
+        vertices.push([v.x.into(), v.y.into(), -thickness / 2.]);
     }
     for v in &buffer.vertices {
-        vertices.push([v.x.into(), v.y.into(), 0.8]);
+        vertices.push([v.x.into(), v.y.into(), thickness / 2.]);
     }
 
     // Compute the vertices: the front and back faces are easy - we just duplicate
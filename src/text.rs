@@ -1,5 +1,14 @@
 const FONT_DATA: &'static [u8] = include_bytes!("../font6x8_1bpp.raw");
 
+/// Height of a span rendered by [`blit_text_span`], in drawing units.
+pub(crate) const FONT_HEIGHT: f64 = 1.0;
+
+/// Width of a span rendered by [`blit_text_span`] with the given number
+/// of characters, in drawing units. Each character is 6x8 pixels.
+pub(crate) fn text_width(chars: usize) -> f64 {
+    chars as f64 * 6. / 8. * FONT_HEIGHT
+}
+
 /// Returns the value of a pixel in a character in the font.
 pub(crate) fn character_pixel(c: char, x: u32, y: u32) -> bool {
     let char_per_row = 240 / 6;
@@ -67,7 +76,7 @@ pub fn blit_text_span(x: f64, y: f64, text: &str) -> usvg::Image {
         id: "".to_string(),
         transform: usvg::Transform::new_translate(x, y),
         view_box: usvg::ViewBox {
-            rect: usvg::Rect::new(0., 0., text.len() as f64, 1.0).unwrap(),
+            rect: usvg::Rect::new(0., 0., text_width(text.len()), FONT_HEIGHT).unwrap(),
             aspect: usvg::AspectRatio::default(),
         },
         visibility: usvg::Visibility::Visible,
@@ -0,0 +1,116 @@
+//! Converts geometry to and from Well-Known Text (WKT).
+
+use geo::{Coordinate, LineString, MultiPolygon, Polygon};
+use nom::branch::alt;
+use nom::bytes::complete::{tag, tag_no_case};
+use nom::character::complete::{multispace0, multispace1};
+use nom::combinator::{all_consuming, map};
+use nom::error::VerboseError;
+use nom::multi::separated_list1;
+use nom::number::complete::double;
+use nom::sequence::{delimited, preceded, separated_pair, tuple};
+use nom::IResult;
+
+fn ring(ls: &LineString<f64>) -> String {
+    let points: Vec<_> = ls
+        .points_iter()
+        .map(|p| format!("{} {}", p.x(), p.y()))
+        .collect();
+    format!("({})", points.join(", "))
+}
+
+/// Formats the geometry as a WKT `MULTIPOLYGON`.
+pub fn format(poly: &MultiPolygon<f64>) -> String {
+    let polys: Vec<_> = poly
+        .iter()
+        .map(|p| {
+            let rings: Vec<_> = std::iter::once(p.exterior())
+                .chain(p.interiors().iter())
+                .map(ring)
+                .collect();
+            format!("({})", rings.join(", "))
+        })
+        .collect();
+    format!("MULTIPOLYGON ({})", polys.join(", "))
+}
+
+fn sep(i: &str) -> IResult<&str, (), VerboseError<&str>> {
+    map(tuple((multispace0, tag(","), multispace0)), |_| ())(i)
+}
+
+fn parens<'a, O, F>(inner: F) -> impl FnMut(&'a str) -> IResult<&'a str, O, VerboseError<&'a str>>
+where
+    F: FnMut(&'a str) -> IResult<&'a str, O, VerboseError<&'a str>>,
+{
+    delimited(
+        tuple((multispace0, tag("("), multispace0)),
+        inner,
+        tuple((multispace0, tag(")"))),
+    )
+}
+
+fn parse_ring(i: &str) -> IResult<&str, LineString<f64>, VerboseError<&str>> {
+    map(
+        parens(separated_list1(
+            sep,
+            map(separated_pair(double, multispace1, double), |(x, y)| {
+                Coordinate { x, y }
+            }),
+        )),
+        LineString,
+    )(i)
+}
+
+fn parse_polygon(i: &str) -> IResult<&str, Polygon<f64>, VerboseError<&str>> {
+    map(parens(separated_list1(sep, parse_ring)), |mut rings| {
+        let exterior = rings.remove(0);
+        Polygon::new(exterior, rings)
+    })(i)
+}
+
+/// Parses a WKT `POLYGON` or `MULTIPOLYGON`.
+pub fn parse(i: &str) -> Result<MultiPolygon<f64>, String> {
+    let res: IResult<&str, MultiPolygon<f64>, VerboseError<&str>> = all_consuming(delimited(
+        multispace0,
+        alt((
+            map(
+                preceded(
+                    tag_no_case("MULTIPOLYGON"),
+                    parens(separated_list1(sep, parse_polygon)),
+                ),
+                MultiPolygon,
+            ),
+            map(preceded(tag_no_case("POLYGON"), parse_polygon), |p| {
+                MultiPolygon(vec![p])
+            }),
+        )),
+        multispace0,
+    ))(i);
+
+    match res {
+        Ok((_, poly)) => Ok(poly),
+        Err(e) => Err(format!("{:?}", e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let want = "MULTIPOLYGON (((0 0, 4 0, 4 4, 0 4, 0 0), (1 1, 2 1, 2 2, 1 1)))";
+        let poly = parse(want).unwrap();
+        assert_eq!(poly.0.len(), 1);
+        assert_eq!(poly.0[0].interiors().len(), 1);
+        assert_eq!(format(&poly), want);
+    }
+
+    #[test]
+    fn polygon() {
+        let poly = parse("polygon((0 0,1.5 0,1.5 -1,0 0))").unwrap();
+        assert_eq!(poly.0.len(), 1);
+        assert_eq!(poly.0[0].exterior().0[2], Coordinate { x: 1.5, y: -1. });
+        assert!(parse("POLYGON ((0 0, 1 0").is_err());
+    }
+}
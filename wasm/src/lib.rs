@@ -1,4 +1,4 @@
-use maker_panel::{features::InnerAtom, Layer, Panel, SpecErr};
+use maker_panel::{features::InnerAtom, DrillInfo, Layer, Panel, SpecErr};
 use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 
@@ -7,9 +7,21 @@ mod version;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(remote = "SpecErr")]
 pub enum Err {
-    Parse(String),
+    Parse {
+        message: String,
+        line: usize,
+        col: usize,
+        snippet: String,
+    },
     UndefinedVariable(String),
     BadType(String),
+    UndefinedFunction(String),
+    RecursiveFunction(String),
+    BadArgumentCount {
+        function: String,
+        expected: usize,
+        got: usize,
+    },
 }
 
 #[derive(Serialize, Deserialize)]
@@ -36,6 +48,8 @@ pub struct Render {
     pub inners: Vec<Vec<(f64, f64)>>,
     pub surface_features: Vec<Surface>,
     pub named_features: Vec<NamedInfo>,
+    /// Thickness of the board in millimeters, for extruding the outline.
+    pub thickness: f64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -58,20 +72,21 @@ pub enum Surface {
     },
 }
 
+impl From<DrillInfo> for Surface {
+    fn from(d: DrillInfo) -> Self {
+        Surface::Drill {
+            radius: d.diameter / 2.,
+            plated: d.plated,
+            center: d.center.x_y(),
+        }
+    }
+}
+
 impl std::convert::TryFrom<&InnerAtom> for Surface {
     type Error = ();
 
     fn try_from(a: &InnerAtom) -> Result<Self, Self::Error> {
         match a {
-            InnerAtom::Drill {
-                center,
-                radius,
-                plated,
-            } => Ok(Surface::Drill {
-                radius: *radius,
-                plated: *plated,
-                center: (center.x_y().0, center.x_y().1),
-            }),
             InnerAtom::Circle {
                 center,
                 radius,
@@ -86,22 +101,16 @@ impl std::convert::TryFrom<&InnerAtom> for Surface {
     }
 }
 
-#[wasm_bindgen]
-pub fn render(spec: &str, convex_hull: bool) -> JsValue {
-    let mut panel = Panel::new();
-    if let Err(e) = panel.push_spec(spec) {
-        return JsValue::from_serde(&SpecErrHelper(e)).unwrap();
-    }
-    panel.convex_hull(convex_hull);
+fn render_panel(panel: &Panel) -> Render {
     let edge = panel.edge_geometry();
     if edge.is_none() {
-        return JsValue::from_serde(&Render {
+        return Render {
             outer: vec![],
             inners: vec![],
             named_features: vec![],
             surface_features: vec![],
-        })
-        .unwrap();
+            thickness: panel.board_thickness(),
+        };
     }
 
     let named: Vec<_> = panel
@@ -121,6 +130,7 @@ pub fn render(spec: &str, convex_hull: bool) -> JsValue {
         .unwrap()
         .iter()
         .map(|p| Render {
+            thickness: panel.board_thickness(),
             named_features: named.clone(),
             outer: p.exterior().points_iter().map(|p| p.x_y()).collect(),
             inners: p
@@ -129,14 +139,159 @@ pub fn render(spec: &str, convex_hull: bool) -> JsValue {
                 .map(|l| l.points_iter().map(|p| p.x_y()).collect())
                 .collect(),
             surface_features: panel
-                .interior_geometry()
-                .iter()
-                .map(|f| Surface::try_from(f))
-                .filter(|f| f.is_ok())
-                .map(|f| f.unwrap())
+                .iter_drills()
+                .map(Surface::from)
+                .chain(
+                    panel
+                        .interior_geometry()
+                        .iter()
+                        .map(|f| Surface::try_from(f))
+                        .filter(|f| f.is_ok())
+                        .map(|f| f.unwrap()),
+                )
                 .collect(),
         })
         .collect();
 
-    JsValue::from_serde(&polys[0]).unwrap()
+    polys.into_iter().next().unwrap()
+}
+
+#[wasm_bindgen]
+pub fn render(spec: &str, convex_hull: bool, thickness: Option<f64>) -> JsValue {
+    let mut panel = Panel::new();
+    if let Err(e) = panel.push_spec(spec) {
+        return JsValue::from_serde(&SpecErrHelper(e)).unwrap();
+    }
+    panel.convex_hull(convex_hull);
+    if let Some(t) = thickness {
+        panel.set_board_thickness(t);
+    }
+    JsValue::from_serde(&render_panel(&panel)).unwrap()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenderWithUV {
+    #[serde(flatten)]
+    pub render: Render,
+    /// Vertices of the tessellated panel, as (x, y, u, v).
+    pub vertices: Vec<(f32, f32, f32, f32)>,
+    pub indices: Vec<u16>,
+}
+
+#[wasm_bindgen]
+pub fn render_with_uv(spec: &str, convex_hull: bool) -> JsValue {
+    let mut panel = Panel::new();
+    if let Err(e) = panel.push_spec(spec) {
+        return JsValue::from_serde(&SpecErrHelper(e)).unwrap();
+    }
+    panel.convex_hull(convex_hull);
+
+    let (vertices, indices) = match panel.tessellate_2d_with_uv() {
+        Ok(b) => (
+            b.vertices.iter().map(|p| (p.x, p.y, p.u, p.v)).collect(),
+            b.indices,
+        ),
+        Err(_) => (vec![], vec![]),
+    };
+    JsValue::from_serde(&RenderWithUV {
+        render: render_panel(&panel),
+        vertices,
+        indices,
+    })
+    .unwrap()
+}
+
+#[wasm_bindgen]
+pub fn layer_paths(spec: &str, layer: &str) -> JsValue {
+    let mut panel = Panel::new();
+    if let Err(e) = panel.push_spec(spec) {
+        return JsValue::from_serde(&SpecErrHelper(e)).unwrap();
+    }
+    let layer: Layer = match layer.parse() {
+        Ok(l) => l,
+        Err(_) => return JsValue::undefined(),
+    };
+
+    match panel.layer_svg_paths(layer) {
+        Ok(paths) => JsValue::from_serde(&paths).unwrap(),
+        Err(_) => JsValue::undefined(),
+    }
+}
+
+#[wasm_bindgen]
+pub fn bounding_box(spec: &str) -> JsValue {
+    let mut panel = Panel::new();
+    if let Err(e) = panel.push_spec(spec) {
+        return JsValue::from_serde(&SpecErrHelper(e)).unwrap();
+    }
+    match panel.bounding_box() {
+        Some(b) => JsValue::from_serde(&(b.min().x, b.min().y, b.max().x, b.max().y)).unwrap(),
+        None => JsValue::undefined(),
+    }
+}
+
+#[wasm_bindgen]
+pub fn has_layer(spec: &str, layer: &str) -> bool {
+    let mut panel = Panel::new();
+    if panel.push_spec(spec).is_err() {
+        return false;
+    }
+    match layer.parse() {
+        Ok(layer) => panel.has_layer(layer),
+        Err(_) => false,
+    }
+}
+
+#[wasm_bindgen]
+pub fn non_empty_layers(spec: &str) -> JsValue {
+    let mut panel = Panel::new();
+    if let Err(e) = panel.push_spec(spec) {
+        return JsValue::from_serde(&SpecErrHelper(e)).unwrap();
+    }
+    let layers: Vec<String> = panel
+        .non_empty_layers()
+        .iter()
+        .map(|l| l.to_string())
+        .collect();
+    JsValue::from_serde(&layers).unwrap()
+}
+
+#[wasm_bindgen]
+pub fn render_svg(spec: &str) -> String {
+    let mut panel = Panel::new();
+    if panel.push_spec(spec).is_err() {
+        return String::new();
+    }
+    panel.to_svg_string().unwrap_or_default()
+}
+
+#[wasm_bindgen]
+pub fn render_svg_bytes(spec: &str) -> Vec<u8> {
+    let mut panel = Panel::new();
+    if panel.push_spec(spec).is_err() {
+        return vec![];
+    }
+    let mut out = Vec::new();
+    match panel.serialize_svg(&mut out) {
+        Ok(()) => out,
+        Err(_) => vec![],
+    }
+}
+
+#[wasm_bindgen]
+pub fn gerbers_zip(spec: &str) -> Vec<u8> {
+    let mut panel = Panel::new();
+    if panel.push_spec(spec).is_err() {
+        return vec![];
+    }
+    panel.export_gerbers_zip().unwrap_or_default()
+}
+
+#[wasm_bindgen]
+pub fn render_png(spec: &str, width: u32) -> Vec<u8> {
+    let mut panel = Panel::new();
+    if panel.push_spec(spec).is_err() {
+        return vec![];
+    }
+    panel.make_png_bytes(width).unwrap_or_default()
 }